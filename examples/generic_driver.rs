@@ -5,6 +5,7 @@ use env_logger::{
     Target,
 };
 use log::LevelFilter;
+use scraplirs::defaults::Defaults;
 use scraplirs::driver::{
     GenericDriver,
     GenericDriverBuilder,
@@ -38,19 +39,24 @@ fn enable_logging() {
 
 /// Build and return the generic driver object. Note that the builder can be chained without having
 /// to re-assign but this is broken up more just for (hopefully) clarity.
+///
+/// Rather than repeating user/password/ssh options in every script, these can also come from a
+/// defaults file (pointed to by `SCRAPLI_DEFAULTS_FILE`) and/or `SCRAPLI_*` environment variables
+/// (ex: `SCRAPLI_USER`, `SCRAPLI_PASSWORD`) -- explicit builder calls always win over those.
 fn setup_connection() -> GenericDriver {
-    let mut driver_builder = GenericDriverBuilder::new(HOST.to_string());
+    let defaults = Defaults::load().expect("failed loading driver defaults");
 
-    // user/password, obviously can skip if you're authing w/ a key/key from ssh config file
-    driver_builder = driver_builder
-        .user(USER.to_string())
-        .password(PASSWORD.to_string());
+    let mut driver_builder = GenericDriverBuilder::new_with_defaults(HOST, defaults);
+
+    // user/password, obviously can skip if you're authing w/ a key/key from ssh config file (or if
+    // you've set them via the defaults file/environment)
+    driver_builder = driver_builder.user(USER).password(PASSWORD);
 
     // we'll disable strict key checking too
     driver_builder = driver_builder.ssh_strict_key(false);
 
     // you can either set ssh config file path or in our case just point to /dev/null
-    driver_builder = driver_builder.ssh_config_file_path(DEV_NULL.to_string());
+    driver_builder = driver_builder.ssh_config_file_path(DEV_NULL);
 
     // depending on your device and if you use a config file with this already set or not, you may
     // need to pass some key type/kex/key algos...
@@ -67,6 +73,9 @@ fn setup_connection() -> GenericDriver {
         ),
     ]);
 
+    // when in doubt about which layer an option came from, the builder can tell you
+    println!("resolved options:\n{}", driver_builder.resolved_options());
+
     driver_builder.build()
 }
 
@@ -1,5 +1,7 @@
 mod args;
+mod auth_responder;
 mod authenticate;
+mod cancel;
 #[allow(clippy::module_inception)]
 mod channel;
 mod constants;
@@ -13,7 +15,14 @@ mod util;
 mod write;
 
 pub use args::Args;
+pub use auth_responder::{
+    AuthResponder,
+    AuthResponseCallback,
+    AuthResponseSource,
+};
+pub use cancel::CancelHandle;
 pub use channel::Channel;
 pub use operation::Options as OperationOptions;
+pub use patterns::default_auth_fatal_patterns;
 pub use send_interactive::Event as SendInteractiveEvent;
 pub use send_interactive::Events as SendInteractiveEvents;
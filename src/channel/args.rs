@@ -1,16 +1,22 @@
 use crate::channel::patterns::{
+    default_auth_fatal_patterns,
     default_auth_passphrase_pattern,
     default_auth_password_pattern,
     default_auth_username_pattern,
     default_comms_prompt_pattern,
 };
 
+use super::auth_responder::AuthResponder;
 use super::constants::{
+    DEFAULT_CONSOLE_LOGOUT_COMMAND,
+    DEFAULT_CONSOLE_RETURN_INTERVAL,
     DEFAULT_PROMPT_SEARCH_DEPTH,
     DEFAULT_READ_DELAY,
     DEFAULT_RETURN_CHAR,
+    DEFAULT_TIMEOUT_OPEN,
     DEFAULT_TIMEOUT_OPS,
 };
+use crate::credentials::Credential;
 use core::time::Duration;
 use regex::bytes::Regex;
 
@@ -31,10 +37,35 @@ pub struct Args {
     pub password_pattern: Regex,
     /// Pattern used to find the ssh key passphrase prompt during in channel authentication.
     pub passphrase_pattern: Regex,
+    /// Patterns that, if seen during in channel authentication, immediately end authentication
+    /// with an error (ex: "Permission denied" or "Login incorrect").
+    pub auth_fatal_patterns: Vec<Regex>,
+    /// Responders for additional prompts seen during in channel authentication (ex: one time
+    /// password prompts) -- these are checked before the username/password/passphrase patterns.
+    pub auth_responders: Vec<AuthResponder>,
+    /// Indicates if the channel is connecting to a device console (ex: via a terminal server) --
+    /// in console mode returns are sent until a prompt of any kind shows up, and the device is
+    /// logged in to telnet style after any authentication to the console server itself.
+    pub console_mode: bool,
+    /// The user for logging in to the device console, if empty the transport user is used.
+    pub console_user: String,
+    /// The password for logging in to the device console, if unset the transport password is
+    /// used.
+    pub console_password: Credential,
+    /// How long to wait for output from a quiet console before sending another return.
+    pub console_return_interval: Duration,
+    /// Indicates if the device console should be logged out of when closing the channel, so no
+    /// logged in session is left behind on the console.
+    pub console_logout_on_close: bool,
+    /// The command sent to log out of the device console when closing the channel.
+    pub console_logout_command: String,
     /// Delay between reads of the underlying transport.
     pub read_delay: Duration,
     /// Duration for `timeout_ops` -- the timeout for channel send operations.
     pub timeout_ops: Duration,
+    /// Duration for `timeout_open` -- the overall deadline for opening the channel, including
+    /// authentication and (for drivers) finding the prompt.
+    pub timeout_open: Duration,
 }
 
 impl Default for Args {
@@ -47,8 +78,17 @@ impl Default for Args {
             username_pattern: default_auth_username_pattern(),
             password_pattern: default_auth_password_pattern(),
             passphrase_pattern: default_auth_passphrase_pattern(),
+            auth_fatal_patterns: default_auth_fatal_patterns(),
+            auth_responders: vec![],
+            console_mode: false,
+            console_user: String::new(),
+            console_password: Credential::default(),
+            console_return_interval: DEFAULT_CONSOLE_RETURN_INTERVAL,
+            console_logout_on_close: false,
+            console_logout_command: DEFAULT_CONSOLE_LOGOUT_COMMAND.to_owned(),
             read_delay: DEFAULT_READ_DELAY,
             timeout_ops: DEFAULT_TIMEOUT_OPS,
+            timeout_open: DEFAULT_TIMEOUT_OPEN,
         }
     }
 }
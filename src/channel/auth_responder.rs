@@ -0,0 +1,92 @@
+extern crate alloc;
+
+use crate::errors::{
+    ErrorKind,
+    ScrapliError,
+};
+use alloc::sync::Arc;
+use core::fmt::{
+    Debug,
+    Formatter,
+    Result as FmtResult,
+};
+use regex::bytes::Regex;
+use std::env;
+
+/// The custom type for auth responder callables.
+///
+/// The callable receives the prompt that was seen (ex: "Verification code:") and returns the
+/// response to send, for example a freshly generated TOTP code. Closures may capture state, for
+/// example a TOTP secret.
+pub type AuthResponseCallback = Arc<dyn Fn(&str) -> Result<String, ScrapliError> + Send + Sync>;
+
+/// `AuthResponseSource` is where the response to an auth responder prompt comes from.
+#[derive(Clone)]
+pub enum AuthResponseSource {
+    /// A static response -- an empty string simply sends a return (ex: to acknowledge a banner).
+    Static(String),
+    /// The response is read from the named environment variable when the prompt is seen.
+    Env(String),
+    /// The response is returned by the callable when the prompt is seen.
+    Callback(AuthResponseCallback),
+}
+
+impl Debug for AuthResponseSource {
+    fn fmt(
+        &self,
+        f: &mut Formatter<'_>,
+    ) -> FmtResult {
+        // never print static responses, they are very likely secrets
+        match self {
+            Self::Static(_) => write!(f, "Static(<redacted>)"),
+            Self::Env(name) => write!(f, "Env({name:?})"),
+            Self::Callback(_) => write!(f, "Callback"),
+        }
+    }
+}
+
+impl AuthResponseSource {
+    /// Returns the response for the given `prompt`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ScrapliError` if the environment variable of an `Env` source is not set (or is not
+    /// valid unicode) or if the callable of a `Callback` source returns an error.
+    pub fn resolve(
+        &self,
+        prompt: &str,
+    ) -> Result<String, ScrapliError> {
+        match self {
+            Self::Static(response) => Ok(response.clone()),
+            Self::Env(name) => env::var(name).map_err(|err| ScrapliError {
+                details: format!(
+                    "failed reading auth response from environment variable '{name}', error: {err}"
+                ),
+                kind: ErrorKind::Generic,
+            }),
+            Self::Callback(callback) => callback(prompt),
+        }
+    }
+}
+
+/// `AuthResponder` answers an additional prompt seen during in channel authentication -- for
+/// example a "Verification code:" one time password prompt or a banner requiring acknowledgement.
+#[derive(Debug, Clone)]
+pub struct AuthResponder {
+    /// The pattern of the prompt to respond to.
+    pub pattern: Regex,
+    /// Where the response to the prompt comes from.
+    pub source: AuthResponseSource,
+}
+
+impl AuthResponder {
+    /// Returns a new `AuthResponder` responding to prompts matching `pattern` with the response
+    /// from `source`.
+    #[must_use]
+    pub const fn new(
+        pattern: Regex,
+        source: AuthResponseSource,
+    ) -> Self {
+        Self { pattern, source }
+    }
+}
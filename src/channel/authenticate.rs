@@ -1,4 +1,5 @@
 use super::constants::{
+    AUTH_RESPONDER_SEEN_MAX,
     PASSPHRASE_SEEN_MAX,
     PASSWORD_SEEN_MAX,
     USER_SEEN_MAX,
@@ -8,28 +9,90 @@ use crate::channel::patterns::{
     default_auth_passphrase_pattern,
     default_auth_password_pattern,
     default_auth_username_pattern,
+    default_console_press_return_pattern,
+    default_host_key_changed_pattern,
+    default_host_key_fingerprint_pattern,
+    default_host_key_host_pattern,
+    default_host_key_prompt_pattern,
+    default_host_key_verification_failed_pattern,
 };
-use crate::errors::ScrapliError;
-use log::error;
+use crate::credentials::Credential;
+use crate::errors::{
+    ErrorKind,
+    ScrapliError,
+};
+use crate::transport::base::{
+    HostKeyPolicy,
+    InChannelAuthData,
+    InChannelAuthType,
+};
+use core::slice;
+use log::{
+    debug,
+    error,
+};
+use regex::bytes::Regex;
 
 impl Channel {
+    /// Runs in channel authentication as requested by the transport (or console authentication if
+    /// console mode is enabled), returning the output read while authenticating.
+    pub(crate) fn authenticate(
+        &mut self,
+        auth_data: &InChannelAuthData,
+    ) -> Result<Vec<u8>, ScrapliError> {
+        if self.args.console_mode {
+            debug!("console mode is enabled, waking console and starting console auth...");
+
+            self.authenticate_console(auth_data)
+        } else {
+            match auth_data.auth_type {
+                InChannelAuthType::Telnet => {
+                    debug!("transport requests in channel telnet auth, starting...");
+
+                    self.authenticate_telnet(auth_data.user.as_bytes(), &auth_data.password)
+                }
+
+                InChannelAuthType::SSH => {
+                    debug!("transport requests in channel ssh auth, starting...");
+
+                    self.authenticate_ssh(auth_data)
+                }
+
+                InChannelAuthType::None => {
+                    debug!("transport requests no in channel auth, reading until prompt...");
+
+                    self.read_until_prompt()
+                }
+            }
+        }
+    }
+
     #[allow(clippy::arithmetic_side_effects)]
     pub(crate) fn authenticate_telnet(
         &mut self,
         user: &[u8],
-        password: &[u8],
+        password: &Credential,
     ) -> Result<Vec<u8>, ScrapliError> {
         let mut user_seen_count = 0;
         let mut password_seen_count = 0;
+        let mut responder_seen_counts = vec![0; self.args.auth_responders.len()];
 
         let mut rb: Vec<u8> = vec![];
 
         loop {
-            let nb = self.read_until_any_prompt(&[
-                self.args.prompt_pattern.clone(),
-                self.args.username_pattern.clone(),
-                self.args.password_pattern.clone(),
-            ])?;
+            let nb = self.read_until_any_prompt(
+                &[
+                    vec![
+                        self.args.prompt_pattern.clone(),
+                        self.args.username_pattern.clone(),
+                        self.args.password_pattern.clone(),
+                    ],
+                    self.args.auth_fatal_patterns.clone(),
+                    self.auth_responder_patterns(),
+                    self.console_patterns(),
+                ]
+                .concat(),
+            )?;
 
             if nb.is_empty() {
                 continue;
@@ -41,6 +104,24 @@ impl Channel {
                 return Ok(rb);
             }
 
+            self.check_auth_fatal_output(&rb)?;
+
+            if self.args.console_mode && default_console_press_return_pattern().is_match(&rb) {
+                debug!("console asking for return to start session, sending return");
+
+                self.write_return()?;
+
+                rb = vec![];
+
+                continue;
+            }
+
+            if self.handle_auth_responders(&rb, &mut responder_seen_counts)? {
+                rb = vec![];
+
+                continue;
+            }
+
             if default_auth_username_pattern().is_match(&rb) {
                 user_seen_count += 1;
 
@@ -51,7 +132,10 @@ impl Channel {
 
                     error!("{}", msg);
 
-                    return Err(ScrapliError { details: msg });
+                    return Err(ScrapliError {
+                        details: msg,
+                        kind: ErrorKind::Generic,
+                    });
                 }
 
                 self.write_and_return(user)?;
@@ -71,10 +155,13 @@ impl Channel {
 
                     error!("{}", msg);
 
-                    return Err(ScrapliError { details: msg });
+                    return Err(ScrapliError {
+                        details: msg,
+                        kind: ErrorKind::Generic,
+                    });
                 }
 
-                self.write_and_return(password)?;
+                self.write_and_return(password.secret()?.expose_secret().as_bytes())?;
 
                 rb = vec![];
             }
@@ -84,20 +171,34 @@ impl Channel {
     #[allow(clippy::arithmetic_side_effects)]
     pub(crate) fn authenticate_ssh(
         &mut self,
-        password: &[u8],
-        passphrase: &[u8],
+        auth_data: &InChannelAuthData,
     ) -> Result<Vec<u8>, ScrapliError> {
+        let jump_hosts = auth_data.jump_hosts.as_slice();
+
         let mut password_seen_count = 0;
+        // each jump host may prompt for a password too
+        let password_seen_max = usize::from(PASSWORD_SEEN_MAX) + jump_hosts.len();
         let mut passphrase_seen_count = 0;
+        let mut responder_seen_counts = vec![0; self.args.auth_responders.len()];
 
         let mut rb: Vec<u8> = vec![];
 
         loop {
-            let nb = self.read_until_any_prompt(&[
-                self.args.prompt_pattern.clone(),
-                self.args.password_pattern.clone(),
-                self.args.passphrase_pattern.clone(),
-            ])?;
+            let nb = self.read_until_any_prompt(
+                &[
+                    vec![
+                        self.args.prompt_pattern.clone(),
+                        self.args.password_pattern.clone(),
+                        self.args.passphrase_pattern.clone(),
+                        default_host_key_prompt_pattern(),
+                        default_host_key_verification_failed_pattern(),
+                    ],
+                    self.args.auth_fatal_patterns.clone(),
+                    self.auth_responder_patterns(),
+                    self.console_patterns(),
+                ]
+                .concat(),
+            )?;
 
             if nb.is_empty() {
                 continue;
@@ -109,20 +210,68 @@ impl Channel {
                 return Ok(rb);
             }
 
+            if default_host_key_verification_failed_pattern().is_match(&rb) {
+                return Err(host_key_error(&rb, auth_data.host.as_str()));
+            }
+
+            self.check_auth_fatal_output(&rb)?;
+
+            // in console mode a login or "press return" prompt means we made it through to the
+            // device console, the device login is handled telnet style from here
+            if self.args.console_mode
+                && (default_auth_username_pattern().is_match(&rb)
+                    || default_console_press_return_pattern().is_match(&rb))
+            {
+                return Ok(rb);
+            }
+
+            if default_host_key_prompt_pattern().is_match(&rb) {
+                self.handle_host_key_prompt(&rb, auth_data)?;
+
+                rb = vec![];
+
+                continue;
+            }
+
+            if self.handle_auth_responders(&rb, &mut responder_seen_counts)? {
+                rb = vec![];
+
+                continue;
+            }
+
             if default_auth_password_pattern().is_match(&rb) {
                 password_seen_count += 1;
 
-                if password_seen_count > PASSWORD_SEEN_MAX {
+                if password_seen_count > password_seen_max {
                     let msg = String::from(
                         "password prompt seen multiple times, assuming authentication failed",
                     );
 
                     error!("{}", msg);
 
-                    return Err(ScrapliError { details: msg });
+                    return Err(ScrapliError {
+                        details: msg,
+                        kind: ErrorKind::Generic,
+                    });
                 }
 
-                self.write_and_return(password)?;
+                // ssh prompts as "user@host's password:", so we can tell jump host prompts apart
+                let prompt = String::from_utf8_lossy(&rb);
+
+                let prompting_jump_host = jump_hosts.iter().find(|jump_host| {
+                    jump_host.password.is_set()
+                        && prompt.contains(format!("@{}'s password", jump_host.host).as_str())
+                });
+
+                let password = if let Some(jump_host) = prompting_jump_host {
+                    debug!("sending password for jump host '{}'", jump_host.host);
+
+                    jump_host.password.secret()?
+                } else {
+                    auth_data.password.secret()?
+                };
+
+                self.write_and_return(password.expose_secret().as_bytes())?;
 
                 rb = vec![];
 
@@ -137,13 +286,271 @@ impl Channel {
 
                     error!("{}", msg);
 
-                    return Err(ScrapliError { details: msg });
+                    return Err(ScrapliError {
+                        details: msg,
+                        kind: ErrorKind::Generic,
+                    });
                 }
 
-                self.write_and_return(passphrase)?;
+                self.write_and_return(
+                    auth_data
+                        .private_key_passphrase
+                        .secret()?
+                        .expose_secret()
+                        .as_bytes(),
+                )?;
 
                 rb = vec![];
             }
         }
     }
+
+    /// Returns an error if any of the `auth_fatal_patterns` match the output in `rb`.
+    fn check_auth_fatal_output(
+        &self,
+        rb: &[u8],
+    ) -> Result<(), ScrapliError> {
+        let Some(matched) = self
+            .args
+            .auth_fatal_patterns
+            .iter()
+            .find_map(|pattern| pattern.find(rb))
+        else {
+            return Ok(());
+        };
+
+        let output = String::from_utf8_lossy(matched.as_bytes())
+            .trim()
+            .to_owned();
+
+        let msg = format!("fatal output seen during in channel authentication: '{output}'");
+
+        error!("{}", msg);
+
+        Err(ScrapliError {
+            details: msg,
+            kind: ErrorKind::AuthenticationFailed { output },
+        })
+    }
+
+    /// Authenticates to a device console (ex: via a terminal server) -- returns are sent whenever
+    /// the console is quiet until a prompt of any kind shows up. Any authentication the transport
+    /// requires (ex: ssh to the console server) happens first, then the device console is logged
+    /// in to (if it is not already logged in) like telnet.
+    pub(crate) fn authenticate_console(
+        &mut self,
+        auth_data: &InChannelAuthData,
+    ) -> Result<Vec<u8>, ScrapliError> {
+        self.console_waking = true;
+
+        let result = self.console_login(auth_data);
+
+        self.console_waking = false;
+
+        result
+    }
+
+    #[allow(clippy::expect_used)]
+    fn console_login(
+        &mut self,
+        auth_data: &InChannelAuthData,
+    ) -> Result<Vec<u8>, ScrapliError> {
+        if self.args.auth_bypass || matches!(auth_data.auth_type, InChannelAuthType::None) {
+            let prompt_pattern = self.args.prompt_pattern.clone();
+
+            return self.read_until_any_prompt(slice::from_ref(&prompt_pattern));
+        }
+
+        if matches!(auth_data.auth_type, InChannelAuthType::SSH) {
+            let rb = self.authenticate_ssh(auth_data)?;
+
+            // already logged in (at whatever privilege level), nothing more to do
+            if self.args.prompt_pattern.is_match(&rb) {
+                return Ok(rb);
+            }
+
+            // put the device login prompt back so the telnet style login sees it
+            self.queue
+                .lock()
+                .expect("failed acquiring queue lock")
+                .requeue(rb);
+        }
+
+        let user = if self.args.console_user.is_empty() {
+            auth_data.user.clone()
+        } else {
+            self.args.console_user.clone()
+        };
+
+        let password = if self.args.console_password.is_set() {
+            self.args.console_password.clone()
+        } else {
+            auth_data.password.clone()
+        };
+
+        self.authenticate_telnet(user.as_bytes(), &password)
+    }
+
+    fn console_patterns(&self) -> Vec<Regex> {
+        if !self.args.console_mode {
+            return vec![];
+        }
+
+        vec![
+            self.args.username_pattern.clone(),
+            default_console_press_return_pattern(),
+        ]
+    }
+
+    fn auth_responder_patterns(&self) -> Vec<Regex> {
+        self.args
+            .auth_responders
+            .iter()
+            .map(|responder| responder.pattern.clone())
+            .collect()
+    }
+
+    /// Answers the prompt in `rb` with the first matching auth responder, returning true if a
+    /// responder matched. `seen_counts` tracks how often each responder has matched so that a
+    /// rejected response does not loop forever.
+    #[allow(clippy::arithmetic_side_effects)]
+    fn handle_auth_responders(
+        &mut self,
+        rb: &[u8],
+        seen_counts: &mut [u8],
+    ) -> Result<bool, ScrapliError> {
+        let Some((idx, responder)) = self
+            .args
+            .auth_responders
+            .iter()
+            .enumerate()
+            .find(|(_, responder)| responder.pattern.is_match(rb))
+        else {
+            return Ok(false);
+        };
+
+        if let Some(seen_count) = seen_counts.get_mut(idx) {
+            *seen_count += 1;
+
+            if *seen_count > AUTH_RESPONDER_SEEN_MAX {
+                let msg = format!(
+                    "auth responder prompt '{}' seen multiple times, assuming authentication failed",
+                    responder.pattern
+                );
+
+                error!("{}", msg);
+
+                return Err(ScrapliError {
+                    details: msg,
+                    kind: ErrorKind::Generic,
+                });
+            }
+        }
+
+        let prompt = String::from_utf8_lossy(rb);
+
+        // the response is very likely a secret, so only the pattern is ever logged
+        debug!("responding to auth prompt matching '{}'", responder.pattern);
+
+        let response = responder.source.resolve(prompt.trim())?;
+
+        self.write_and_return(response.as_bytes())?;
+
+        Ok(true)
+    }
+
+    /// Answers an ssh unknown host key ("are you sure you want to continue connecting") prompt
+    /// based on the host key policy of the prompting host (the host or one of the jump hosts).
+    fn handle_host_key_prompt(
+        &mut self,
+        rb: &[u8],
+        auth_data: &InChannelAuthData,
+    ) -> Result<(), ScrapliError> {
+        let (prompting_host, fingerprint) = host_key_details(rb, auth_data.host.as_str());
+
+        let policy = auth_data
+            .jump_hosts
+            .iter()
+            .find(|jump_host| jump_host.host == prompting_host)
+            .map_or(&auth_data.host_key_policy, |jump_host| {
+                &jump_host.host_key_policy
+            });
+
+        // cloning only bumps the callback's reference count
+        let accepted = match policy.clone() {
+            HostKeyPolicy::TrustOnFirstUse => true,
+            HostKeyPolicy::Callback(callback) => callback(&prompting_host, &fingerprint),
+            HostKeyPolicy::Strict | HostKeyPolicy::AcceptNew | HostKeyPolicy::Insecure => false,
+        };
+
+        if accepted {
+            debug!("accepting host key '{fingerprint}' for host '{prompting_host}'");
+
+            return self.write_and_return(b"yes");
+        }
+
+        self.write_and_return(b"no")?;
+
+        let msg = format!("host key '{fingerprint}' for host '{prompting_host}' was rejected");
+
+        error!("{}", msg);
+
+        Err(ScrapliError {
+            details: msg,
+            kind: ErrorKind::HostKeyRejected {
+                host: prompting_host,
+                fingerprint,
+            },
+        })
+    }
+}
+
+/// Returns the host and key fingerprint from ssh host key messages in `rb`, falling back to
+/// `default_host` if the host cannot be found. The fingerprint is empty if it cannot be found.
+fn host_key_details(
+    rb: &[u8],
+    default_host: &str,
+) -> (String, String) {
+    let host = default_host_key_host_pattern()
+        .captures(rb)
+        .and_then(|captures| captures.iter().skip(1).flatten().next())
+        .map_or_else(
+            || default_host.to_owned(),
+            |host| String::from_utf8_lossy(host.as_bytes()).into_owned(),
+        );
+
+    let fingerprint = default_host_key_fingerprint_pattern()
+        .find(rb)
+        .map(|fingerprint| String::from_utf8_lossy(fingerprint.as_bytes()).into_owned())
+        .unwrap_or_default();
+
+    (host, fingerprint)
+}
+
+/// Returns the (typed) error for a failed ssh host key verification -- either a changed (mismatched)
+/// key or an unknown key that ssh refused due to the host key policy.
+fn host_key_error(
+    rb: &[u8],
+    default_host: &str,
+) -> ScrapliError {
+    let (host, fingerprint) = host_key_details(rb, default_host);
+
+    let error = if default_host_key_changed_pattern().is_match(rb) {
+        ScrapliError {
+            details: format!(
+                "host key for host '{host}' has changed (presented key '{fingerprint}'), refusing \
+                to connect"
+            ),
+            kind: ErrorKind::HostKeyMismatch { host, fingerprint },
+        }
+    } else {
+        ScrapliError {
+            details: format!("host key for host '{host}' is unknown and was rejected"),
+            kind: ErrorKind::HostKeyRejected { host, fingerprint },
+        }
+    };
+
+    error!("{}", error.details);
+
+    error
 }
@@ -0,0 +1,32 @@
+extern crate alloc;
+
+use alloc::sync::Arc;
+use core::sync::atomic::{
+    AtomicBool,
+    Ordering,
+};
+
+/// `CancelHandle` cancels an in progress (or the next) `open` of the `Channel` (or driver) it was
+/// obtained from. Handles are cheap to clone and can be sent to, and used from, other threads.
+#[derive(Debug, Clone, Default)]
+pub struct CancelHandle {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancelHandle {
+    /// Requests cancellation -- the `open` fails with an `ErrorKind::OpenCancelled` error the next
+    /// time it checks for cancellation (each read of the channel while opening).
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+
+    /// Returns true if cancellation has been requested (and not yet consumed by an `open`).
+    #[must_use]
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+
+    pub(crate) fn reset(&self) {
+        self.cancelled.store(false, Ordering::SeqCst);
+    }
+}
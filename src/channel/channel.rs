@@ -2,15 +2,22 @@ extern crate alloc;
 extern crate log;
 extern crate once_cell;
 
-use crate::errors::ScrapliError;
-use crate::transport::base::{
-    InChannelAuthType,
-    Transport,
+use crate::channel::constants::NEW_LINE_BYTE;
+use crate::errors::{
+    ErrorKind,
+    OpenPhase,
+    ScrapliError,
 };
+use crate::transport::base::Transport;
 
 use crate::util::queue::Queue;
 
 use alloc::sync::Arc;
+use chrono::{
+    DateTime,
+    Duration as ChronoDuration,
+    Utc,
+};
 use log::{
     debug,
     error,
@@ -25,7 +32,10 @@ use std::sync::mpsc::{
 use std::sync::Mutex;
 use std::thread;
 
-use super::Args;
+use super::{
+    Args,
+    CancelHandle,
+};
 
 /// The scraplirs `Channel` object -- the channel "wraps" the transport object and handles sending
 /// and reading from the transport.
@@ -33,9 +43,15 @@ pub struct Channel {
     /// The arguments that the channel was created with.
     pub args: Args,
     pub(super) transport: Arc<Mutex<dyn Transport + Send>>,
-    queue: Arc<Mutex<Queue>>,
+    pub(super) queue: Arc<Mutex<Queue>>,
     read_error_receiver: Option<Receiver<ScrapliError>>,
     read_done_sender: Option<Sender<bool>>,
+    pub(super) last_prompt: Vec<u8>,
+    last_prompt_banner: Vec<u8>,
+    open_deadline: Option<DateTime<Utc>>,
+    pub(super) open_phase: Option<OpenPhase>,
+    cancel_handle: CancelHandle,
+    pub(super) console_waking: bool,
 }
 
 impl Channel {
@@ -51,9 +67,69 @@ impl Channel {
             queue: Arc::new(Mutex::new(Queue::new())),
             read_error_receiver: None,
             read_done_sender: None,
+            last_prompt: vec![],
+            last_prompt_banner: vec![],
+            open_deadline: None,
+            open_phase: None,
+            cancel_handle: CancelHandle::default(),
+            console_waking: false,
+        }
+    }
+
+    /// Returns a `CancelHandle` that can be used (from any thread) to cancel opening the channel.
+    #[must_use]
+    pub fn cancel_handle(&self) -> CancelHandle {
+        self.cancel_handle.clone()
+    }
+
+    /// Sets the phase of an in progress open -- the `timeout_open` deadline (set when the channel
+    /// was opened) and cancellation are enforced on prompt reads until `exit_open_phase` is called.
+    pub(crate) const fn enter_open_phase(
+        &mut self,
+        phase: OpenPhase,
+    ) {
+        if self.open_deadline.is_some() {
+            self.open_phase = Some(phase);
         }
     }
 
+    /// Ends an in progress open, no longer enforcing the `timeout_open` deadline or cancellation.
+    pub(crate) fn exit_open_phase(&mut self) {
+        self.open_phase = None;
+        self.open_deadline = None;
+        self.cancel_handle.reset();
+    }
+
+    /// Returns an error if opening is in progress and it has been cancelled or the `timeout_open`
+    /// deadline has passed.
+    pub(super) fn check_open_deadline(&self) -> Result<(), ScrapliError> {
+        let Some(phase) = self.open_phase else {
+            return Ok(());
+        };
+
+        if self.cancel_handle.is_cancelled() {
+            return Err(ScrapliError {
+                details: format!("open cancelled during {phase} phase"),
+                kind: ErrorKind::OpenCancelled { phase },
+            });
+        }
+
+        if self
+            .open_deadline
+            .is_some_and(|deadline| Utc::now() >= deadline)
+        {
+            return Err(ScrapliError {
+                details: format!(
+                    "timed out opening connection, stalled in {phase} phase after {:?}",
+                    self.args.timeout_open
+                ),
+                kind: ErrorKind::OpenTimedOut { phase },
+            });
+        }
+
+        Ok(())
+    }
+
     #[allow(clippy::significant_drop_tightening)]
     ///  Open the channel and underlying transport. This method kicks off the internal read loop
     ///  which constantly reads from the underlying transport.
@@ -68,19 +144,48 @@ impl Channel {
     /// Returns a `ScrapliError` if something that cannot be recovered from occurs.
     #[allow(clippy::expect_used)]
     pub fn open(&mut self) -> Result<(), ScrapliError> {
+        let timeout_open = match ChronoDuration::from_std(self.args.timeout_open) {
+            Ok(timeout_open) => timeout_open,
+            Err(err) => {
+                return Err(ScrapliError {
+                    details: format!(
+                        "failed casting std Duration to chrono Duration, this shouldn't happen, \
+                        error: {err}"
+                    ),
+                    kind: ErrorKind::Generic,
+                })
+            }
+        };
+
+        self.open_deadline = Some(Utc::now() + timeout_open);
+        self.enter_open_phase(OpenPhase::Connect);
+
+        if let Err(err) = self.check_open_deadline() {
+            self.exit_open_phase();
+
+            return Err(err);
+        }
+
         let Ok(mut unlocked_transport) = self.transport.lock() else {
             return Err(ScrapliError {
                 details: String::from(
                     "failed acquiring transport lock during open, this should not happen",
                 ),
+                kind: ErrorKind::Generic,
             });
         };
 
         let transport_auth_data = unlocked_transport.in_channel_auth_data();
 
-        unlocked_transport.open()?;
+        let transport_open_result = unlocked_transport.open();
         drop(unlocked_transport);
 
+        if let Err(err) = transport_open_result.and_then(|()| self.check_open_deadline()) {
+            self.exit_open_phase();
+
+            return Err(err);
+        }
+
         let read_loop_transport_clone = Arc::<Mutex<dyn Transport + Send>>::clone(&self.transport);
         let read_loop_queue_clone = Arc::<Mutex<Queue>>::clone(&self.queue);
         let read_delay = self.args.read_delay;
@@ -103,33 +208,33 @@ impl Channel {
             );
         });
 
-        if self.args.auth_bypass {
+        if self.args.auth_bypass && !self.args.console_mode {
             debug!("auth bypass is enabled, skipping in channel auth check");
 
+            self.open_phase = None;
+
             return Ok(());
         }
 
-        let mut auth_buff: Vec<u8> = vec![];
+        let auth_result = self.authenticate(&transport_auth_data);
 
-        match transport_auth_data.auth_type {
-            InChannelAuthType::Telnet => {
-                debug!("transport requests in channel telnet auth, starting...");
+        self.open_phase = None;
 
-                auth_buff.extend(self.authenticate_telnet(
-                    transport_auth_data.user.as_bytes(),
-                    transport_auth_data.password.as_bytes(),
-                )?);
-            }
+        let auth_buff = match auth_result {
+            Ok(auth_buff) => auth_buff,
+            Err(err) => {
+                // whatever failed (timeout, cancellation, rejected credentials or host key), the
+                // channel is unusable, make sure we do not leave the transport (and read loop)
+                // running
+                if let Err(close_err) = self.close() {
+                    error!("failed closing channel after failed open, error: {close_err}");
+                }
 
-            InChannelAuthType::SSH => {
-                debug!("transport requests in channel ssh auth, starting...");
+                self.exit_open_phase();
 
-                auth_buff.extend(self.authenticate_ssh(
-                    transport_auth_data.password.as_bytes(),
-                    transport_auth_data.private_key_passphrase.as_bytes(),
-                )?);
+                return Err(err);
             }
-        }
+        };
 
         if auth_buff.is_empty() {
             return Ok(());
@@ -143,7 +248,8 @@ impl Channel {
         Ok(())
     }
 
-    /// Close the channel and underlying transport.
+    /// Close the channel and underlying transport -- closing a channel that is not open does
+    /// nothing.
     ///
     /// # Errors
     ///
@@ -152,10 +258,24 @@ impl Channel {
     pub fn close(&mut self) -> Result<(), ScrapliError> {
         info!("channel closing...");
 
+        let Some(read_done_sender) = self.read_done_sender.take() else {
+            debug!("channel is not open, nothing to close");
+
+            return Ok(());
+        };
+
+        if self.args.console_mode && self.args.console_logout_on_close {
+            debug!("console logout on close is enabled, logging out of console");
+
+            let logout_command = self.args.console_logout_command.clone();
+
+            if let Err(err) = self.write_and_return(logout_command.as_bytes()) {
+                error!("failed logging out of console, error: {err}");
+            }
+        }
+
         // send the done signal to tell our channel read loop to stop
-        self.read_done_sender
-            .as_ref()
-            .expect("attempting to close when read done sender is not set")
+        read_done_sender
             .send(true)
             .expect("error sending on read done channel, this is probably a bug");
 
@@ -170,6 +290,7 @@ impl Channel {
             }
             Err(err) => Err(ScrapliError {
                 details: format!("failed acquiring lock on transport, error: {err}"),
+                kind: ErrorKind::Generic,
             }),
         };
     }
@@ -208,6 +329,7 @@ impl Channel {
 
                         return Err(ScrapliError {
                             details: msg.to_owned(),
+                            kind: ErrorKind::Generic,
                         });
                     }
                 }
@@ -222,4 +344,40 @@ impl Channel {
 
         Ok(q.dequeue())
     }
+
+    /// Returns the last prompt the channel matched while reading -- this is updated any time a read
+    /// operation finds the prompt (or any of a set of prompts), so after a `send_input` this holds
+    /// the prompt the device returned to.
+    #[must_use]
+    pub const fn last_prompt(&self) -> &[u8] {
+        self.last_prompt.as_slice()
+    }
+
+    /// Returns the line immediately preceding the last prompt the channel matched (empty if there
+    /// was none) -- some platforms print context on the line above the prompt, for example the junos
+    /// `[edit interfaces]` banner.
+    #[must_use]
+    pub const fn last_prompt_banner(&self) -> &[u8] {
+        self.last_prompt_banner.as_slice()
+    }
+
+    /// Stores the matched prompt as the last prompt, and the line of the read buffer immediately
+    /// preceding the prompt as the last prompt banner.
+    pub(super) fn set_last_prompt(
+        &mut self,
+        rb: &[u8],
+        prompt: &regex::bytes::Match,
+    ) {
+        self.last_prompt = prompt.as_bytes().to_vec();
+
+        let preceding = rb.get(..prompt.start()).unwrap_or_default();
+
+        self.last_prompt_banner = preceding
+            .trim_ascii_end()
+            .rsplit(|&byte| byte == NEW_LINE_BYTE)
+            .next()
+            .unwrap_or_default()
+            .trim_ascii()
+            .to_vec();
+    }
 }
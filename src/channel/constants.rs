@@ -25,8 +25,22 @@ pub const PASSWORD_SEEN_MAX: u8 = 2;
 /// Constant to indicate what the "max seen" (ssh key) passphrase prompts is.
 pub const PASSPHRASE_SEEN_MAX: u8 = 2;
 
+/// Constant to indicate what the "max seen" prompts for each auth responder is.
+pub const AUTH_RESPONDER_SEEN_MAX: u8 = 2;
+
+/// Default `console_return_interval` value -- how long to wait for output from a (quiet) console
+/// before sending another return.
+pub const DEFAULT_CONSOLE_RETURN_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Default `console_logout_command` value.
+pub const DEFAULT_CONSOLE_LOGOUT_COMMAND: &str = "exit";
+
 /// Default "strip prompt" value (yes, strip the prompt by default).
 pub const DEFAULT_STRIP_PROMPT: bool = true;
 
 /// Default `timeout_ops` value.
 pub const DEFAULT_TIMEOUT_OPS: Duration = Duration::from_secs(30);
+
+/// Default `timeout_open` value -- the deadline for opening (connecting, authenticating and finding
+/// the prompt of) a connection.
+pub const DEFAULT_TIMEOUT_OPEN: Duration = Duration::from_mins(1);
@@ -9,7 +9,8 @@ pub struct Options {
     /// *true* (yes, *do* strip the prompt out).
     pub strip_prompt: bool,
     /// Eagerly send the input -- as in send it and do not wait to return to the expected prompt,
-    /// this should only be used by the netconf driver.
+    /// this should only be used by the netconf driver or for "bulk" configuration operations. When
+    /// set, the input itself is returned rather than the output of the input.
     pub eager: bool,
     /// Timeout to use for the operation, overrides (if set) the default channel ops timeout.
     pub timeout: Option<Duration>,
@@ -44,6 +44,22 @@ pub fn default_auth_password_pattern() -> Regex {
     .clone()
 }
 
+/// # Panics
+///
+///  Returns (once), the complied default console "press return" pattern -- the banner terminal
+///  servers and idle device consoles show before a session is started. This should realisitcally
+///  never panic.
+#[allow(clippy::expect_used)]
+pub fn default_console_press_return_pattern() -> Regex {
+    static RE: OnceCell<Regex> = OnceCell::new();
+
+    RE.get_or_init(|| {
+        Regex::new(r"(?im)press\s+(return|enter)\s+to\s+(get\s+started|continue|activate)")
+            .expect("failed compiling pattern, this is a bug")
+    })
+    .clone()
+}
+
 /// # Panics
 ///
 ///  Returns (once), the complied default passphrase (for private keys) pattern. This should
@@ -59,6 +75,110 @@ pub fn default_auth_passphrase_pattern() -> Regex {
     .clone()
 }
 
+/// # Panics
+///
+///  Returns (once), the complied default "fatal" in channel authentication output patterns --
+///  output that means authentication can never succeed, such as ssh failing to connect or the
+///  device rejecting the credentials. This should realisitcally never panic.
+#[allow(clippy::expect_used)]
+pub fn default_auth_fatal_patterns() -> Vec<Regex> {
+    static RES: OnceCell<Vec<Regex>> = OnceCell::new();
+
+    RES.get_or_init(|| {
+        [
+            r"(?im)^.*permission denied \(.*\)\.?\s*$",
+            "(?im)^.*connection refused.*$",
+            "(?im)^.*could not resolve hostname.*$",
+            "(?im)^.*no route to host.*$",
+            "(?im)^.*connection timed out.*$",
+            "(?im)^.*connection closed by .*$",
+            "(?im)^.*unable to negotiate with .*$",
+            "(?im)^.*login incorrect.*$",
+        ]
+        .iter()
+        .map(|pattern| Regex::new(pattern).expect("failed compiling pattern, this is a bug"))
+        .collect()
+    })
+    .clone()
+}
+
+/// # Panics
+///
+///  Returns (once), the complied ssh unknown host key prompt pattern. This should realisitcally
+///  never panic.
+#[allow(clippy::expect_used)]
+pub fn default_host_key_prompt_pattern() -> Regex {
+    static RE: OnceCell<Regex> = OnceCell::new();
+
+    RE.get_or_init(|| {
+        Regex::new(r"(?i)are you sure you want to continue connecting.*\?\s*$")
+            .expect("failed compiling pattern, this is a bug")
+    })
+    .clone()
+}
+
+/// # Panics
+///
+///  Returns (once), the complied ssh host key verification failed pattern. This should
+///  realisitcally never panic.
+#[allow(clippy::expect_used)]
+pub fn default_host_key_verification_failed_pattern() -> Regex {
+    static RE: OnceCell<Regex> = OnceCell::new();
+
+    RE.get_or_init(|| {
+        Regex::new("(?i)host key verification failed")
+            .expect("failed compiling pattern, this is a bug")
+    })
+    .clone()
+}
+
+/// # Panics
+///
+///  Returns (once), the complied ssh host key changed pattern. This should realisitcally never
+///  panic.
+#[allow(clippy::expect_used)]
+pub fn default_host_key_changed_pattern() -> Regex {
+    static RE: OnceCell<Regex> = OnceCell::new();
+
+    RE.get_or_init(|| {
+        Regex::new("(?i)remote host identification has changed")
+            .expect("failed compiling pattern, this is a bug")
+    })
+    .clone()
+}
+
+/// # Panics
+///
+///  Returns (once), the complied pattern capturing the host from ssh host key messages. This
+///  should realisitcally never panic.
+#[allow(clippy::expect_used)]
+pub fn default_host_key_host_pattern() -> Regex {
+    static RE: OnceCell<Regex> = OnceCell::new();
+
+    RE.get_or_init(|| {
+        Regex::new(
+            r"(?i)(?:authenticity of host '\[?([^' \]]+)|host key for \[?([^\s\]]+)\]?(?::\d+)? has changed|host key is known for \[?([^\s\]]+))",
+        )
+        .expect("failed compiling pattern, this is a bug")
+    })
+    .clone()
+}
+
+/// # Panics
+///
+///  Returns (once), the complied pattern capturing a host key fingerprint from ssh host key
+///  messages. This should realisitcally never panic.
+#[allow(clippy::expect_used)]
+pub fn default_host_key_fingerprint_pattern() -> Regex {
+    static RE: OnceCell<Regex> = OnceCell::new();
+
+    RE.get_or_init(|| {
+        Regex::new("(SHA256:[A-Za-z0-9+/=]+|MD5:[0-9a-fA-F:]+)")
+            .expect("failed compiling pattern, this is a bug")
+    })
+    .clone()
+}
+
 /// # Panics
 ///
 ///  Returns (once), the complied ansi matching pattern. This should realisitcally never panic.
@@ -2,7 +2,10 @@ extern crate alloc;
 use super::constants::ANSI_ESCAPE_BYTE;
 use super::Channel;
 use crate::channel::util::strip_ansi;
-use crate::errors::ScrapliError;
+use crate::errors::{
+    ErrorKind,
+    ScrapliError,
+};
 use crate::transport::base::Transport;
 use crate::util::queue::Queue;
 use alloc::sync::Arc;
@@ -54,6 +57,7 @@ impl Channel {
                         details: String::from(
                             "failed acquiring transport lock in channel read loop",
                         ),
+                        kind: ErrorKind::Generic,
                     })
                     .expect("error sending on read error channel, this is probably a bug");
 
@@ -68,6 +72,7 @@ impl Channel {
                     read_error_sender
                         .send(ScrapliError {
                             details: format!("encountered error while reading from transport in channel read loop, error: {err}"),
+                            kind: err.kind,
                         })
                         .expect("error sending on read error channel, this is probably a bug");
                     thread::sleep(read_delay);
@@ -1,9 +1,15 @@
 use super::Channel;
 use crate::channel::constants::NEW_LINE_BYTE;
-use crate::errors::ScrapliError;
+use crate::errors::{
+    ErrorKind,
+    OpenPhase,
+    ScrapliError,
+};
 use crate::util::bytes;
+use log::debug;
 use regex::bytes::Regex;
 use std::thread;
+use std::time::Instant;
 
 impl Channel {
     #[allow(clippy::indexing_slicing)]
@@ -48,7 +54,9 @@ impl Channel {
 
         rb.extend(pnb.as_slice());
 
-        if self.args.prompt_pattern.is_match(rb.as_ref()) {
+        if let Some(prompt) = self.args.prompt_pattern.find(rb.as_ref()) {
+            self.set_last_prompt(rb.as_slice(), &prompt);
+
             return (true, Ok(rb));
         }
 
@@ -61,9 +69,27 @@ impl Channel {
     ///
     /// Returns a `ScrapliError` if something that cannot be recovered from occurs.
     pub fn read_until_prompt(&mut self) -> Result<Vec<u8>, ScrapliError> {
+        self.read_until_prompt_before(None)
+    }
+
+    /// Like `read_until_prompt`, but gives up once the deadline (if any) has passed -- for reads
+    /// where the device may never show a prompt matching the prompt pattern.
+    pub(super) fn read_until_prompt_before(
+        &mut self,
+        deadline: Option<Instant>,
+    ) -> Result<Vec<u8>, ScrapliError> {
         let rb: Vec<u8> = vec![];
 
         loop {
+            self.check_open_deadline()?;
+
+            if deadline.is_some_and(|instant| instant <= Instant::now()) {
+                return Err(ScrapliError {
+                    details: String::from("timed out waiting for prompt"),
+                    kind: ErrorKind::Generic,
+                });
+            }
+
             let (prompt_found, result) = self._read_and_check_for_prompt(rb.as_slice());
 
             let rb = match result {
@@ -103,7 +129,41 @@ impl Channel {
         rb.extend(pnb.as_slice());
 
         for prompt in prompts {
-            if prompt.is_match(rb.as_ref()) {
+            if let Some(matched_prompt) = prompt.find(rb.as_ref()) {
+                self.set_last_prompt(rb.as_slice(), &matched_prompt);
+
+                return (true, Ok(rb));
+            }
+        }
+
+        (false, Ok(rb))
+    }
+
+    /// Like `_read_and_check_for_any_prompt`, but appends *all* new output to the read buffer
+    /// rather than only the last `prompt_search_depth` bytes of it -- only the prompt search is
+    /// limited to the tail of the buffer. The prompt search also only considers the buffer from
+    /// `search_from` onwards, and happens even if nothing new was read. Used where the entire
+    /// output matters (ex: checking the aggregated output of eager operations for failures) and
+    /// prompts earlier in the buffer must not count (ex: prompts between eagerly sent inputs).
+    pub(crate) fn _read_and_check_for_any_prompt_full(
+        &mut self,
+        old_rb: &[u8],
+        search_from: usize,
+        prompts: &[Regex],
+    ) -> (bool, Result<Vec<u8>, ScrapliError>) {
+        let mut rb = old_rb.to_vec();
+
+        match self.read() {
+            Ok(nb) => rb.extend(nb.as_slice()),
+            Err(err) => return (false, Err(err)),
+        }
+
+        let prb = self.process_read_buf(rb.get(search_from..).unwrap_or_default());
+
+        for prompt in prompts {
+            if let Some(matched_prompt) = prompt.find(prb.as_ref()) {
+                self.set_last_prompt(prb.as_slice(), &matched_prompt);
+
                 return (true, Ok(rb));
             }
         }
@@ -122,7 +182,11 @@ impl Channel {
     ) -> Result<Vec<u8>, ScrapliError> {
         let rb: Vec<u8> = vec![];
 
+        let mut last_activity = Instant::now();
+
         loop {
+            self.check_open_deadline()?;
+
             let (prompt_found, result) =
                 self._read_and_check_for_any_prompt(rb.as_slice(), prompts);
 
@@ -131,6 +195,23 @@ impl Channel {
                 Err(err) => return Err(err),
             };
 
+            if !rb.is_empty() {
+                last_activity = Instant::now();
+
+                // any output at all means we connected, so we are now authenticating
+                if self.open_phase == Some(OpenPhase::Connect) {
+                    self.open_phase = Some(OpenPhase::Authentication);
+                }
+            } else if self.console_waking
+                && last_activity.elapsed() >= self.args.console_return_interval
+            {
+                debug!("console quiet, sending return to wake it");
+
+                self.write_return()?;
+
+                last_activity = Instant::now();
+            }
+
             if prompt_found {
                 return Ok(rb);
             }
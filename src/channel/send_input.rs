@@ -1,12 +1,16 @@
 use super::constants::NEW_LINE_BYTE;
 use super::Channel;
 use super::OperationOptions;
-use crate::errors::ScrapliError;
+use crate::errors::{
+    ErrorKind,
+    ScrapliError,
+};
 use crate::util::bytes::{
     trim_cutset,
     trim_cutset_right,
 };
 use chrono::{
+    DateTime,
     Duration as ChronoDuration,
     Utc,
 };
@@ -14,7 +18,7 @@ use std::thread;
 
 impl Channel {
     #[allow(clippy::indexing_slicing)]
-    fn process_output(
+    pub(crate) fn process_output(
         &self,
         b: &[u8],
         strip_prompt: bool,
@@ -58,42 +62,9 @@ impl Channel {
         b: &[u8],
         options: &OperationOptions,
     ) -> Result<Vec<u8>, ScrapliError> {
-        let timeout = match ChronoDuration::from_std(options.timeout.unwrap_or(self.args.timeout_ops)) {
-            Ok(timeout) => timeout,
-            Err(err) => {
-                return Err(
-                    ScrapliError{
-                        details: format!("failed casting std Duration to chrono Duration, this shouldn't happen, error: {err}")
-                    }
-                )
-            }
-        };
-
-        let deadline = Utc::now() + timeout;
-
-        self.write(b)?;
-
-        let mut rb: Vec<u8> = vec![];
-
-        loop {
-            let now = Utc::now();
-
-            if deadline <= now {
-                return Err(ScrapliError {
-                    details: String::from("timed out sending input to device"),
-                });
-            }
-
-            let (found, result) = self._read_and_check_for_fuzzy(rb.as_slice(), b);
-            rb = match result {
-                Ok(rb) => rb,
-                Err(err) => return Err(err),
-            };
+        let deadline = self.send_input_deadline(options)?;
 
-            if found {
-                break;
-            }
-        }
+        self.write_and_read_input(b, deadline)?;
 
         self.write_return()?;
 
@@ -109,6 +80,7 @@ impl Channel {
             if deadline <= now {
                 return Err(ScrapliError {
                     details: String::from("timed out sending input to device"),
+                    kind: ErrorKind::Generic,
                 });
             }
 
@@ -142,6 +114,25 @@ impl Channel {
         }
     }
 
+    fn send_input_deadline(
+        &self,
+        options: &OperationOptions,
+    ) -> Result<DateTime<Utc>, ScrapliError> {
+        let timeout = match ChronoDuration::from_std(options.timeout.unwrap_or(self.args.timeout_ops)) {
+            Ok(timeout) => timeout,
+            Err(err) => {
+                return Err(
+                    ScrapliError{
+                        details: format!("failed casting std Duration to chrono Duration, this shouldn't happen, error: {err}"),
+                        kind: ErrorKind::Generic,
+                    }
+                )
+            }
+        };
+
+        Ok(Utc::now() + timeout)
+    }
+
     /// Send an input to the device, this is a convenience function to write a string, it wraps
     /// `send_input_bytes`.
     ///
@@ -155,4 +146,55 @@ impl Channel {
     ) -> Result<Vec<u8>, ScrapliError> {
         self.send_input_bytes(input.as_bytes(), options)
     }
+
+    /// Sends an input to the device without waiting for the prompt (like `send_input` in eager
+    /// mode), but returns whatever was read while finding the input rather than the input itself
+    /// -- this way callers that send many eager inputs can still see any output the device
+    /// emitted between them.
+    pub(crate) fn send_input_eager(
+        &mut self,
+        input: &str,
+        options: &OperationOptions,
+    ) -> Result<Vec<u8>, ScrapliError> {
+        let deadline = self.send_input_deadline(options)?;
+
+        let rb = self.write_and_read_input(input.as_bytes(), deadline)?;
+
+        self.write_return()?;
+
+        Ok(rb)
+    }
+
+    /// Writes the input and reads until the input is seen echoed back from the device, returns
+    /// everything read while finding the input.
+    fn write_and_read_input(
+        &mut self,
+        input: &[u8],
+        deadline: DateTime<Utc>,
+    ) -> Result<Vec<u8>, ScrapliError> {
+        self.write(input)?;
+
+        let mut rb: Vec<u8> = vec![];
+
+        loop {
+            let now = Utc::now();
+
+            if deadline <= now {
+                return Err(ScrapliError {
+                    details: String::from("timed out sending input to device"),
+                    kind: ErrorKind::Generic,
+                });
+            }
+
+            let (found, result) = self._read_and_check_for_fuzzy(rb.as_slice(), input);
+            rb = match result {
+                Ok(rb) => rb,
+                Err(err) => return Err(err),
+            };
+
+            if found {
+                return Ok(rb);
+            }
+        }
+    }
 }
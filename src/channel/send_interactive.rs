@@ -1,6 +1,9 @@
 use super::Channel;
 use super::OperationOptions;
-use crate::errors::ScrapliError;
+use crate::errors::{
+    ErrorKind,
+    ScrapliError,
+};
 use chrono::{
     Duration as ChronoDuration,
     Utc,
@@ -16,6 +19,7 @@ use regex::bytes::Regex;
 
 /// Holds options to use when performing "interactive" channel operations via `send_interactive`
 /// `Channel` method.
+#[derive(Clone)]
 pub struct Event {
     /// The input to send to the channel.
     pub input: String,
@@ -45,12 +49,17 @@ impl fmt::Display for Event {
         &self,
         f: &mut fmt::Formatter,
     ) -> fmt::Result {
+        if self.hidden {
+            return write!(f, "input: <hidden>, expecting: {}", self.response);
+        }
+
         write!(f, "input: {}, expecting: {}", self.input, self.response)
     }
 }
 
 /// `SendInteractiveEvents` is a custom type for a slice of `SendInteractiveEvent` such that we can
 /// implement (maybe among other future things?!) the `Display` trait.
+#[derive(Clone)]
 pub struct Events(pub Vec<Event>);
 
 impl fmt::Display for Events {
@@ -95,7 +104,8 @@ impl Channel {
             Err(err) => {
                 return Err(
                     ScrapliError{
-                        details: format!("failed casting std Duration to chrono Duration, this shouldn't happen, error: {err}")
+                        details: format!("failed casting std Duration to chrono Duration, this shouldn't happen, error: {err}"),
+                        kind: ErrorKind::Generic,
                     }
                 )
             }
@@ -119,6 +129,7 @@ impl Channel {
                                 "channel response '{}', could not be compiled, error: {}",
                                 event.response, err
                             ),
+                            kind: ErrorKind::Generic,
                         })
                     }
                 };
@@ -141,6 +152,7 @@ impl Channel {
                     if deadline <= now {
                         return Err(ScrapliError {
                             details: String::from("timed out sending input to device"),
+                            kind: ErrorKind::Generic,
                         });
                     }
 
@@ -175,6 +187,7 @@ impl Channel {
                 if deadline <= now {
                     return Err(ScrapliError {
                         details: String::from("timed out sending input to device"),
+                        kind: ErrorKind::Generic,
                     });
                 }
 
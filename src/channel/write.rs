@@ -1,5 +1,9 @@
 use super::Channel;
-use crate::errors::ScrapliError;
+use crate::errors::{
+    ErrorKind,
+    ScrapliError,
+};
+use std::time::Instant;
 
 impl Channel {
     /// Write `b` bytes to the device -- typically you should use `write_and_return` instead.
@@ -19,6 +23,7 @@ impl Channel {
             }
             Err(err) => Err(ScrapliError {
                 details: format!("failed acquiring lock on transport, error: {err}"),
+                kind: ErrorKind::Generic,
             }),
         };
     }
@@ -37,6 +42,24 @@ impl Channel {
             }
             Err(err) => Err(ScrapliError {
                 details: format!("failed acquiring lock on transport, error: {err}"),
+                kind: ErrorKind::Generic,
+            }),
+        };
+    }
+
+    /// Sends a break on the underlying transport (ex: a serial line break to get a device into its
+    /// boot loader).
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ScrapliError` if the transport does not support breaks or sending the break
+    /// fails.
+    pub fn send_break(&mut self) -> Result<(), ScrapliError> {
+        return match self.transport.lock() {
+            Ok(mut unlocked_transport) => unlocked_transport.send_break(),
+            Err(err) => Err(ScrapliError {
+                details: format!("failed acquiring lock on transport, error: {err}"),
+                kind: ErrorKind::Generic,
             }),
         };
     }
@@ -59,11 +82,13 @@ impl Channel {
     ///
     /// # Errors
     ///
-    /// Returns a `ScrapliError` if something that cannot be recovered from occurs.
+    /// Returns a `ScrapliError` if something that cannot be recovered from occurs, or if no prompt
+    /// matching the prompt pattern is seen within `timeout_ops`.
     pub fn get_prompt(&mut self) -> Result<Vec<u8>, ScrapliError> {
         self.write_return()?;
 
-        let nb = self.read_until_prompt()?;
+        let nb =
+            self.read_until_prompt_before(Instant::now().checked_add(self.args.timeout_ops))?;
 
         return self.args.prompt_pattern.find(nb.as_slice()).map_or_else(
             || {
@@ -71,6 +96,7 @@ impl Channel {
                     details: String::from(
                         "read until prompt, but couldn't match prompt, this is a bug",
                     ),
+                    kind: ErrorKind::Generic,
                 })
             },
             |b| Ok(b.as_bytes().to_vec()),
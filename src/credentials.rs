@@ -0,0 +1,440 @@
+extern crate alloc;
+
+use crate::errors::{
+    ErrorKind,
+    ScrapliError,
+};
+use alloc::sync::Arc;
+use core::fmt::{
+    Debug,
+    Formatter,
+    Result as FmtResult,
+};
+use core::str;
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use std::env;
+use std::fs;
+use std::process::Command;
+use zeroize::Zeroizing;
+
+/// `Secret` holds a secret value (ex: a password) -- the value is zeroed when the `Secret` is
+/// dropped and is never included in `Debug` output.
+#[derive(Clone, Default)]
+pub struct Secret(Zeroizing<String>);
+
+impl Secret {
+    /// Returns the secret value -- take care to not log (or otherwise leak) the returned value!
+    #[must_use]
+    pub fn expose_secret(&self) -> &str {
+        self.0.as_str()
+    }
+
+    /// Returns true if the secret value is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns a new `Secret` holding the value `value`.
+    #[must_use]
+    pub fn new(value: &str) -> Self {
+        Self(Zeroizing::new(value.to_owned()))
+    }
+}
+
+impl Debug for Secret {
+    fn fmt(
+        &self,
+        f: &mut Formatter<'_>,
+    ) -> FmtResult {
+        write!(f, "Secret(<redacted>)")
+    }
+}
+
+/// `CredentialProvider` provides a secret (ex: a password) on demand.
+///
+/// Providers are asked for their secret each time it is needed (ex: each time a password prompt is
+/// seen), so the secret is only held in memory as long as necessary.
+pub trait CredentialProvider: Send + Sync {
+    /// Returns the secret.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ScrapliError` if the secret cannot be obtained.
+    fn secret(&self) -> Result<Secret, ScrapliError>;
+}
+
+/// `StaticProvider` provides a secret that is known up front.
+pub struct StaticProvider {
+    secret: Secret,
+}
+
+impl StaticProvider {
+    /// Returns a new `StaticProvider` providing the secret `value`.
+    #[must_use]
+    pub fn new(value: &str) -> Self {
+        Self {
+            secret: Secret::new(value),
+        }
+    }
+}
+
+impl CredentialProvider for StaticProvider {
+    fn secret(&self) -> Result<Secret, ScrapliError> {
+        Ok(self.secret.clone())
+    }
+}
+
+/// `EnvProvider` provides a secret read from an environment variable.
+pub struct EnvProvider {
+    name: String,
+}
+
+impl EnvProvider {
+    /// Returns a new `EnvProvider` reading the secret from the environment variable `name`.
+    #[must_use]
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_owned(),
+        }
+    }
+}
+
+impl CredentialProvider for EnvProvider {
+    fn secret(&self) -> Result<Secret, ScrapliError> {
+        match env::var(self.name.as_str()) {
+            Ok(value) => Ok(Secret(Zeroizing::new(value))),
+            Err(err) => Err(ScrapliError {
+                details: format!(
+                    "failed reading credential from environment variable '{}', error: {err}",
+                    self.name
+                ),
+                kind: ErrorKind::Generic,
+            }),
+        }
+    }
+}
+
+/// `FileProvider` provides a secret read from a file -- trailing newlines are stripped.
+pub struct FileProvider {
+    path: String,
+}
+
+impl FileProvider {
+    /// Returns a new `FileProvider` reading the secret from the file at `path`.
+    #[must_use]
+    pub fn new(path: &str) -> Self {
+        Self {
+            path: path.to_owned(),
+        }
+    }
+}
+
+impl CredentialProvider for FileProvider {
+    fn secret(&self) -> Result<Secret, ScrapliError> {
+        match fs::read_to_string(self.path.as_str()) {
+            Ok(raw_content) => {
+                let content = Zeroizing::new(raw_content);
+
+                Ok(Secret::new(content.trim_end_matches(['\r', '\n'])))
+            }
+            Err(err) => Err(ScrapliError {
+                details: format!(
+                    "failed reading credential from file '{}', error: {err}",
+                    self.path
+                ),
+                kind: ErrorKind::Generic,
+            }),
+        }
+    }
+}
+
+/// `CommandProvider` provides a secret printed (to stdout) by an external command, for example a
+/// vault helper -- trailing newlines are stripped. The command is executed directly, not via a
+/// shell.
+pub struct CommandProvider {
+    args: Vec<String>,
+    program: String,
+}
+
+impl CommandProvider {
+    /// Returns a new `CommandProvider` executing the `program` with the arguments `args`.
+    #[must_use]
+    pub fn new(
+        program: &str,
+        args: Vec<String>,
+    ) -> Self {
+        Self {
+            args,
+            program: program.to_owned(),
+        }
+    }
+}
+
+impl CredentialProvider for CommandProvider {
+    fn secret(&self) -> Result<Secret, ScrapliError> {
+        let output = match Command::new(self.program.as_str())
+            .args(self.args.as_slice())
+            .output()
+        {
+            Ok(output) => output,
+            Err(err) => {
+                return Err(ScrapliError {
+                    details: format!(
+                        "failed executing credential command '{}', error: {err}",
+                        self.program
+                    ),
+                    kind: ErrorKind::Generic,
+                })
+            }
+        };
+
+        let stdout = Zeroizing::new(output.stdout);
+
+        if !output.status.success() {
+            return Err(ScrapliError {
+                details: format!(
+                    "credential command '{}' failed with {}, stderr: {}",
+                    self.program,
+                    output.status,
+                    String::from_utf8_lossy(&output.stderr).trim()
+                ),
+                kind: ErrorKind::Generic,
+            });
+        }
+
+        match str::from_utf8(&stdout) {
+            Ok(secret) => Ok(Secret::new(secret.trim_end_matches(['\r', '\n']))),
+            Err(err) => Err(ScrapliError {
+                details: format!(
+                    "credential command '{}' output is not valid utf-8, error: {err}",
+                    self.program
+                ),
+                kind: ErrorKind::Generic,
+            }),
+        }
+    }
+}
+
+/// `Credential` is an (optionally set) credential backed by a `CredentialProvider`. Credentials are
+/// cheap to clone -- clones share the provider rather than copying the secret.
+#[derive(Clone, Default)]
+pub struct Credential {
+    provider: Option<Arc<dyn CredentialProvider>>,
+}
+
+impl Credential {
+    /// Returns a new `Credential` with the static secret `value` -- the credential is unset if
+    /// `value` is empty.
+    #[must_use]
+    pub fn from_static(value: &str) -> Self {
+        if value.is_empty() {
+            return Self::default();
+        }
+
+        Self::new(StaticProvider::new(value))
+    }
+
+    /// Returns true if the credential is set.
+    #[must_use]
+    pub const fn is_set(&self) -> bool {
+        self.provider.is_some()
+    }
+
+    /// Returns a new `Credential` backed by the given `provider`.
+    #[must_use]
+    pub fn new<P: CredentialProvider + 'static>(provider: P) -> Self {
+        Self {
+            provider: Some(Arc::new(provider)),
+        }
+    }
+
+    /// Returns the secret of the credential, an empty secret if the credential is unset.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ScrapliError` if the provider fails to provide the secret.
+    pub fn secret(&self) -> Result<Secret, ScrapliError> {
+        self.provider
+            .as_ref()
+            .map_or_else(|| Ok(Secret::default()), |provider| provider.secret())
+    }
+}
+
+impl Debug for Credential {
+    fn fmt(
+        &self,
+        f: &mut Formatter<'_>,
+    ) -> FmtResult {
+        if self.is_set() {
+            write!(f, "Credential(<redacted>)")
+        } else {
+            write!(f, "Credential(<unset>)")
+        }
+    }
+}
+
+/// `CredentialSource` is a (serializable) description of where a credential comes from.
+///
+/// Useful for storing connection profiles without necessarily storing the secret itself. In YAML
+/// this looks like `password: {env: DEVICE_PASSWORD}`.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CredentialSource {
+    /// The command (program followed by its arguments) that prints the secret.
+    Command(Vec<String>),
+    /// The environment variable to read the secret from.
+    Env(String),
+    /// The path of the file to read the secret from.
+    File(String),
+    /// The secret value itself.
+    Value(String),
+}
+
+impl CredentialSource {
+    /// Returns the `Credential` for the source -- an unset credential for an empty value or
+    /// command.
+    #[must_use]
+    #[expect(
+        clippy::pattern_type_mismatch,
+        reason = "binding the variant values without this needs `ref` patterns, which are linted too"
+    )]
+    pub fn credential(&self) -> Credential {
+        match self {
+            Self::Command(command) => command
+                .split_first()
+                .map_or_else(Credential::default, |(program, args)| {
+                    Credential::new(CommandProvider::new(program, args.to_vec()))
+                }),
+            Self::Env(name) => Credential::new(EnvProvider::new(name)),
+            Self::File(path) => Credential::new(FileProvider::new(path)),
+            Self::Value(value) => Credential::from_static(value),
+        }
+    }
+}
+
+impl Debug for CredentialSource {
+    #[expect(
+        clippy::pattern_type_mismatch,
+        reason = "binding the variant values without this needs `ref` patterns, which are linted too"
+    )]
+    fn fmt(
+        &self,
+        f: &mut Formatter<'_>,
+    ) -> FmtResult {
+        match self {
+            Self::Command(command) => write!(f, "Command({command:?})"),
+            Self::Env(name) => write!(f, "Env({name:?})"),
+            Self::File(path) => write!(f, "File({path:?})"),
+            Self::Value(_) => write!(f, "Value(<redacted>)"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        CommandProvider,
+        Credential,
+        CredentialProvider as _,
+        CredentialSource,
+        EnvProvider,
+        FileProvider,
+        Secret,
+    };
+    use std::env;
+    use std::error::Error;
+    use std::fs;
+
+    type TestResult = Result<(), Box<dyn Error>>;
+
+    #[test]
+    fn command_provider_trims_output() -> TestResult {
+        let secret = CommandProvider::new("echo", vec![String::from("s3cret")]).secret()?;
+
+        assert_eq!(secret.expose_secret(), "s3cret", "trailing newline trimmed");
+
+        Ok(())
+    }
+
+    #[test]
+    fn command_provider_fails_on_non_zero_exit() {
+        assert!(
+            CommandProvider::new("false", vec![]).secret().is_err(),
+            "non zero exit is an error"
+        );
+    }
+
+    #[test]
+    fn debug_never_shows_secret() {
+        let secret = Secret::new("s3cret");
+        let credential = Credential::from_static("s3cret");
+        let source = CredentialSource::Value(String::from("s3cret"));
+
+        for output in [
+            format!("{secret:?}"),
+            format!("{secret:#?}"),
+            format!("{credential:?}"),
+            format!("{credential:#?}"),
+            format!("{source:?}"),
+            format!("{source:#?}"),
+        ] {
+            assert!(
+                !output.contains("s3cret"),
+                "secret in debug output {output}"
+            );
+        }
+
+        assert_eq!(
+            format!("{:?}", Credential::default()),
+            "Credential(<unset>)",
+            "unset credential"
+        );
+    }
+
+    #[test]
+    fn env_provider_reads_variable() -> TestResult {
+        env::set_var("SCRAPLIRS_CREDENTIALS_TEST_PRESENT", "s3cret");
+
+        let secret = EnvProvider::new("SCRAPLIRS_CREDENTIALS_TEST_PRESENT").secret()?;
+
+        assert_eq!(secret.expose_secret(), "s3cret", "variable value");
+
+        Ok(())
+    }
+
+    #[test]
+    fn env_provider_fails_on_missing_variable() {
+        assert!(
+            EnvProvider::new("SCRAPLIRS_CREDENTIALS_TEST_MISSING")
+                .secret()
+                .is_err(),
+            "missing variable is an error"
+        );
+    }
+
+    #[test]
+    fn file_provider_trims_trailing_newline() -> TestResult {
+        let path = env::temp_dir().join(format!(
+            "scraplirs-credentials-{}-secret",
+            std::process::id()
+        ));
+
+        fs::write(&path, "s3cret\r\n")?;
+
+        let result = FileProvider::new(&path.to_string_lossy()).secret();
+
+        fs::remove_file(&path)?;
+
+        assert_eq!(
+            result?.expose_secret(),
+            "s3cret",
+            "trailing newline trimmed"
+        );
+
+        Ok(())
+    }
+}
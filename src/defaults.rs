@@ -0,0 +1,423 @@
+extern crate alloc;
+
+use crate::credentials::{
+    Credential,
+    EnvProvider,
+};
+use crate::errors::{
+    ErrorKind,
+    ScrapliError,
+};
+use crate::util::serde_helpers::option_duration;
+use alloc::collections::BTreeMap;
+use core::fmt::{
+    Display,
+    Formatter,
+    Result as FmtResult,
+};
+use core::str::FromStr;
+use core::time::Duration;
+use serde::{
+    Deserialize,
+    Deserializer,
+};
+use std::env;
+use std::fs;
+use std::path::Path;
+
+/// The prefix of all environment variables read by `Defaults::from_env`.
+pub const DEFAULTS_ENV_PREFIX: &str = "SCRAPLI_";
+
+/// The environment variable holding the path of the defaults file read by `Defaults::load`.
+pub const DEFAULTS_FILE_ENV_VAR: &str = "SCRAPLI_DEFAULTS_FILE";
+
+/// Where the value of a (resolved) option came from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OptionSource {
+    /// The option was set by an explicit builder call.
+    Builder,
+    /// The option was not set anywhere, the scraplirs default is used.
+    Default,
+    /// The option was set by the given environment variable.
+    Env(String),
+    /// The option was set in the defaults file at the given path.
+    File(String),
+}
+
+impl Display for OptionSource {
+    #[expect(
+        clippy::pattern_type_mismatch,
+        reason = "binding the variant values without this needs `ref` patterns, which are linted too"
+    )]
+    fn fmt(
+        &self,
+        f: &mut Formatter<'_>,
+    ) -> FmtResult {
+        match self {
+            Self::Builder => write!(f, "builder"),
+            Self::Default => write!(f, "default"),
+            Self::Env(name) => write!(f, "env {name}"),
+            Self::File(path) => write!(f, "file '{path}'"),
+        }
+    }
+}
+
+/// `Defaults` holds the options that are commonly repeated in every script (credentials, ssh
+/// settings and the like) so they can be provided once via a defaults file and/or environment
+/// variables.
+///
+/// Precedence, lowest to highest: the defaults file, `SCRAPLI_*` environment variables, explicit
+/// builder calls. Defaults are applied by `GenericDriverBuilder::new_with_defaults`, so any builder
+/// call made afterward wins.
+///
+/// The defaults file is YAML (or JSON) with kebab-case keys, ex:
+///
+/// ```yaml
+/// user: admin
+/// ssh-strict-key: false
+/// ssh-config-file-path: /dev/null
+/// timeout-ops: 45s
+/// system-extra-args: ["-o", "PubkeyAcceptedKeyTypes=+ssh-rsa"]
+/// ```
+///
+/// Files with a `.toml` extension are read as TOML with the same keys, ex:
+///
+/// ```toml
+/// user = "admin"
+/// ssh-strict-key = false
+/// timeout-ops = "45s"
+/// ```
+///
+/// Environment variables are the upper cased option names prefixed with `SCRAPLI_`, ex:
+/// `SCRAPLI_USER` or `SCRAPLI_SSH_STRICT_KEY`. `SCRAPLI_SYSTEM_EXTRA_ARGS` is split on
+/// whitespace. Passwords from the environment are read when needed, not when loading.
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(rename_all = "kebab-case", default, deny_unknown_fields)]
+pub struct Defaults {
+    /// The password for authenticating to the host.
+    #[serde(deserialize_with = "deserialize_credential")]
+    pub password: Option<Credential>,
+    /// The port to connect to.
+    pub port: Option<u16>,
+    /// The secondary (enable/escalate) password -- only applies to network drivers.
+    #[serde(deserialize_with = "deserialize_credential")]
+    pub secondary_password: Option<Credential>,
+    #[serde(skip)]
+    sources: BTreeMap<&'static str, OptionSource>,
+    /// The path to an ssh config file to use.
+    pub ssh_config_file_path: Option<String>,
+    /// The path to an ssh known hosts file to use.
+    pub ssh_known_hosts_file_path: Option<String>,
+    /// The path to a private key to use for ssh authentication.
+    pub ssh_private_key_path: Option<String>,
+    /// Indicates if strict host key checking is enabled for ssh transports.
+    pub ssh_strict_key: Option<bool>,
+    /// Extra arguments passed to the system transport open binary (ex: ssh `-o` options).
+    pub system_extra_args: Option<Vec<String>>,
+    /// The operation timeout, as a human readable duration (ex: "1m 30s").
+    #[serde(deserialize_with = "option_duration::deserialize")]
+    pub timeout_ops: Option<Duration>,
+    /// The socket timeout, as a human readable duration (ex: "15s").
+    #[serde(deserialize_with = "option_duration::deserialize")]
+    pub timeout_socket: Option<Duration>,
+    /// The username for authenticating to the host.
+    pub user: Option<String>,
+}
+
+impl Defaults {
+    /// Returns the defaults read from `SCRAPLI_*` environment variables.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ScrapliError` if an environment variable holds an invalid value.
+    pub fn from_env() -> Result<Self, ScrapliError> {
+        let mut defaults = Self::default();
+
+        if let Some(value) = env_option("user") {
+            defaults.user = Some(value);
+        }
+
+        if env_option("password").is_some() {
+            defaults.password = Some(Credential::new(EnvProvider::new(&env_var_name("password"))));
+        }
+
+        if env_option("secondary-password").is_some() {
+            defaults.secondary_password = Some(Credential::new(EnvProvider::new(&env_var_name(
+                "secondary-password",
+            ))));
+        }
+
+        if let Some(value) = env_option("port") {
+            defaults.port = Some(parse_env_value("port", value.as_str())?);
+        }
+
+        if let Some(value) = env_option("ssh-strict-key") {
+            defaults.ssh_strict_key = Some(parse_env_value("ssh-strict-key", value.as_str())?);
+        }
+
+        defaults.ssh_config_file_path = env_option("ssh-config-file-path");
+        defaults.ssh_known_hosts_file_path = env_option("ssh-known-hosts-file-path");
+        defaults.ssh_private_key_path = env_option("ssh-private-key-path");
+
+        if let Some(value) = env_option("system-extra-args") {
+            defaults.system_extra_args =
+                Some(value.split_whitespace().map(str::to_owned).collect());
+        }
+
+        if let Some(value) = env_option("timeout-socket") {
+            defaults.timeout_socket = Some(parse_env_duration("timeout-socket", value.as_str())?);
+        }
+
+        if let Some(value) = env_option("timeout-ops") {
+            defaults.timeout_ops = Some(parse_env_duration("timeout-ops", value.as_str())?);
+        }
+
+        for name in defaults.set_options() {
+            defaults
+                .sources
+                .insert(name, OptionSource::Env(env_var_name(name)));
+        }
+
+        Ok(defaults)
+    }
+
+    /// Returns the defaults read from the file at `path` -- TOML if the path has a `.toml`
+    /// extension, otherwise YAML (or JSON).
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ScrapliError` if the file cannot be read or parsed.
+    pub fn from_file(path: &str) -> Result<Self, ScrapliError> {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(err) => {
+                return Err(ScrapliError {
+                    details: format!("failed reading defaults file '{path}', error: {err}"),
+                    kind: ErrorKind::Generic,
+                })
+            }
+        };
+
+        let is_toml = Path::new(path)
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("toml"));
+
+        let parsed = if is_toml {
+            toml::from_str::<Self>(content.as_str()).map_err(|err| err.to_string())
+        } else {
+            serde_yaml::from_str::<Self>(content.as_str()).map_err(|err| err.to_string())
+        };
+
+        let mut defaults = match parsed {
+            Ok(defaults) => defaults,
+            Err(err) => {
+                return Err(ScrapliError {
+                    details: format!("failed parsing defaults file '{path}', error: {err}"),
+                    kind: ErrorKind::Generic,
+                })
+            }
+        };
+
+        for name in defaults.set_options() {
+            defaults
+                .sources
+                .insert(name, OptionSource::File(path.to_owned()));
+        }
+
+        Ok(defaults)
+    }
+
+    /// Returns the defaults from the file at the path in `SCRAPLI_DEFAULTS_FILE` (if set)
+    /// overlaid with any `SCRAPLI_*` environment variables.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ScrapliError` if the defaults file cannot be read or parsed, or if an environment
+    /// variable holds an invalid value.
+    pub fn load() -> Result<Self, ScrapliError> {
+        let file_defaults = match env::var(DEFAULTS_FILE_ENV_VAR) {
+            Ok(path) if !path.is_empty() => Self::from_file(path.as_str())?,
+            _ => Self::default(),
+        };
+
+        Ok(file_defaults.merge(Self::from_env()?))
+    }
+
+    /// Returns the defaults with `other` overlaid on top -- options set in `other` win.
+    #[must_use]
+    pub fn merge(
+        mut self,
+        other: Self,
+    ) -> Self {
+        let Self {
+            user,
+            password,
+            secondary_password,
+            port,
+            ssh_strict_key,
+            ssh_config_file_path,
+            ssh_known_hosts_file_path,
+            ssh_private_key_path,
+            system_extra_args,
+            timeout_socket,
+            timeout_ops,
+            sources,
+        } = other;
+
+        self.user = user.or(self.user);
+        self.password = password.or(self.password);
+        self.secondary_password = secondary_password.or(self.secondary_password);
+        self.port = port.or(self.port);
+        self.ssh_strict_key = ssh_strict_key.or(self.ssh_strict_key);
+        self.ssh_config_file_path = ssh_config_file_path.or(self.ssh_config_file_path);
+        self.ssh_known_hosts_file_path =
+            ssh_known_hosts_file_path.or(self.ssh_known_hosts_file_path);
+        self.ssh_private_key_path = ssh_private_key_path.or(self.ssh_private_key_path);
+        self.system_extra_args = system_extra_args.or(self.system_extra_args);
+        self.timeout_socket = timeout_socket.or(self.timeout_socket);
+        self.timeout_ops = timeout_ops.or(self.timeout_ops);
+        self.sources.extend(sources);
+
+        self
+    }
+
+    /// Returns the names of the options that are set.
+    fn set_options(&self) -> Vec<&'static str> {
+        [
+            ("user", self.user.is_some()),
+            ("password", self.password.is_some()),
+            ("secondary-password", self.secondary_password.is_some()),
+            ("port", self.port.is_some()),
+            ("ssh-strict-key", self.ssh_strict_key.is_some()),
+            ("ssh-config-file-path", self.ssh_config_file_path.is_some()),
+            (
+                "ssh-known-hosts-file-path",
+                self.ssh_known_hosts_file_path.is_some(),
+            ),
+            ("ssh-private-key-path", self.ssh_private_key_path.is_some()),
+            ("system-extra-args", self.system_extra_args.is_some()),
+            ("timeout-socket", self.timeout_socket.is_some()),
+            ("timeout-ops", self.timeout_ops.is_some()),
+        ]
+        .into_iter()
+        .filter_map(|(name, is_set)| is_set.then_some(name))
+        .collect()
+    }
+
+    /// Returns where the option `name` (kebab-case, ex: "ssh-strict-key") came from.
+    #[must_use]
+    pub fn source(
+        &self,
+        name: &str,
+    ) -> OptionSource {
+        self.sources
+            .get(name)
+            .cloned()
+            .unwrap_or(OptionSource::Default)
+    }
+}
+
+/// A single option of `ResolvedOptions` -- secrets are never included in the value.
+#[derive(Debug, Clone)]
+pub struct ResolvedOption {
+    /// The (kebab-case) name of the option.
+    pub name: &'static str,
+    /// Where the value came from.
+    pub source: OptionSource,
+    /// The (display) value of the option.
+    pub value: String,
+}
+
+/// `ResolvedOptions` is the final value, and the source of that value, of each option that can be
+/// set via `Defaults` -- useful for troubleshooting which layer an option came from.
+#[derive(Debug, Clone, Default)]
+pub struct ResolvedOptions {
+    /// The resolved options.
+    pub options: Vec<ResolvedOption>,
+}
+
+impl ResolvedOptions {
+    /// Returns the resolved option `name`, if present.
+    #[must_use]
+    pub fn get(
+        &self,
+        name: &str,
+    ) -> Option<&ResolvedOption> {
+        self.options.iter().find(|option| option.name == name)
+    }
+
+    pub(crate) fn push(
+        &mut self,
+        name: &'static str,
+        value: String,
+        source: OptionSource,
+    ) {
+        self.options.push(ResolvedOption {
+            name,
+            source,
+            value,
+        });
+    }
+}
+
+impl Display for ResolvedOptions {
+    fn fmt(
+        &self,
+        f: &mut Formatter<'_>,
+    ) -> FmtResult {
+        for option in &self.options {
+            writeln!(f, "{}: {} ({})", option.name, option.value, option.source)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the environment variable name for the (kebab-case) option `name`.
+fn env_var_name(name: &str) -> String {
+    format!(
+        "{DEFAULTS_ENV_PREFIX}{}",
+        name.replace('-', "_").to_uppercase()
+    )
+}
+
+/// Returns the value of the environment variable for the option `name`, if set and not empty.
+fn env_option(name: &str) -> Option<String> {
+    env::var(env_var_name(name))
+        .ok()
+        .filter(|value| !value.is_empty())
+}
+
+fn parse_env_value<T: FromStr>(
+    name: &str,
+    value: &str,
+) -> Result<T, ScrapliError> {
+    value.parse::<T>().map_err(|_err| ScrapliError {
+        details: format!(
+            "invalid value '{value}' for environment variable '{}'",
+            env_var_name(name)
+        ),
+        kind: ErrorKind::Generic,
+    })
+}
+
+fn parse_env_duration(
+    name: &str,
+    value: &str,
+) -> Result<Duration, ScrapliError> {
+    humantime::parse_duration(value).map_err(|err| ScrapliError {
+        details: format!(
+            "invalid duration '{value}' for environment variable '{}', error: {err}",
+            env_var_name(name)
+        ),
+        kind: ErrorKind::Generic,
+    })
+}
+
+fn deserialize_credential<'de, D: Deserializer<'de>>(
+    deserializer: D
+) -> Result<Option<Credential>, D::Error> {
+    let value = Option::<String>::deserialize(deserializer)?;
+
+    Ok(value.map(|secret| Credential::from_static(secret.as_str())))
+}
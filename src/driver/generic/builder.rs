@@ -1,93 +1,523 @@
+extern crate alloc;
+
 use crate::channel::{
     Args as ChannelArgs,
+    AuthResponder,
     Channel,
 };
+use crate::credentials::Credential;
+use crate::defaults::{
+    Defaults,
+    OptionSource,
+    ResolvedOptions,
+};
 use crate::driver::generic::driver::{
     Args,
     Driver,
-    GenericDriverOnXCallable,
 };
+use crate::driver::interceptor::Interceptor;
+use crate::errors::ScrapliError;
+use crate::platform::Platform;
+use crate::response::FailurePattern;
 use crate::transport::base::{
+    HostKeyPolicy,
+    JumpHost,
+    Transport,
     TransportArgs,
     TransportSSHArgs,
     TransportType,
 };
+use crate::transport::exec::{
+    Exec,
+    ExecArgs,
+    ExecPreset,
+};
+use crate::transport::serial::{
+    FlowControl,
+    Parity,
+    Serial,
+    SerialArgs,
+    StopBits,
+};
 use crate::transport::system::{
     System,
     SystemArgs,
 };
+use crate::transport::tcp::{
+    Tcp,
+    TcpArgs,
+};
+use alloc::collections::BTreeSet;
 use core::time::Duration;
 use regex::bytes::Regex;
 
+/// Builds the channel wrapping a user provided transport -- see `Builder::transport_factory`.
+type TransportFactory = Box<dyn FnOnce(ChannelArgs, TransportArgs) -> Channel + Send>;
+
 /// `Builder` is a struct that holds a bunch of settings/defaults that can be used to build a
 /// *generic* Driver object.
 pub struct Builder {
     args: Args,
     channel_args: ChannelArgs,
-    transport_type: TransportType,
+    defaults: Defaults,
+    explicit_options: BTreeSet<&'static str>,
     transport_args: TransportArgs,
+    transport_exec_args: ExecArgs,
+    transport_factory: Option<TransportFactory>,
+    transport_serial_args: SerialArgs,
     transport_ssh_args: TransportSSHArgs,
     transport_system_args: SystemArgs,
+    transport_tcp_args: TcpArgs,
+    transport_type: TransportType,
 }
 
-#[allow(clippy::missing_const_for_fn)]
-#[allow(clippy::return_self_not_must_use)]
-#[allow(clippy::must_use_candidate)]
+#[expect(
+    clippy::missing_const_for_fn,
+    reason = "only some setters could be const, the builder keeps one setter style"
+)]
+#[expect(
+    clippy::return_self_not_must_use,
+    reason = "the setters are always chained, so marking each one `must_use` only adds noise"
+)]
+#[expect(
+    clippy::must_use_candidate,
+    reason = "the setters are always chained, so marking each one `must_use` only adds noise"
+)]
 impl Builder {
+    /// Sets the `auth_bypass` option -- this flag means we skip trying to do any kind of in
+    /// channel authentication.
+    pub fn auth_bypass(
+        mut self,
+        enabled: bool,
+    ) -> Self {
+        self.channel_args.auth_bypass = enabled;
+
+        self
+    }
+
+    /// Sets the `auth_fatal_patterns` for use when authenticating. If any of these patterns is seen
+    /// during in channel authentication, authentication fails immediately rather than waiting for
+    /// a prompt. This replaces the default patterns -- to extend them instead, include
+    /// `default_auth_fatal_patterns()` in `patterns`.
+    pub fn auth_fatal_patterns(
+        mut self,
+        patterns: Vec<Regex>,
+    ) -> Self {
+        self.channel_args.auth_fatal_patterns = patterns;
+
+        self
+    }
+
+    /// Sets the `auth_responders` for use when authenticating. Each responder answers prompts
+    /// matching its pattern (ex: "Verification code:") with the response from its source (a static
+    /// string, an environment variable or a callable) -- responders are checked before the
+    /// username, password and passphrase patterns.
+    pub fn auth_responders(
+        mut self,
+        responders: Vec<AuthResponder>,
+    ) -> Self {
+        self.channel_args.auth_responders = responders;
+
+        self
+    }
+
+    /// Build "builds" and returns a Driver object. If the `platform_type` is a known platform, the
+    /// platform's auth fatal patterns are added to the `auth_fatal_patterns`.
+    #[must_use]
+    pub fn build(mut self) -> Driver {
+        if let Ok(patterns) = Platform::new(&self.args.platform_type)
+            .and_then(|platform| platform.auth_fatal_patterns())
+        {
+            self.channel_args.auth_fatal_patterns.extend(patterns);
+        }
+
+        if let Some(transport_factory) = self.transport_factory {
+            return Driver::new(
+                self.args,
+                transport_factory(self.channel_args, self.transport_args),
+            );
+        }
+
+        let channel: Channel = match self.transport_type {
+            TransportType::System => Channel::new(
+                self.channel_args,
+                System::new(
+                    self.transport_args,
+                    self.transport_ssh_args,
+                    self.transport_system_args,
+                ),
+            ),
+            TransportType::Serial => Channel::new(
+                self.channel_args,
+                Serial::new(self.transport_args, self.transport_serial_args),
+            ),
+            TransportType::Exec => Channel::new(
+                self.channel_args,
+                Exec::new(self.transport_args, &self.transport_exec_args),
+            ),
+            TransportType::Tcp => Channel::new(
+                self.channel_args,
+                Tcp::new(self.transport_args, self.transport_tcp_args),
+            ),
+        };
+
+        Driver::new(self.args, channel)
+    }
+
+    /// Sets the `console_logout_command` sent to log out of the device console on close.
+    pub fn console_logout_command(
+        mut self,
+        command: &str,
+    ) -> Self {
+        command.clone_into(&mut self.channel_args.console_logout_command);
+
+        self
+    }
+
+    /// Sets the `console_logout_on_close` option -- if true the `console_logout_command` is sent
+    /// when closing so no logged in session is left behind on the console.
+    pub fn console_logout_on_close(
+        mut self,
+        enabled: bool,
+    ) -> Self {
+        self.channel_args.console_logout_on_close = enabled;
+
+        self
+    }
+
+    /// Sets the `console_mode` option -- enable this when connecting to a device console (ex: via
+    /// an Opengear or Cisco terminal server). In console mode returns are sent until a prompt of
+    /// any kind shows up ("Press RETURN to get started" banners included), and the device is
+    /// logged in to telnet style after any authentication to the console server itself. Already
+    /// logged in sessions (at any privilege level matching the prompt pattern) are simply reused.
+    pub fn console_mode(
+        mut self,
+        enabled: bool,
+    ) -> Self {
+        self.channel_args.console_mode = enabled;
+
+        self
+    }
+
+    /// Sets the `console_password` for logging in to the device console -- only needed if it
+    /// differs from the password of the transport (ex: the console server password).
+    pub fn console_password(
+        mut self,
+        password: &str,
+    ) -> Self {
+        self.channel_args.console_password = Credential::from_static(password);
+
+        self
+    }
+
+    /// Sets the credential to use as the `console_password` for logging in to the device console.
+    pub fn console_password_credential(
+        mut self,
+        credential: Credential,
+    ) -> Self {
+        self.channel_args.console_password = credential;
+
+        self
+    }
+
+    /// Sets the `console_return_interval` -- how long to wait for output from a quiet console
+    /// before sending another return.
+    pub fn console_return_interval(
+        mut self,
+        interval: Duration,
+    ) -> Self {
+        self.channel_args.console_return_interval = interval;
+
+        self
+    }
+
+    /// Sets the `console_user` for logging in to the device console -- only needed if it differs
+    /// from the user of the transport (ex: the console server user).
+    pub fn console_user(
+        mut self,
+        user: &str,
+    ) -> Self {
+        user.clone_into(&mut self.channel_args.console_user);
+
+        self
+    }
+
+    /// Returns the `Defaults` the builder was created with.
+    pub(crate) const fn defaults(&self) -> &Defaults {
+        &self.defaults
+    }
+
+    /// Set the `command` (and its arguments) an `Exec` transport runs in the target, ex:
+    /// `["sr_cli"]`. Will be ignored if transport type is not `Exec`.
+    pub fn exec_command(
+        mut self,
+        command: Vec<String>,
+    ) -> Self {
+        self.transport_exec_args.command = command;
+
+        self
+    }
+
+    /// Set the pod `container` of an `Exec` transport using the kubectl preset. Will be ignored if
+    /// transport type is not `Exec`.
+    pub fn exec_container(
+        mut self,
+        container: &str,
+    ) -> Self {
+        container.clone_into(&mut self.transport_exec_args.container);
+
+        self
+    }
+
+    /// Set the `extra_args` passed to the exec binary (before the target) of an `Exec` transport.
+    /// Will be ignored if transport type is not `Exec`.
+    pub fn exec_extra_args(
+        mut self,
+        extra_args: Vec<String>,
+    ) -> Self {
+        self.transport_exec_args.extra_args = extra_args;
+
+        self
+    }
+
+    /// Set the kubernetes `namespace` of an `Exec` transport using the kubectl preset. Will be
+    /// ignored if transport type is not `Exec`.
+    pub fn exec_namespace(
+        mut self,
+        namespace: &str,
+    ) -> Self {
+        namespace.clone_into(&mut self.transport_exec_args.namespace);
+
+        self
+    }
+
+    /// Set the `open_bin` of an `Exec` transport, overriding the binary of the preset (required for
+    /// the custom preset). Will be ignored if transport type is not `Exec`.
+    pub fn exec_open_bin(
+        mut self,
+        open_bin: &str,
+    ) -> Self {
+        open_bin.clone_into(&mut self.transport_exec_args.open_bin);
+
+        self
+    }
+
+    /// Set the `preset` (docker, podman, kubectl, netns or custom) of an `Exec` transport. Will be
+    /// ignored if transport type is not `Exec`.
+    pub fn exec_preset(
+        mut self,
+        preset: ExecPreset,
+    ) -> Self {
+        self.transport_exec_args.preset = preset;
+
+        self
+    }
+
+    /// Set the `target` (container, pod or network namespace) of an `Exec` transport, if unset the
+    /// host is used as the target. Will be ignored if transport type is not `Exec`.
+    pub fn exec_target(
+        mut self,
+        target: &str,
+    ) -> Self {
+        target.clone_into(&mut self.transport_exec_args.target);
+
+        self
+    }
+
+    /// Sets the `failed_when_contains` argument of a driver.
+    pub fn failed_when_contains(
+        mut self,
+        contains: Vec<String>,
+    ) -> Self {
+        self.args.failed_when_contains = contains;
+
+        self
+    }
+
+    /// Sets the `failure_patterns` argument of a driver.
+    pub fn failure_patterns(
+        mut self,
+        patterns: Vec<FailurePattern>,
+    ) -> Self {
+        self.args.failure_patterns = patterns;
+
+        self
+    }
+
+    /// Adds an interceptor to the end of the driver's interceptor pipeline -- every command and
+    /// interactive operation is passed through the interceptors in the order they were added.
+    pub fn interceptor<I: Interceptor + 'static>(
+        mut self,
+        interceptor: I,
+    ) -> Self {
+        self.args.interceptors.push(Box::new(interceptor));
+
+        self
+    }
+
     /// Return a new instance of `Builder` with sane defaults set.
     pub fn new(host: &str) -> Self {
         Self {
             args: Args::new(host),
             channel_args: ChannelArgs::default(),
-            transport_type: TransportType::System,
+            defaults: Defaults::default(),
+            explicit_options: BTreeSet::new(),
             transport_args: TransportArgs::new(host),
+            transport_exec_args: ExecArgs::default(),
+            transport_factory: None,
+            transport_serial_args: SerialArgs::default(),
             transport_ssh_args: TransportSSHArgs::default(),
             transport_system_args: SystemArgs::default(),
+            transport_tcp_args: TcpArgs::default(),
+            transport_type: TransportType::System,
         }
     }
 
-    /// Sets the `auth_bypass` option -- this flag means we skip trying to do any kind of in
-    /// channel authentication.
-    pub fn auth_bypass(
-        mut self,
-        b: bool,
+    /// Return a new instance of `Builder` with the options set in `defaults` applied over the sane
+    /// defaults -- any builder calls made afterward take precedence over the `Defaults`.
+    pub fn new_with_defaults(
+        host: &str,
+        defaults: Defaults,
     ) -> Self {
-        self.channel_args.auth_bypass = b;
+        let mut builder = Self::new(host);
+
+        if let Some(user) = defaults.user.as_ref() {
+            user.clone_into(&mut builder.transport_args.user);
+        }
+
+        if let Some(password) = defaults.password.as_ref() {
+            builder.transport_args.password = password.clone();
+        }
+
+        if let Some(port) = defaults.port {
+            builder.args.port = port;
+            builder.transport_args.port = port;
+            builder.transport_args.port_set = true;
+        }
+
+        if let Some(ssh_strict_key) = defaults.ssh_strict_key {
+            builder.transport_ssh_args.host_key_policy = if ssh_strict_key {
+                HostKeyPolicy::Strict
+            } else {
+                HostKeyPolicy::Insecure
+            };
+        }
+
+        if let Some(config_file_path) = defaults.ssh_config_file_path.as_ref() {
+            config_file_path.clone_into(&mut builder.transport_ssh_args.config_file_path);
+        }
+
+        if let Some(known_hosts_file_path) = defaults.ssh_known_hosts_file_path.as_ref() {
+            known_hosts_file_path.clone_into(&mut builder.transport_ssh_args.known_hosts_file_path);
+        }
+
+        if let Some(private_key_path) = defaults.ssh_private_key_path.as_ref() {
+            private_key_path.clone_into(&mut builder.transport_ssh_args.private_key_path);
+        }
+
+        if let Some(extra_args) = defaults.system_extra_args.as_ref() {
+            extra_args.clone_into(&mut builder.transport_system_args.extra_args);
+        }
+
+        if let Some(timeout_socket) = defaults.timeout_socket {
+            builder.transport_args.timeout_socket = timeout_socket;
+            builder.transport_args.timeout_socket_set = true;
+        }
+
+        if let Some(timeout_ops) = defaults.timeout_ops {
+            builder.channel_args.timeout_ops = timeout_ops;
+        }
+
+        builder.defaults = defaults;
+
+        builder
+    }
+
+    /// Sets the `on_close` hook of a driver -- executed right before closing the channel.
+    pub fn on_close<F>(
+        mut self,
+        hook: F,
+    ) -> Self
+    where
+        F: FnMut(&mut Driver) -> Result<(), ScrapliError> + Send + 'static,
+    {
+        self.args.on_close = Some(Box::new(hook));
 
         self
     }
 
-    /// Sets the channel `prompt_search_depth` -- this is the depth that we search backwards in
-    /// output for the prompt. Setting this smaller means we have to regex through less data but
-    /// risks us "missing" the prompt which will cause us to deadlock and timeout.
-    pub fn prompt_search_depth(
+    /// Sets the `on_error` hook of a driver -- executed with the error if opening, closing or
+    /// sending to the driver fails.
+    pub fn on_error<F>(
+        mut self,
+        hook: F,
+    ) -> Self
+    where
+        F: FnMut(&mut Driver, &ScrapliError) + Send + 'static,
+    {
+        self.args.on_error = Some(Box::new(hook));
+
+        self
+    }
+
+    /// Sets the `on_open` hook of a driver -- executed after authenticating (and the
+    /// `post_auth` hook), before returning from `open`.
+    pub fn on_open<F>(
         mut self,
-        i: u16,
+        hook: F,
+    ) -> Self
+    where
+        F: FnMut(&mut Driver) -> Result<(), ScrapliError> + Send + 'static,
+    {
+        self.args.on_open = Some(Box::new(hook));
+
+        self
+    }
+
+    /// Returns the source of the option `name` -- the builder if it was set by an explicit builder
+    /// call, otherwise whatever layer of the `Defaults` it came from.
+    fn option_source(
+        &self,
+        name: &'static str,
+    ) -> OptionSource {
+        if self.explicit_options.contains(name) {
+            return OptionSource::Builder;
+        }
+
+        self.defaults.source(name)
+    }
+
+    /// Sets the `passphrase_pattern` for use when authenticating -- applicable only for *ssh*
+    /// transports of course. This is the regex pattern used to "know" when the device is prompting
+    /// for the ssh key passphrase.
+    pub fn passphrase_pattern(
+        mut self,
+        pattern: Regex,
     ) -> Self {
-        self.channel_args.prompt_search_depth = i;
+        self.channel_args.passphrase_pattern = pattern;
 
         self
     }
 
-    /// Sets the `prompt_pattern` -- this is the primary regex pattern used by the channel to
-    /// "know" when we are at a prompt (and therefore can send more data and our previous command
-    /// is "done").
-    pub fn prompt_pattern(
+    /// Sets the password to use for authentication.
+    pub fn password(
         mut self,
-        r: Regex,
+        password: &str,
     ) -> Self {
-        self.channel_args.prompt_pattern = r;
+        self.transport_args.password = Credential::from_static(password);
+        self.explicit_options.insert("password");
 
         self
     }
 
-    /// Sets the `username_pattern` for use when authenticating. This is the regex pattern used to
-    /// "know" when the device is prompting for the users username.
-    pub fn username_pattern(
+    /// Sets the credential (ex: an environment variable or vault helper command backed
+    /// `Credential`) to use for password authentication.
+    pub fn password_credential(
         mut self,
-        r: Regex,
+        credential: Credential,
     ) -> Self {
-        self.channel_args.username_pattern = r;
+        self.transport_args.password = credential;
+        self.explicit_options.insert("password");
 
         self
     }
@@ -96,162 +526,270 @@ impl Builder {
     /// "know" when the device is prompting for the users password.
     pub fn password_pattern(
         mut self,
-        r: Regex,
+        pattern: Regex,
     ) -> Self {
-        self.channel_args.password_pattern = r;
+        self.channel_args.password_pattern = pattern;
 
         self
     }
 
-    /// Sets the `passphrase_pattern` for use when authenticating -- applicable only for *ssh*
-    /// transports of course. This is the regex pattern used to "know" when the device is prompting
-    /// for the ssh key passphrase.
-    pub fn passphrase_pattern(
+    /// Sets the `platform_type` argument of a driver.
+    pub fn platform_type(
         mut self,
-        r: Regex,
+        platform_type: &str,
     ) -> Self {
-        self.channel_args.passphrase_pattern = r;
+        platform_type.clone_into(&mut self.args.platform_type);
 
         self
     }
 
-    /// Sets the `return_char` of the channel object.
-    pub fn return_char(
+    /// Sets the port to connect to.
+    pub fn port(
         mut self,
-        s: &str,
+        port: u16,
     ) -> Self {
-        self.channel_args.return_char = s.to_owned();
+        self.args.port = port;
+        self.transport_args.port = port;
+        self.transport_args.port_set = true;
+        self.explicit_options.insert("port");
 
         self
     }
 
-    /// Sets the `read_delay` of the underlying channel.
-    pub fn read_delay(
+    /// Sets the `post_auth` hook of a driver -- executed immediately after in channel
+    /// authentication.
+    pub fn post_auth<F>(
         mut self,
-        d: Duration,
-    ) -> Self {
-        self.channel_args.read_delay = d;
+        hook: F,
+    ) -> Self
+    where
+        F: FnMut(&mut Driver) -> Result<(), ScrapliError> + Send + 'static,
+    {
+        self.args.post_auth = Some(Box::new(hook));
 
         self
     }
 
-    /// Sets the `timeout_ops` of the underlying channel.
-    pub fn timeout_ops(
+    /// Sets the `pre_close` hook of a driver -- executed before the `on_close` hook.
+    pub fn pre_close<F>(
+        mut self,
+        hook: F,
+    ) -> Self
+    where
+        F: FnMut(&mut Driver) -> Result<(), ScrapliError> + Send + 'static,
+    {
+        self.args.pre_close = Some(Box::new(hook));
+
+        self
+    }
+
+    /// Sets the `pre_open` hook of a driver -- executed before opening the channel.
+    pub fn pre_open<F>(
         mut self,
-        d: Duration,
+        hook: F,
+    ) -> Self
+    where
+        F: FnMut(&mut Driver) -> Result<(), ScrapliError> + Send + 'static,
+    {
+        self.args.pre_open = Some(Box::new(hook));
+
+        self
+    }
+
+    /// Sets the `prompt_pattern` -- this is the primary regex pattern used by the channel to
+    /// "know" when we are at a prompt (and therefore can send more data and our previous command
+    /// is "done").
+    pub fn prompt_pattern(
+        mut self,
+        pattern: Regex,
     ) -> Self {
-        self.channel_args.timeout_ops = d;
+        self.channel_args.prompt_pattern = pattern;
 
         self
     }
 
-    /// Defines the transport type to use with the driver.
-    pub fn transport_type(
+    /// Sets the channel `prompt_search_depth` -- this is the depth that we search backwards in
+    /// output for the prompt. Setting this smaller means we have to regex through less data but
+    /// risks us "missing" the prompt which will cause us to deadlock and timeout.
+    pub fn prompt_search_depth(
         mut self,
-        t: TransportType,
+        depth: u16,
     ) -> Self {
-        self.transport_type = t;
+        self.channel_args.prompt_search_depth = depth;
 
         self
     }
 
-    /// Sets the port to connect to.
-    pub fn port(
+    /// Sets the `read_delay` of the underlying channel.
+    pub fn read_delay(
         mut self,
-        i: u16,
+        delay: Duration,
     ) -> Self {
-        self.args.port = i;
-        self.transport_args.port = i;
+        self.channel_args.read_delay = delay;
 
         self
     }
 
-    /// Sets the user(name) to use for authentication.
-    pub fn user(
+    /// Sets the read size of the underlying transport.
+    pub fn read_size(
         mut self,
-        s: &str,
+        size: u16,
     ) -> Self {
-        self.transport_args.user = s.to_owned();
+        self.transport_args.read_size = size;
 
         self
     }
 
-    /// Sets the password to use for authentication.
-    pub fn password(
+    /// Returns the resolved value, and source, of each option that can be set via `Defaults` --
+    /// secrets are redacted.
+    pub fn resolved_options(&self) -> ResolvedOptions {
+        let mut resolved = ResolvedOptions::default();
+
+        let password = if self.transport_args.password.is_set() {
+            "<redacted>"
+        } else {
+            "<unset>"
+        };
+
+        resolved.push(
+            "user",
+            self.transport_args.user.clone(),
+            self.option_source("user"),
+        );
+        resolved.push(
+            "password",
+            password.to_owned(),
+            self.option_source("password"),
+        );
+        resolved.push(
+            "port",
+            self.transport_args.port.to_string(),
+            self.option_source("port"),
+        );
+        resolved.push(
+            "ssh-strict-key",
+            self.transport_ssh_args
+                .host_key_policy
+                .strict_host_key_checking()
+                .to_owned(),
+            self.option_source("ssh-strict-key"),
+        );
+        resolved.push(
+            "ssh-config-file-path",
+            self.transport_ssh_args.config_file_path.clone(),
+            self.option_source("ssh-config-file-path"),
+        );
+        resolved.push(
+            "ssh-known-hosts-file-path",
+            self.transport_ssh_args.known_hosts_file_path.clone(),
+            self.option_source("ssh-known-hosts-file-path"),
+        );
+        resolved.push(
+            "ssh-private-key-path",
+            self.transport_ssh_args.private_key_path.clone(),
+            self.option_source("ssh-private-key-path"),
+        );
+        resolved.push(
+            "system-extra-args",
+            self.transport_system_args.extra_args.join(" "),
+            self.option_source("system-extra-args"),
+        );
+        resolved.push(
+            "timeout-socket",
+            humantime::format_duration(self.transport_args.timeout_socket).to_string(),
+            self.option_source("timeout-socket"),
+        );
+        resolved.push(
+            "timeout-ops",
+            humantime::format_duration(self.channel_args.timeout_ops).to_string(),
+            self.option_source("timeout-ops"),
+        );
+
+        resolved
+    }
+
+    /// Sets the `return_char` of the channel object.
+    pub fn return_char(
         mut self,
-        s: &str,
+        return_char: &str,
     ) -> Self {
-        self.transport_args.password = s.to_owned();
+        return_char.clone_into(&mut self.channel_args.return_char);
 
         self
     }
 
-    /// Sets the `timeout_socket` parameter.
-    pub fn timeout_socket(
+    /// Set the `baud_rate` of a `Serial` transport. Will be ignored if transport type is not
+    /// `Serial`.
+    pub fn serial_baud_rate(
         mut self,
-        d: Duration,
+        baud_rate: u32,
     ) -> Self {
-        self.transport_args.timeout_socket = d;
+        self.transport_serial_args.baud_rate = baud_rate;
 
         self
     }
 
-    /// Sets the read size of the underlying transport.
-    pub fn read_size(
+    /// Set the `break_on_open` setting of a `Serial` transport -- if true a break is sent right
+    /// after opening the device. Will be ignored if transport type is not `Serial`.
+    pub fn serial_break_on_open(
         mut self,
-        i: u16,
+        enabled: bool,
     ) -> Self {
-        self.transport_args.read_size = i;
+        self.transport_serial_args.break_on_open = enabled;
 
         self
     }
 
-    /// Sets the terminal height if applicable for the selected transport.
-    pub fn term_height(
+    /// Set the `device` (ex: "/dev/ttyUSB0") of a `Serial` transport, if unset the host is used as
+    /// the device. Will be ignored if transport type is not `Serial`.
+    pub fn serial_device(
         mut self,
-        i: u16,
+        device: &str,
     ) -> Self {
-        self.transport_args.term_height = i;
+        device.clone_into(&mut self.transport_serial_args.device);
 
         self
     }
 
-    /// Sets the terminal width if applicable for the selected transport.
-    pub fn term_width(
+    /// Set the `flow_control` of a `Serial` transport. Will be ignored if transport type is not
+    /// `Serial`.
+    pub fn serial_flow_control(
         mut self,
-        i: u16,
+        flow_control: FlowControl,
     ) -> Self {
-        self.transport_args.term_width = i;
+        self.transport_serial_args.flow_control = flow_control;
 
         self
     }
 
-    /// Enable or disable ssh strict key checking for *ssh* transports.
-    pub fn ssh_strict_key(
+    /// Set the `parity` of a `Serial` transport. Will be ignored if transport type is not `Serial`.
+    pub fn serial_parity(
         mut self,
-        b: bool,
+        parity: Parity,
     ) -> Self {
-        self.transport_ssh_args.strict_key = b;
+        self.transport_serial_args.parity = parity;
 
         self
     }
 
-    /// Sets the `private_key_path` argument of a driver using an *ssh* transport.
-    pub fn ssh_private_key_path(
+    /// Set the `stop_bits` of a `Serial` transport. Will be ignored if transport type is not
+    /// `Serial`.
+    pub fn serial_stop_bits(
         mut self,
-        s: &str,
+        stop_bits: StopBits,
     ) -> Self {
-        self.transport_ssh_args.private_key_path = s.to_owned();
+        self.transport_serial_args.stop_bits = stop_bits;
 
         self
     }
 
-    /// Sets the `private_key_passphrase` argument of a driver using an *ssh* transport.
-    pub fn ssh_private_key_passphrase(
+    /// Set the number of `wake_returns` a `Serial` transport sends after opening the device to
+    /// elicit a prompt. Will be ignored if transport type is not `Serial`.
+    pub fn serial_wake_returns(
         mut self,
-        s: &str,
+        returns: u8,
     ) -> Self {
-        self.transport_ssh_args.private_key_passphrase = s.to_owned();
+        self.transport_serial_args.wake_returns = returns;
 
         self
     }
@@ -259,9 +797,32 @@ impl Builder {
     /// Sets the `config_file_path` argument of a driver using an *ssh* transport.
     pub fn ssh_config_file_path(
         mut self,
-        s: &str,
+        path: &str,
+    ) -> Self {
+        path.clone_into(&mut self.transport_ssh_args.config_file_path);
+        self.explicit_options.insert("ssh-config-file-path");
+
+        self
+    }
+
+    /// Sets the host key policy for *ssh* transports.
+    pub fn ssh_host_key_policy(
+        mut self,
+        policy: HostKeyPolicy,
+    ) -> Self {
+        self.transport_ssh_args.host_key_policy = policy;
+        self.explicit_options.insert("ssh-strict-key");
+
+        self
+    }
+
+    /// Sets the `jump_hosts` argument of a driver using an *ssh* transport -- the chain of jump
+    /// hosts (bastions) to connect through, in order.
+    pub fn ssh_jump_hosts(
+        mut self,
+        jump_hosts: Vec<JumpHost>,
     ) -> Self {
-        self.transport_ssh_args.config_file_path = s.to_owned();
+        self.transport_ssh_args.jump_hosts = jump_hosts;
 
         self
     }
@@ -269,50 +830,80 @@ impl Builder {
     /// Sets the `known_hosts_file_path` argument of a driver using an *ssh* transport.
     pub fn ssh_known_hosts_file_path(
         mut self,
-        s: &str,
+        path: &str,
     ) -> Self {
-        self.transport_ssh_args.known_hosts_file_path = s.to_owned();
+        path.clone_into(&mut self.transport_ssh_args.known_hosts_file_path);
+        self.explicit_options.insert("ssh-known-hosts-file-path");
 
         self
     }
 
-    /// Sets the `failed_when_contains` argument of a driver.
-    pub fn failed_when_contains(
+    /// Sets the `private_key_passphrase` argument of a driver using an *ssh* transport.
+    pub fn ssh_private_key_passphrase(
         mut self,
-        v: Vec<String>,
+        passphrase: &str,
     ) -> Self {
-        self.args.failed_when_contains = v;
+        self.transport_ssh_args.private_key_passphrase = Credential::from_static(passphrase);
 
         self
     }
 
-    /// Sets the `on_open` argument of a driver.
-    pub fn on_open(
+    /// Sets the credential to use as the `private_key_passphrase` of a driver using an *ssh*
+    /// transport.
+    pub fn ssh_private_key_passphrase_credential(
         mut self,
-        f: GenericDriverOnXCallable,
+        credential: Credential,
     ) -> Self {
-        self.args.on_open = Some(f);
+        self.transport_ssh_args.private_key_passphrase = credential;
 
         self
     }
 
-    /// Sets the `on_close` argument of a driver.
-    pub fn on_close(
+    /// Sets the `private_key_path` argument of a driver using an *ssh* transport.
+    pub fn ssh_private_key_path(
         mut self,
-        f: GenericDriverOnXCallable,
+        path: &str,
     ) -> Self {
-        self.args.on_close = Some(f);
+        path.clone_into(&mut self.transport_ssh_args.private_key_path);
+        self.explicit_options.insert("ssh-private-key-path");
 
         self
     }
 
-    /// Set the `open_bin` setting of a `System` transport. Will be ignored if transport type is
+    /// Enable or disable ssh strict key checking for *ssh* transports -- shorthand for setting the
+    /// host key policy to `HostKeyPolicy::Strict` (true) or `HostKeyPolicy::Insecure` (false).
+    pub fn ssh_strict_key(
+        mut self,
+        strict: bool,
+    ) -> Self {
+        self.transport_ssh_args.host_key_policy = if strict {
+            HostKeyPolicy::Strict
+        } else {
+            HostKeyPolicy::Insecure
+        };
+        self.explicit_options.insert("ssh-strict-key");
+
+        self
+    }
+
+    /// Sets the `structured_output_pipe` argument of a driver.
+    pub fn structured_output_pipe(
+        mut self,
+        pipe: &str,
+    ) -> Self {
+        pipe.clone_into(&mut self.args.structured_output_pipe);
+
+        self
+    }
+
+    /// Set the `extra_args` setting of a `System` transport. Will be ignored if transport type is
     /// not `System`.
-    pub fn system_open_bin(
+    pub fn system_extra_args(
         mut self,
-        s: &str,
+        extra_args: Vec<String>,
     ) -> Self {
-        self.transport_system_args.open_bin = s.to_owned();
+        self.transport_system_args.extra_args = extra_args;
+        self.explicit_options.insert("system-extra-args");
 
         self
     }
@@ -321,38 +912,473 @@ impl Builder {
     /// not `System`.
     pub fn system_open_args(
         mut self,
-        v: Vec<String>,
+        open_args: Vec<String>,
     ) -> Self {
-        self.transport_system_args.open_args = v;
+        self.transport_system_args.open_args = open_args;
 
         self
     }
 
-    /// Set the `extra_args` setting of a `System` transport. Will be ignored if transport type is
+    /// Set the `open_bin` setting of a `System` transport. Will be ignored if transport type is
     /// not `System`.
-    pub fn system_extra_args(
+    pub fn system_open_bin(
         mut self,
-        v: Vec<String>,
+        open_bin: &str,
     ) -> Self {
-        self.transport_system_args.extra_args = v;
+        open_bin.clone_into(&mut self.transport_system_args.open_bin);
 
         self
     }
 
-    /// Build "builds" and returns a Driver object.
-    #[must_use]
-    pub fn build(self) -> Driver {
-        let c: Channel = match self.transport_type {
-            TransportType::System => Channel::new(
-                self.channel_args,
-                System::new(
-                    self.transport_args,
-                    self.transport_ssh_args,
-                    self.transport_system_args,
-                ),
-            ),
-        };
+    /// Set the `read_poll_interval` of a `Tcp` transport -- how long a single read waits for data.
+    /// Longer intervals wake an idle connection less often, but delay writes by up to the interval.
+    /// Will be ignored if transport type is not `Tcp`.
+    pub fn tcp_read_poll_interval(
+        mut self,
+        interval: Duration,
+    ) -> Self {
+        self.transport_tcp_args.read_poll_interval = interval;
+
+        self
+    }
+
+    /// Set the `tls` setting of a `Tcp` transport -- if true the connection is wrapped in TLS. Will
+    /// be ignored if transport type is not `Tcp`.
+    pub fn tcp_tls(
+        mut self,
+        enabled: bool,
+    ) -> Self {
+        self.transport_tcp_args.tls = enabled;
+
+        self
+    }
+
+    /// Set the `tls_ca_file` (PEM file of CA certificates to trust) of a `Tcp` transport, if unset
+    /// the bundled webpki roots are trusted. Will be ignored if transport type is not `Tcp`.
+    pub fn tcp_tls_ca_file(
+        mut self,
+        ca_file: &str,
+    ) -> Self {
+        ca_file.clone_into(&mut self.transport_tcp_args.tls_ca_file);
+
+        self
+    }
+
+    /// Set the `tls_insecure` setting of a `Tcp` transport -- if true the server certificate is not
+    /// verified. Will be ignored if transport type is not `Tcp`.
+    pub fn tcp_tls_insecure(
+        mut self,
+        enabled: bool,
+    ) -> Self {
+        self.transport_tcp_args.tls_insecure = enabled;
+
+        self
+    }
+
+    /// Set the `tls_server_name` (used for SNI and certificate verification) of a `Tcp` transport,
+    /// if unset the host is used. Will be ignored if transport type is not `Tcp`.
+    pub fn tcp_tls_server_name(
+        mut self,
+        server_name: &str,
+    ) -> Self {
+        server_name.clone_into(&mut self.transport_tcp_args.tls_server_name);
+
+        self
+    }
+
+    /// Sets the terminal height if applicable for the selected transport.
+    pub fn term_height(
+        mut self,
+        height: u16,
+    ) -> Self {
+        self.transport_args.term_height = height;
+
+        self
+    }
+
+    /// Sets the terminal width if applicable for the selected transport.
+    pub fn term_width(
+        mut self,
+        width: u16,
+    ) -> Self {
+        self.transport_args.term_width = width;
+
+        self
+    }
+
+    /// Sets the `timeout_open` of the underlying channel -- the overall deadline for opening the
+    /// driver (connecting, authenticating and finding the prompt).
+    pub fn timeout_open(
+        mut self,
+        timeout: Duration,
+    ) -> Self {
+        self.channel_args.timeout_open = timeout;
+
+        self
+    }
+
+    /// Sets the `timeout_ops` of the underlying channel.
+    pub fn timeout_ops(
+        mut self,
+        timeout: Duration,
+    ) -> Self {
+        self.channel_args.timeout_ops = timeout;
+        self.explicit_options.insert("timeout-ops");
+
+        self
+    }
+
+    /// Sets the `timeout_socket` parameter.
+    pub fn timeout_socket(
+        mut self,
+        timeout: Duration,
+    ) -> Self {
+        self.transport_args.timeout_socket = timeout;
+        self.transport_args.timeout_socket_set = true;
+        self.explicit_options.insert("timeout-socket");
+
+        self
+    }
+
+    /// Sets a factory that builds a user provided transport (ex: a transport implemented outside of
+    /// scraplirs, or a mock transport for testing) from the transport args. When set, the factory
+    /// is used instead of the transport selected via `transport_type`.
+    pub fn transport_factory<T, F>(
+        mut self,
+        factory: F,
+    ) -> Self
+    where
+        T: Transport + Send + 'static,
+        F: FnOnce(TransportArgs) -> T + Send + 'static,
+    {
+        self.transport_factory = Some(Box::new(move |channel_args, transport_args| {
+            Channel::new(channel_args, factory(transport_args))
+        }));
+
+        self
+    }
+
+    /// Defines the transport type to use with the driver.
+    pub fn transport_type(
+        mut self,
+        transport_type: TransportType,
+    ) -> Self {
+        self.transport_type = transport_type;
+
+        self
+    }
+
+    /// Sets the user(name) to use for authentication.
+    pub fn user(
+        mut self,
+        user: &str,
+    ) -> Self {
+        user.clone_into(&mut self.transport_args.user);
+        self.explicit_options.insert("user");
+
+        self
+    }
+
+    /// Sets the `username_pattern` for use when authenticating. This is the regex pattern used to
+    /// "know" when the device is prompting for the users username.
+    pub fn username_pattern(
+        mut self,
+        pattern: Regex,
+    ) -> Self {
+        self.channel_args.username_pattern = pattern;
+
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::credentials::{
+        Credential,
+        CredentialSource,
+    };
+    use crate::options::{
+        DriverOptions,
+        ExecOptions,
+        SerialOptions,
+        TcpOptions,
+    };
+    use crate::transport::base::TransportType;
+    use crate::transport::exec::ExecPreset;
+    use crate::transport::serial::{
+        FlowControl,
+        Parity,
+        StopBits,
+    };
+    use core::time::Duration;
+    use regex::bytes::Regex;
+    use std::error::Error;
+
+    type TestResult = Result<(), Box<dyn Error>>;
+
+    fn value(text: &str) -> Option<CredentialSource> {
+        Some(CredentialSource::Value(text.to_owned()))
+    }
+
+    fn secret(credential: &Credential) -> Result<String, Box<dyn Error>> {
+        Ok(credential.secret()?.expose_secret().to_owned())
+    }
+
+    fn strings(values: &[&str]) -> Vec<String> {
+        values.iter().map(|&text| text.to_owned()).collect()
+    }
+
+    /// Returns options with every option set to something other than the builder default -- no
+    /// `..Default::default()` here so that new options must be added to the test.
+    fn every_option() -> Result<DriverOptions, regex::Error> {
+        Ok(DriverOptions {
+            auth_bypass: Some(true),
+            console_mode: Some(true),
+            console_password: value("console-secret"),
+            console_user: Some(String::from("console-user")),
+            default_desired_privilege_level: Some(String::from("configuration")),
+            exec: Some(ExecOptions {
+                command: Some(strings(&["/bin/bash", "-l"])),
+                container: Some(String::from("sidecar")),
+                extra_args: Some(strings(&["--context", "lab"])),
+                namespace: Some(String::from("network")),
+                open_bin: Some(String::from("/opt/bin/kubectl")),
+                preset: Some(ExecPreset::Kubectl),
+                target: Some(String::from("router-0")),
+            }),
+            failed_when_contains: Some(strings(&["% Bad input"])),
+            host: String::from("router"),
+            passphrase_pattern: Some(Regex::new("passphrase-pattern")?),
+            password: value("secret"),
+            password_pattern: Some(Regex::new("password-pattern")?),
+            platform_type: Some(String::from("cisco_iosxe")),
+            port: Some(2022),
+            prompt_pattern: Some(Regex::new("prompt-pattern")?),
+            read_delay: Some(Duration::from_millis(7)),
+            return_char: Some(String::from("\r")),
+            secondary_password: value("enable-secret"),
+            serial: Some(SerialOptions {
+                baud_rate: Some(115_200),
+                break_on_open: Some(true),
+                device: Some(String::from("/dev/ttyUSB1")),
+                flow_control: Some(FlowControl::Hardware),
+                parity: Some(Parity::Even),
+                stop_bits: Some(StopBits::Two),
+                wake_returns: Some(3),
+            }),
+            ssh_config_file_path: Some(String::from("/ssh/config")),
+            ssh_known_hosts_file_path: Some(String::from("/ssh/known_hosts")),
+            ssh_private_key_passphrase: value("key-secret"),
+            ssh_private_key_path: Some(String::from("/ssh/id_ed25519")),
+            ssh_strict_key: Some(false),
+            structured_output_pipe: Some(String::from("| json")),
+            system_extra_args: Some(strings(&["-o", "Compression=yes"])),
+            system_open_bin: Some(String::from("/opt/bin/ssh")),
+            tcp: Some(TcpOptions {
+                read_poll_interval: Some(Duration::from_millis(20)),
+                tls: Some(true),
+                tls_ca_file: Some(String::from("/tls/ca.pem")),
+                tls_insecure: Some(true),
+                tls_server_name: Some(String::from("router.example.com")),
+            }),
+            timeout_open: Some(Duration::from_secs(11)),
+            timeout_ops: Some(Duration::from_secs(12)),
+            timeout_socket: Some(Duration::from_secs(13)),
+            transport_type: Some(TransportType::Tcp),
+            user: Some(String::from("admin")),
+            username_pattern: Some(Regex::new("username-pattern")?),
+        })
+    }
+
+    #[test]
+    fn generic_driver_builder_applies_every_option() -> TestResult {
+        let builder = every_option()?.generic_driver_builder();
+
+        let args = &builder.args;
+        assert_eq!(args.host, "router", "host");
+        assert_eq!(args.port, 2022, "port");
+        assert_eq!(args.platform_type, "cisco_iosxe", "platform type");
+        assert_eq!(args.structured_output_pipe, "| json", "pipe");
+        assert_eq!(
+            args.failed_when_contains,
+            strings(&["% Bad input"]),
+            "failed when contains"
+        );
+
+        let channel_args = &builder.channel_args;
+        assert!(channel_args.auth_bypass, "auth bypass");
+        assert_eq!(
+            channel_args.prompt_pattern.as_str(),
+            "prompt-pattern",
+            "prompt pattern"
+        );
+        assert_eq!(
+            channel_args.username_pattern.as_str(),
+            "username-pattern",
+            "username pattern"
+        );
+        assert_eq!(
+            channel_args.password_pattern.as_str(),
+            "password-pattern",
+            "password pattern"
+        );
+        assert_eq!(
+            channel_args.passphrase_pattern.as_str(),
+            "passphrase-pattern",
+            "passphrase pattern"
+        );
+        assert_eq!(channel_args.return_char, "\r", "return char");
+        assert_eq!(
+            channel_args.read_delay,
+            Duration::from_millis(7),
+            "read delay"
+        );
+        assert_eq!(
+            channel_args.timeout_open,
+            Duration::from_secs(11),
+            "timeout open"
+        );
+        assert_eq!(
+            channel_args.timeout_ops,
+            Duration::from_secs(12),
+            "timeout ops"
+        );
+        assert!(channel_args.console_mode, "console mode");
+        assert_eq!(channel_args.console_user, "console-user", "console user");
+        assert_eq!(
+            secret(&channel_args.console_password)?,
+            "console-secret",
+            "console password"
+        );
+
+        assert_eq!(builder.transport_type, TransportType::Tcp, "transport type");
+
+        let transport_args = &builder.transport_args;
+        assert_eq!(transport_args.user, "admin", "user");
+        assert_eq!(secret(&transport_args.password)?, "secret", "password");
+        assert_eq!(transport_args.port, 2022, "transport port");
+        assert_eq!(
+            transport_args.timeout_socket,
+            Duration::from_secs(13),
+            "timeout socket"
+        );
+
+        let ssh_args = &builder.transport_ssh_args;
+        assert_eq!(
+            ssh_args.host_key_policy.strict_host_key_checking(),
+            "no",
+            "ssh strict key"
+        );
+        assert_eq!(ssh_args.config_file_path, "/ssh/config", "ssh config");
+        assert_eq!(
+            ssh_args.known_hosts_file_path, "/ssh/known_hosts",
+            "ssh known hosts"
+        );
+        assert_eq!(ssh_args.private_key_path, "/ssh/id_ed25519", "ssh key");
+        assert_eq!(
+            secret(&ssh_args.private_key_passphrase)?,
+            "key-secret",
+            "ssh key passphrase"
+        );
+
+        let system_args = &builder.transport_system_args;
+        assert_eq!(system_args.open_bin, "/opt/bin/ssh", "system open bin");
+        assert_eq!(
+            system_args.extra_args,
+            strings(&["-o", "Compression=yes"]),
+            "system extra args"
+        );
+
+        let serial_args = &builder.transport_serial_args;
+        assert_eq!(serial_args.device, "/dev/ttyUSB1", "serial device");
+        assert_eq!(serial_args.baud_rate, 115_200, "serial baud rate");
+        assert_eq!(serial_args.parity, Parity::Even, "serial parity");
+        assert_eq!(serial_args.stop_bits, StopBits::Two, "serial stop bits");
+        assert_eq!(
+            serial_args.flow_control,
+            FlowControl::Hardware,
+            "serial flow control"
+        );
+        assert!(serial_args.break_on_open, "serial break on open");
+        assert_eq!(serial_args.wake_returns, 3, "serial wake returns");
+
+        let exec_args = &builder.transport_exec_args;
+        assert_eq!(exec_args.preset, ExecPreset::Kubectl, "exec preset");
+        assert_eq!(exec_args.open_bin, "/opt/bin/kubectl", "exec open bin");
+        assert_eq!(exec_args.target, "router-0", "exec target");
+        assert_eq!(exec_args.namespace, "network", "exec namespace");
+        assert_eq!(exec_args.container, "sidecar", "exec container");
+        assert_eq!(
+            exec_args.command,
+            strings(&["/bin/bash", "-l"]),
+            "exec command"
+        );
+        assert_eq!(
+            exec_args.extra_args,
+            strings(&["--context", "lab"]),
+            "exec extra args"
+        );
+
+        let tcp_args = &builder.transport_tcp_args;
+        assert_eq!(
+            tcp_args.read_poll_interval,
+            Duration::from_millis(20),
+            "tcp read poll interval"
+        );
+        assert!(tcp_args.tls, "tcp tls");
+        assert_eq!(
+            tcp_args.tls_server_name, "router.example.com",
+            "tcp tls server name"
+        );
+        assert_eq!(tcp_args.tls_ca_file, "/tls/ca.pem", "tcp tls ca file");
+        assert!(tcp_args.tls_insecure, "tcp tls insecure");
+
+        Ok(())
+    }
+
+    #[test]
+    fn network_driver_builder_applies_every_option() -> TestResult {
+        let driver = every_option()?.network_driver_builder().build();
+
+        assert_eq!(
+            secret(&driver.args.secondary_password)?,
+            "enable-secret",
+            "secondary password"
+        );
+        assert_eq!(
+            driver.args.default_desired_privilege_level, "configuration",
+            "default desired privilege level"
+        );
+        assert_eq!(
+            driver.generic_driver.args.platform_type, "cisco_iosxe",
+            "generic options are applied too"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn unset_options_keep_builder_defaults() -> TestResult {
+        let builder = DriverOptions {
+            host: String::from("router"),
+            ..DriverOptions::default()
+        }
+        .generic_driver_builder();
+
+        let default_builder = super::Builder::new("router");
+
+        assert_eq!(builder.args.port, default_builder.args.port, "default port");
+        assert_eq!(
+            builder.channel_args.timeout_ops, default_builder.channel_args.timeout_ops,
+            "default timeout ops"
+        );
+        assert_eq!(
+            builder.transport_type,
+            TransportType::System,
+            "default transport type"
+        );
+        assert!(builder.explicit_options.is_empty(), "no explicit options");
 
-        Driver::new(self.args, c)
+        Ok(())
     }
 }
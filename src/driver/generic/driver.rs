@@ -1,20 +1,73 @@
-use crate::channel::Channel;
 use crate::channel::OperationOptions as ChannelOperationOptions;
-use crate::errors::ScrapliError;
+use crate::channel::{
+    CancelHandle,
+    Channel,
+    SendInteractiveEvents,
+};
+use crate::driver::interceptor::{
+    self,
+    Interceptor,
+    Operation,
+};
+use crate::errors::{
+    ErrorKind,
+    OpenPhase,
+    ScrapliError,
+};
+use crate::platform::Platform;
 use crate::response::{
+    FailurePattern,
     MultiResponse,
     Response,
 };
 use crate::transport::base::DEFAULT_PORT;
+use core::mem;
 use log::{
     debug,
+    error,
     info,
 };
 
-/// The custom type for generic driver on open/close callables. The `on_open` callable will be
-/// executed immediately after authentication and before returning from the `open` method, while the
-/// `on_close` variant will be called before closing the transport/channel.
-pub type GenericDriverOnXCallable = fn(d: &Driver) -> Result<(), ScrapliError>;
+/// The custom type for generic driver lifecycle hooks.
+///
+/// Hooks (`pre_open`, `post_auth`, `on_open`, `pre_close` and `on_close`) are closures, so they can
+/// capture state (ex: config to push), and receive the driver mutably so they can send commands.
+///
+/// On open, `pre_open` is executed before the channel (and transport) is opened, `post_auth`
+/// immediately after in channel authentication, and `on_open` after that -- all before returning
+/// from the `open` method. On close, `pre_close` and then `on_close` are executed before closing the
+/// channel (and transport).
+pub type GenericDriverOnXCallable = Box<dyn FnMut(&mut Driver) -> Result<(), ScrapliError> + Send>;
+
+/// The custom type for the generic driver `on_error` hook.
+///
+/// The hook is executed with the error if opening, closing or sending to the driver fails, the
+/// error is still returned after the hook runs. Sends failing within other hooks while opening or
+/// closing only run the hook once, with the error returned from `open`/`close`. Note that the
+/// channel may not be usable when the hook is executed!
+pub type GenericDriverOnErrorCallable = Box<dyn FnMut(&mut Driver, &ScrapliError) + Send>;
+
+/// The lifecycle hooks of a generic driver.
+#[derive(Debug, Clone, Copy)]
+enum Hook {
+    PreOpen,
+    PostAuth,
+    OnOpen,
+    PreClose,
+    OnClose,
+}
+
+impl Hook {
+    const fn name(self) -> &'static str {
+        match self {
+            Self::PreOpen => "pre_open",
+            Self::PostAuth => "post_auth",
+            Self::OnOpen => "on_open",
+            Self::PreClose => "pre_close",
+            Self::OnClose => "on_close",
+        }
+    }
+}
 
 /// `OperationOptions` holds arguments that apply to `Driver` operations (ex: `send_command`).
 #[derive(Default, Clone)]
@@ -22,6 +75,9 @@ pub struct OperationOptions {
     /// List of strings that when seen as sub strings in some output indicate that the operation was
     /// a failure.
     pub failed_when_contains: Vec<String>,
+    /// List of regex patterns (with a severity) that when seen in some output indicate that the
+    /// operation was a failure (or warning).
+    pub failure_patterns: Vec<FailurePattern>,
     /// Indicates if multi operations (send_commands (plural!)) that encounter a failure (based on
     /// `failed_when_contains` output) should stop or not.
     pub stop_on_failed: bool,
@@ -37,13 +93,33 @@ pub struct Args {
     pub host: String,
     /// The port on the host the driver is connecting to.
     pub port: u16,
+    /// The platform type (ex: `cisco_iosxe`) of the device, copied into each `Response` so that
+    /// native parsers can be selected. Empty if not set.
+    pub platform_type: String,
+    /// The pipe (ex: "| json") appended to commands sent with `send_command_structured`, if empty
+    /// the structured output pipe of the `platform_type` platform definition is used.
+    pub structured_output_pipe: String,
     /// The list of strings which indicate command failures.
     pub failed_when_contains: Vec<String>,
-    /// The "on open" callable that is executed (if set) immediately after authenticating.
+    /// The list of regex patterns which indicate command failures (or warnings).
+    pub failure_patterns: Vec<FailurePattern>,
+    /// The "pre open" callable that is executed (if set) before opening the channel.
+    pub(crate) pre_open: Option<GenericDriverOnXCallable>,
+    /// The "post auth" callable that is executed (if set) immediately after authenticating.
+    pub(crate) post_auth: Option<GenericDriverOnXCallable>,
+    /// The "on open" callable that is executed (if set) after authenticating and the `post_auth`
+    /// callable.
     pub(crate) on_open: Option<GenericDriverOnXCallable>,
+    /// The "pre close" callable that is executed (if set) before the `on_close` callable.
+    pub(crate) pre_close: Option<GenericDriverOnXCallable>,
     /// The "on close" callable that is executed (if set) right before closing the channel and the
     /// underlying transport.
     pub(crate) on_close: Option<GenericDriverOnXCallable>,
+    /// The "on error" callable that is executed (if set) when opening, closing or sending fails.
+    pub(crate) on_error: Option<GenericDriverOnErrorCallable>,
+    /// The interceptors that every (command, config and interactive) operation is passed through,
+    /// in order.
+    pub(crate) interceptors: Vec<Box<dyn Interceptor>>,
 }
 
 impl Args {
@@ -54,9 +130,30 @@ impl Args {
         Self {
             host: host.to_owned(),
             port: DEFAULT_PORT,
+            platform_type: String::new(),
+            structured_output_pipe: String::new(),
             failed_when_contains: vec![],
+            failure_patterns: vec![],
+            pre_open: None,
+            post_auth: None,
             on_open: None,
+            pre_close: None,
             on_close: None,
+            on_error: None,
+            interceptors: vec![],
+        }
+    }
+
+    const fn hook(
+        &mut self,
+        hook: Hook,
+    ) -> &mut Option<GenericDriverOnXCallable> {
+        match hook {
+            Hook::PreOpen => &mut self.pre_open,
+            Hook::PostAuth => &mut self.post_auth,
+            Hook::OnOpen => &mut self.on_open,
+            Hook::PreClose => &mut self.pre_close,
+            Hook::OnClose => &mut self.on_close,
         }
     }
 }
@@ -83,54 +180,145 @@ impl Driver {
 
     /// Open the driver and the underlying channel and transport.
     ///
+    /// Opening (connecting, authenticating and running the `post_auth` and `on_open` hooks) must
+    /// complete within the channel `timeout_open` and can be cancelled from another thread via the
+    /// `CancelHandle` returned by `cancel_handle`.
+    ///
     /// # Errors
     ///
-    /// Can return an error if opening the channel fails. Can also return an error if the `on_open`
-    /// callable is set and it returns an error.
+    /// Can return an error if opening the channel fails. Can also return an error if any of the
+    /// `pre_open`, `post_auth` or `on_open` hooks are set and return an error. Returns an
+    /// `ErrorKind::OpenTimedOut` (or `ErrorKind::OpenCancelled`) error naming the phase that stalled
+    /// if the open does not complete in time (or is cancelled). The channel is closed and the
+    /// `on_error` hook (if set) is executed with any error before it is returned.
     pub fn open(&mut self) -> Result<(), ScrapliError> {
+        // set aside so sends failing within the hooks do not run it, it runs once below instead
+        let on_error = self.args.on_error.take();
+
+        let result = self.open_channel().and_then(|()| self.run_on_open());
+
+        self.args.on_error = on_error;
+
+        self.channel.exit_open_phase();
+
+        if let Err(err) = result {
+            // whatever failed, do not leave the transport (and read loop) running
+            if let Err(close_err) = self.channel.close() {
+                error!("failed closing channel after failed open, error: {close_err}");
+            }
+
+            self.run_on_error(&err);
+
+            return Err(err);
+        }
+
+        info!("connection opened successfully");
+
+        Ok(())
+    }
+
+    /// Runs the `pre_open` hook, opens the channel and runs the `post_auth` hook (in the prompt
+    /// discovery phase of the open) -- callers are responsible for calling `exit_open_phase` on the
+    /// channel afterward.
+    pub(crate) fn open_channel(&mut self) -> Result<(), ScrapliError> {
         debug!(
             "opening connection to host {} on port {}",
             self.args.host, self.args.port
         );
 
+        self.run_hook(Hook::PreOpen)?;
+
         self.channel.open()?;
 
-        if let Some(f) = self.args.on_open {
-            debug!("generic driver `on_open` set, executing");
+        self.channel.enter_open_phase(OpenPhase::PromptDiscovery);
 
-            f(self)?;
-        }
+        self.run_hook(Hook::PostAuth)
+    }
 
-        info!("connection opened successfully");
+    /// Runs the `on_open` hook -- the final step of opening the driver.
+    pub(crate) fn run_on_open(&mut self) -> Result<(), ScrapliError> {
+        self.run_hook(Hook::OnOpen)
+    }
 
-        Ok(())
+    /// Executes the given hook (if set). The hook is taken out of the args while it executes so
+    /// that it can be handed the (mutable) driver.
+    fn run_hook(
+        &mut self,
+        hook: Hook,
+    ) -> Result<(), ScrapliError> {
+        let Some(mut f) = self.args.hook(hook).take() else {
+            return Ok(());
+        };
+
+        debug!("generic driver `{}` set, executing", hook.name());
+
+        let result = f(self);
+
+        *self.args.hook(hook) = Some(f);
+
+        result
+    }
+
+    fn run_on_error(
+        &mut self,
+        err: &ScrapliError,
+    ) {
+        let Some(mut f) = self.args.on_error.take() else {
+            return;
+        };
+
+        debug!("generic driver `on_error` set, executing");
+
+        f(self, err);
+
+        self.args.on_error = Some(f);
+    }
+
+    /// Returns a `CancelHandle` that can be used (from any thread) to cancel opening the driver.
+    #[must_use]
+    pub fn cancel_handle(&self) -> CancelHandle {
+        self.channel.cancel_handle()
     }
 
     /// Close the driver and the underlying channel and transport.
     ///
     /// # Errors
     ///
-    /// Can return an error if closing the channel fails. Can also return an error if the `on_close`
-    /// callable is set and it returns an error.
+    /// Can return an error if closing the channel fails. Can also return an error if the
+    /// `pre_close` or `on_close` hooks are set and return an error. The `on_error` hook (if set) is
+    /// executed with any error before it is returned.
     pub fn close(&mut self) -> Result<(), ScrapliError> {
-        debug!(
-            "closing connection to host {} on port {}",
-            self.args.host, self.args.port
-        );
+        // set aside so sends failing within the hooks do not run it, it runs once below instead
+        let on_error = self.args.on_error.take();
 
-        if let Some(f) = self.args.on_open {
-            debug!("generic driver `on_close` set, executing");
+        let result = self.close_channel();
 
-            f(self)?;
-        }
+        self.args.on_error = on_error;
 
-        self.channel.close()?;
+        if let Err(err) = result {
+            self.run_on_error(&err);
+
+            return Err(err);
+        }
 
         info!("connection closed successfully");
 
         Ok(())
     }
 
+    /// Runs the `pre_close` and `on_close` hooks and then closes the channel.
+    pub(crate) fn close_channel(&mut self) -> Result<(), ScrapliError> {
+        debug!(
+            "closing connection to host {} on port {}",
+            self.args.host, self.args.port
+        );
+
+        self.run_hook(Hook::PreClose)?;
+        self.run_hook(Hook::OnClose)?;
+
+        self.channel.close()
+    }
+
     /// Return the current "prompt" from the device.
     ///
     /// # Errors
@@ -149,6 +337,7 @@ impl Driver {
             }
             Err(err) => Err(ScrapliError {
                 details: format!("error fetching prompt from channel, error: {err}"),
+                kind: err.kind,
             }),
         }
     }
@@ -172,26 +361,30 @@ impl Driver {
     ///
     /// # Errors
     ///
-    /// Returns a `ScrapliError` if something that cannot be recovered from occurs.
+    /// Returns a `ScrapliError` if something that cannot be recovered from occurs, or if an
+    /// interceptor blocks the command.
     pub fn send_command_with_options(
         &mut self,
         command: &str,
         options: &OperationOptions,
     ) -> Result<Response, ScrapliError> {
-        info!("send_command requested, sending '{}'", command);
+        self.intercept(Operation::Command(command.to_owned()), |driver, op| {
+            driver.execute_command(op.input()?, options)
+        })
+    }
 
-        let opts = &mut options.clone();
+    /// Sends the command to the device *without* passing it through the interceptors -- used by
+    /// the network driver which intercepts operations itself.
+    pub(crate) fn execute_command(
+        &mut self,
+        command: &str,
+        options: &OperationOptions,
+    ) -> Result<Response, ScrapliError> {
+        info!("send_command requested, sending '{}'", command);
 
-        if options.failed_when_contains.is_empty() {
-            opts.failed_when_contains = self.args.failed_when_contains.clone();
-        }
+        let opts = self.merge_operation_options(options);
 
-        let mut resp = Response::new(
-            command,
-            self.args.host.as_str(),
-            self.args.port,
-            opts.failed_when_contains.clone(),
-        );
+        let mut resp = self.new_response(command, &opts);
 
         match self
             .channel
@@ -206,6 +399,211 @@ impl Driver {
         }
     }
 
+    /// Send "interactive" events to the device (see the channel `send_interactive`) and return a
+    /// `Response` holding the output of all the events -- the input of the `Response` is the input
+    /// of each event (hidden inputs redacted) joined by newlines.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ScrapliError` if something that cannot be recovered from occurs, or if an
+    /// interceptor blocks the events.
+    pub fn send_interactive(
+        &mut self,
+        events: &SendInteractiveEvents,
+        options: &OperationOptions,
+    ) -> Result<Response, ScrapliError> {
+        self.intercept(Operation::Interactive(events.clone()), |driver, op| {
+            driver.execute_interactive(op.events()?, options)
+        })
+    }
+
+    /// Sends the interactive events to the device *without* passing them through the
+    /// interceptors.
+    pub(crate) fn execute_interactive(
+        &mut self,
+        events: &SendInteractiveEvents,
+        options: &OperationOptions,
+    ) -> Result<Response, ScrapliError> {
+        info!("send_interactive requested, processing events {}", events);
+
+        let opts = self.merge_operation_options(options);
+
+        let input = events
+            .iter()
+            .map(|event| {
+                if event.hidden {
+                    "<hidden>"
+                } else {
+                    event.input.as_str()
+                }
+            })
+            .collect::<Vec<&str>>()
+            .join("\n");
+
+        let mut resp = self.new_response(input.as_str(), &opts);
+
+        resp.record(
+            self.channel
+                .send_interactive(events, &opts.channel_operation_options)?,
+        );
+
+        Ok(resp)
+    }
+
+    /// Returns a copy of the given options with the driver `failed_when_contains` and
+    /// `failure_patterns` filled in if the options do not set them.
+    pub(crate) fn merge_operation_options(
+        &self,
+        options: &OperationOptions,
+    ) -> OperationOptions {
+        let mut opts = options.clone();
+
+        if options.failed_when_contains.is_empty() {
+            opts.failed_when_contains
+                .clone_from(&self.args.failed_when_contains);
+        }
+
+        if options.failure_patterns.is_empty() {
+            opts.failure_patterns
+                .clone_from(&self.args.failure_patterns);
+        }
+
+        opts
+    }
+
+    /// Returns a new `Response` for the input with the failure settings of the options (see
+    /// `merge_operation_options`) and the driver host, port and platform type.
+    pub(crate) fn new_response(
+        &self,
+        input: &str,
+        opts: &OperationOptions,
+    ) -> Response {
+        let mut resp = Response::new(
+            input,
+            self.args.host.as_str(),
+            self.args.port,
+            opts.failed_when_contains.clone(),
+        );
+        resp.failure_patterns.clone_from(&opts.failure_patterns);
+        resp.platform_type.clone_from(&self.args.platform_type);
+
+        resp
+    }
+
+    /// Adds an interceptor to the end of the driver's interceptor pipeline.
+    pub fn add_interceptor<I: Interceptor + 'static>(
+        &mut self,
+        i: I,
+    ) {
+        self.args.interceptors.push(Box::new(i));
+    }
+
+    /// Passes the operation through the interceptors, with `exec` executing the (possibly
+    /// rewritten) operation at the end of the pipeline. The `on_error` hook (if set) is executed if
+    /// the operation fails.
+    fn intercept<F>(
+        &mut self,
+        op: Operation,
+        mut exec: F,
+    ) -> Result<Response, ScrapliError>
+    where
+        F: FnMut(&mut Self, &Operation) -> Result<Response, ScrapliError>,
+    {
+        let mut interceptors = mem::take(&mut self.args.interceptors);
+
+        let result = interceptor::run(&mut interceptors, op, &mut |op| exec(self, op));
+
+        self.args.interceptors = interceptors;
+
+        if let Err(err) = result.as_ref() {
+            self.run_on_error(err);
+        }
+
+        result
+    }
+
+    /// Returns the command with the structured output pipe (ex: "| json") appended -- the pipe is
+    /// the `structured_output_pipe` arg if set, otherwise the pipe of the platform definition of the
+    /// `platform_type`. Commands already ending with the pipe are returned as is.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ScrapliError` if no structured output pipe is set and the platform type is unknown
+    /// or does not support structured output.
+    pub fn structured_command(
+        &self,
+        command: &str,
+    ) -> Result<String, ScrapliError> {
+        let structured_output_pipe = if self.args.structured_output_pipe.is_empty() {
+            if self.args.platform_type.is_empty() {
+                return Err(ScrapliError {
+                    details: String::from(
+                        "structured output requested but no structured output pipe or platform \
+                        type set",
+                    ),
+                    kind: ErrorKind::Generic,
+                });
+            }
+
+            Platform::new(self.args.platform_type.as_str())?.structured_output_pipe
+        } else {
+            self.args.structured_output_pipe.clone()
+        };
+
+        if structured_output_pipe.is_empty() {
+            return Err(ScrapliError {
+                details: format!(
+                    "platform type '{}' does not support structured output",
+                    self.args.platform_type
+                ),
+                kind: ErrorKind::Generic,
+            });
+        }
+
+        if command
+            .trim_end()
+            .ends_with(structured_output_pipe.as_str())
+        {
+            return Ok(command.to_owned());
+        }
+
+        Ok(format!("{} {structured_output_pipe}", command.trim_end()))
+    }
+
+    /// Send a command to the device with the platform's structured output pipe (ex: "| json")
+    /// appended and return the parsed json payload.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ScrapliError` if the platform does not support structured output, if something
+    /// that cannot be recovered from occurs, if the device returns a cli error or if the output
+    /// cannot be parsed.
+    pub fn send_command_structured(
+        &mut self,
+        command: &str,
+    ) -> Result<serde_json::Value, ScrapliError> {
+        self.send_command_structured_with_options(command, &OperationOptions::default())
+    }
+
+    /// Send a command to the device with the platform's structured output pipe (ex: "| json")
+    /// appended and return the parsed json payload, with optional options struct provided.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ScrapliError` if the platform does not support structured output, if something
+    /// that cannot be recovered from occurs, if the device returns a cli error or if the output
+    /// cannot be parsed.
+    pub fn send_command_structured_with_options(
+        &mut self,
+        command: &str,
+        options: &OperationOptions,
+    ) -> Result<serde_json::Value, ScrapliError> {
+        let structured_command = self.structured_command(command)?;
+
+        self.send_command_with_options(structured_command.as_str(), options)?
+            .json()
+    }
+
     /// Send a list of commands to the device.
     ///
     /// # Errors
@@ -235,6 +633,7 @@ impl Driver {
         if commands.is_empty() {
             return Err(ScrapliError {
                 details: String::from("send_commands called with empty vec of commands"),
+                kind: ErrorKind::Generic,
             });
         }
 
@@ -0,0 +1,260 @@
+use crate::channel::SendInteractiveEvents;
+use crate::errors::{
+    ErrorKind,
+    ScrapliError,
+};
+use crate::response::Response;
+use core::fmt;
+use log::info;
+use regex::Regex;
+
+/// An `Operation` is the input of a driver operation as seen by interceptors -- interceptors may
+/// rewrite the input (ex: append "| no-more" to a command) before passing it on.
+#[derive(Clone)]
+pub enum Operation {
+    /// A command sent via `send_command` (or `send_commands`, once for each command).
+    Command(String),
+    /// A config sent via the network driver `send_configs` (once for each config line) or
+    /// `send_config` -- in eager mode the entire config "blob" is a single operation.
+    Config(String),
+    /// The events sent via `send_interactive`.
+    Interactive(SendInteractiveEvents),
+}
+
+impl Operation {
+    /// Returns the events of the operation, erroring if an interceptor replaced the operation with
+    /// a command or config one.
+    #[expect(
+        clippy::pattern_type_mismatch,
+        reason = "binding the variant values without this needs `ref` patterns, which are linted too"
+    )]
+    pub(crate) fn events(&self) -> Result<&SendInteractiveEvents, ScrapliError> {
+        match self {
+            Self::Interactive(events) => Ok(events),
+            Self::Command(_) | Self::Config(_) => Err(ScrapliError {
+                details: format!(
+                    "interceptor replaced an interactive operation with a {} operation",
+                    self.kind()
+                ),
+                kind: ErrorKind::Generic,
+            }),
+        }
+    }
+
+    /// Returns the command or config of the operation, erroring if an interceptor replaced the
+    /// operation with an interactive one.
+    #[expect(
+        clippy::pattern_type_mismatch,
+        reason = "binding the variant values without this needs `ref` patterns, which are linted too"
+    )]
+    pub(crate) fn input(&self) -> Result<&str, ScrapliError> {
+        match self {
+            Self::Command(input) | Self::Config(input) => Ok(input.as_str()),
+            Self::Interactive(_) => Err(ScrapliError {
+                details: String::from(
+                    "interceptor replaced a command/config operation with an interactive operation",
+                ),
+                kind: ErrorKind::Generic,
+            }),
+        }
+    }
+
+    /// Returns each input of the operation -- the command, each line of the config or the input of
+    /// each interactive event. Hidden interactive inputs (ex: passwords) are *not* returned, so
+    /// that interceptors never log (or error with) secrets.
+    #[must_use]
+    #[expect(
+        clippy::pattern_type_mismatch,
+        reason = "binding the variant values without this needs `ref` patterns, which are linted too"
+    )]
+    pub fn inputs(&self) -> Vec<&str> {
+        match self {
+            Self::Command(command) => vec![command.as_str()],
+            Self::Config(config) => config.lines().collect(),
+            Self::Interactive(events) => events
+                .iter()
+                .filter(|event| !event.hidden)
+                .map(|event| event.input.as_str())
+                .collect(),
+        }
+    }
+
+    /// Returns the name of the operation kind (ex: "command").
+    #[must_use]
+    pub const fn kind(&self) -> &'static str {
+        match *self {
+            Self::Command(_) => "command",
+            Self::Config(_) => "config",
+            Self::Interactive(_) => "interactive",
+        }
+    }
+}
+
+impl fmt::Display for Operation {
+    #[expect(
+        clippy::pattern_type_mismatch,
+        reason = "binding the variant values without this needs `ref` patterns, which are linted too"
+    )]
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter,
+    ) -> fmt::Result {
+        match self {
+            Self::Command(command) => write!(f, "command '{command}'"),
+            Self::Config(config) => write!(f, "config '{config}'"),
+            Self::Interactive(events) => write!(f, "interactive events {events}"),
+        }
+    }
+}
+
+/// An `Interceptor` sees every (command, config and interactive) operation of a driver.
+///
+/// Each interceptor receives the operation and the `Next` step of the pipeline -- the interceptor
+/// may rewrite the operation before calling `next.run`, block the operation by returning an error
+/// without calling `next.run` at all, inspect (or modify) the returned `Response`, or call
+/// `next.run` more than once to retry the operation. Interceptors are executed in the order they
+/// were registered, the first registered interceptor being the outermost.
+///
+/// Closures can be used as interceptors via `from_fn`.
+pub trait Interceptor: Send {
+    /// Intercepts the operation -- call `next.run` to execute the rest of the pipeline (and
+    /// ultimately the operation itself).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the interceptor blocks the operation, or returns the error of the
+    /// operation (or of a later interceptor).
+    fn intercept(
+        &mut self,
+        op: &mut Operation,
+        next: &mut Next<'_>,
+    ) -> Result<Response, ScrapliError>;
+}
+
+/// `Next` is the remainder of an interceptor pipeline -- the following interceptors and the
+/// operation itself.
+pub struct Next<'a> {
+    exec: &'a mut dyn FnMut(&Operation) -> Result<Response, ScrapliError>,
+    interceptors: &'a mut [Box<dyn Interceptor>],
+}
+
+impl<'a> Next<'a> {
+    #[expect(
+        clippy::single_call_fn,
+        reason = "keeps the pipeline construction next to the pipeline itself"
+    )]
+    pub(crate) fn new(
+        interceptors: &'a mut [Box<dyn Interceptor>],
+        exec: &'a mut dyn FnMut(&Operation) -> Result<Response, ScrapliError>,
+    ) -> Self {
+        Self { exec, interceptors }
+    }
+
+    /// Runs the remainder of the pipeline with the given operation.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a following interceptor blocks the operation or if the operation itself
+    /// fails.
+    pub fn run(
+        &mut self,
+        op: &mut Operation,
+    ) -> Result<Response, ScrapliError> {
+        match self.interceptors.split_first_mut() {
+            None => (self.exec)(op),
+            Some((interceptor, interceptors)) => interceptor.intercept(
+                op,
+                &mut Next {
+                    exec: &mut *self.exec,
+                    interceptors,
+                },
+            ),
+        }
+    }
+}
+
+/// `FnInterceptor` is an `Interceptor` wrapping a closure, see `from_fn`.
+#[expect(
+    clippy::module_name_repetitions,
+    reason = "the type names stay meaningful when imported outside of the module"
+)]
+pub struct FnInterceptor<F>(F);
+
+impl<F> Interceptor for FnInterceptor<F>
+where
+    F: FnMut(&mut Operation, &mut Next<'_>) -> Result<Response, ScrapliError> + Send,
+{
+    fn intercept(
+        &mut self,
+        op: &mut Operation,
+        next: &mut Next<'_>,
+    ) -> Result<Response, ScrapliError> {
+        (self.0)(op, next)
+    }
+}
+
+/// `DenyList` is an `Interceptor` that blocks any operation with an input matching one of its
+/// patterns (ex: `^\s*reload`) with an `ErrorKind::OperationDenied` error.
+pub struct DenyList {
+    patterns: Vec<Regex>,
+}
+
+impl DenyList {
+    /// Returns a new `DenyList` blocking inputs matching any of the given patterns.
+    #[must_use]
+    pub const fn new(patterns: Vec<Regex>) -> Self {
+        Self { patterns }
+    }
+}
+
+impl Interceptor for DenyList {
+    fn intercept(
+        &mut self,
+        op: &mut Operation,
+        next: &mut Next<'_>,
+    ) -> Result<Response, ScrapliError> {
+        for input in op.inputs() {
+            if let Some(pattern) = self.patterns.iter().find(|pattern| pattern.is_match(input)) {
+                info!(
+                    "{} input '{input}' denied by pattern '{pattern}'",
+                    op.kind()
+                );
+
+                return Err(ScrapliError {
+                    details: format!(
+                        "{} input '{input}' denied by pattern '{pattern}'",
+                        op.kind()
+                    ),
+                    kind: ErrorKind::OperationDenied {
+                        input: input.to_owned(),
+                        pattern: pattern.to_string(),
+                    },
+                });
+            }
+        }
+
+        next.run(op)
+    }
+}
+
+/// Returns an `Interceptor` executing the given closure for each operation.
+pub const fn from_fn<F>(closure: F) -> FnInterceptor<F>
+where
+    F: FnMut(&mut Operation, &mut Next<'_>) -> Result<Response, ScrapliError> + Send,
+{
+    FnInterceptor(closure)
+}
+
+/// Runs the operation through the interceptors, with `exec` executing the (possibly rewritten)
+/// operation at the end of the pipeline.
+#[expect(
+    clippy::pub_with_shorthand,
+    reason = "rustfmt rewrites `pub(in crate)` to the shorthand form"
+)]
+pub(crate) fn run(
+    interceptors: &mut [Box<dyn Interceptor>],
+    mut op: Operation,
+    exec: &mut dyn FnMut(&Operation) -> Result<Response, ScrapliError>,
+) -> Result<Response, ScrapliError> {
+    Next::new(interceptors, exec).run(&mut op)
+}
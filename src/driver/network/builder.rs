@@ -1,10 +1,17 @@
+use crate::credentials::Credential;
+use crate::defaults::{
+    OptionSource,
+    ResolvedOptions,
+};
+use crate::driver::interceptor::Interceptor;
 use crate::driver::network::driver::{
     Args,
     Driver,
-    NetworkDriverOnXCallable,
     PrivilegeLevel,
 };
 use crate::driver::GenericDriverBuilder;
+use crate::errors::ScrapliError;
+use regex::bytes::Regex;
 
 /// `Builder` is a struct that holds a bunch of settings/defaults that can be used to build a
 /// *network* Driver object -- you must also provide the *generic* driver builder as the network
@@ -12,26 +19,72 @@ use crate::driver::GenericDriverBuilder;
 pub struct Builder {
     generic_driver_builder: GenericDriverBuilder,
     args: Args,
+    secondary_password_explicit: bool,
 }
 
 #[allow(clippy::missing_const_for_fn)]
 #[allow(clippy::return_self_not_must_use)]
 #[allow(clippy::must_use_candidate)]
 impl Builder {
-    /// Return a new instance of `Builder` with sane defaults set.
+    /// Return a new instance of `Builder` with sane defaults set -- the `secondary_password` of
+    /// the `Defaults` the generic driver builder was created with (if any) is applied.
     pub fn new(generic_driver_builder: GenericDriverBuilder) -> Self {
+        let mut args = Args::default();
+
+        if let Some(secondary_password) = &generic_driver_builder.defaults().secondary_password {
+            args.secondary_password = secondary_password.clone();
+        }
+
         Self {
             generic_driver_builder,
-            args: Args::default(),
+            args,
+            secondary_password_explicit: false,
         }
     }
 
+    /// Returns the resolved value, and source, of each option that can be set via `Defaults` --
+    /// secrets are redacted.
+    pub fn resolved_options(&self) -> ResolvedOptions {
+        let mut resolved = self.generic_driver_builder.resolved_options();
+
+        let secondary_password = if self.args.secondary_password.is_set() {
+            "<redacted>"
+        } else {
+            "<unset>"
+        };
+
+        let source = if self.secondary_password_explicit {
+            OptionSource::Builder
+        } else {
+            self.generic_driver_builder
+                .defaults()
+                .source("secondary-password")
+        };
+
+        resolved.push("secondary-password", secondary_password.to_owned(), source);
+
+        resolved
+    }
+
     /// Sets the `secondary_password` password to use for (enable/escalate) authentication.
     pub fn secondary_password(
         mut self,
         s: &str,
     ) -> Self {
-        self.args.secondary_password = s.to_owned();
+        self.args.secondary_password = Credential::from_static(s);
+        self.secondary_password_explicit = true;
+
+        self
+    }
+
+    /// Sets the credential to use as the `secondary_password` for (enable/escalate)
+    /// authentication.
+    pub fn secondary_password_credential(
+        mut self,
+        c: Credential,
+    ) -> Self {
+        self.args.secondary_password = c;
+        self.secondary_password_explicit = true;
 
         self
     }
@@ -56,22 +109,117 @@ impl Builder {
         self
     }
 
-    /// Sets the `on_open` argument of a driver.
-    pub fn on_open(
+    /// Sets the `config_context_pattern` for the network driver -- the first capture group that
+    /// participates in a match of this pattern against the prompt is the configuration "context".
+    pub fn config_context_pattern(
         mut self,
-        f: NetworkDriverOnXCallable,
+        r: Regex,
     ) -> Self {
-        self.args.on_open = Some(f);
+        self.args.config_context_pattern = r;
+
+        self
+    }
+
+    /// Sets the `config_context_exit` input for the network driver -- this is the input sent to
+    /// exit one level of configuration context when returning to the root configuration context.
+    pub fn config_context_exit(
+        mut self,
+        s: &str,
+    ) -> Self {
+        self.args.config_context_exit = s.to_owned();
+
+        self
+    }
+
+    /// Sets the `pre_open` hook of a driver -- executed before opening the channel.
+    pub fn pre_open<F>(
+        mut self,
+        f: F,
+    ) -> Self
+    where
+        F: FnMut(&mut Driver) -> Result<(), ScrapliError> + Send + 'static,
+    {
+        self.args.pre_open = Some(Box::new(f));
+
+        self
+    }
+
+    /// Sets the `post_auth` hook of a driver -- executed immediately after in channel
+    /// authentication.
+    pub fn post_auth<F>(
+        mut self,
+        f: F,
+    ) -> Self
+    where
+        F: FnMut(&mut Driver) -> Result<(), ScrapliError> + Send + 'static,
+    {
+        self.args.post_auth = Some(Box::new(f));
+
+        self
+    }
+
+    /// Sets the `on_open` hook of a driver -- executed after authenticating (and the
+    /// `post_auth` hook), before returning from `open`.
+    pub fn on_open<F>(
+        mut self,
+        f: F,
+    ) -> Self
+    where
+        F: FnMut(&mut Driver) -> Result<(), ScrapliError> + Send + 'static,
+    {
+        self.args.on_open = Some(Box::new(f));
+
+        self
+    }
+
+    /// Sets the `pre_close` hook of a driver -- executed before the `on_close` hook.
+    pub fn pre_close<F>(
+        mut self,
+        f: F,
+    ) -> Self
+    where
+        F: FnMut(&mut Driver) -> Result<(), ScrapliError> + Send + 'static,
+    {
+        self.args.pre_close = Some(Box::new(f));
+
+        self
+    }
+
+    /// Sets the `on_close` hook of a driver -- executed right before closing the channel.
+    pub fn on_close<F>(
+        mut self,
+        f: F,
+    ) -> Self
+    where
+        F: FnMut(&mut Driver) -> Result<(), ScrapliError> + Send + 'static,
+    {
+        self.args.on_close = Some(Box::new(f));
+
+        self
+    }
+
+    /// Sets the `on_error` hook of a driver -- executed with the error if opening, closing or
+    /// sending to the driver fails.
+    pub fn on_error<F>(
+        mut self,
+        f: F,
+    ) -> Self
+    where
+        F: FnMut(&mut Driver, &ScrapliError) + Send + 'static,
+    {
+        self.args.on_error = Some(Box::new(f));
 
         self
     }
 
-    /// Sets the `on_close` argument of a driver.
-    pub fn on_close(
+    /// Adds an interceptor to the end of the driver's interceptor pipeline -- every command,
+    /// config and interactive operation is passed through the interceptors in the order they were
+    /// added. Interceptors are shared with (set on) the generic driver builder.
+    pub fn interceptor<I: Interceptor + 'static>(
         mut self,
-        f: NetworkDriverOnXCallable,
+        i: I,
     ) -> Self {
-        self.args.on_close = Some(f);
+        self.generic_driver_builder = self.generic_driver_builder.interceptor(i);
 
         self
     }
@@ -1,44 +1,138 @@
+extern crate alloc;
 use crate::channel::{
+    CancelHandle,
     OperationOptions as ChannelOperationOptions,
     SendInteractiveEvent,
     SendInteractiveEvents,
 };
+use crate::credentials::Credential;
+use crate::driver::interceptor::{
+    self,
+    Interceptor,
+    Operation,
+};
 use crate::driver::{
     GenericDriver,
     GenericDriverOperationOptions,
 };
-use crate::errors::ScrapliError;
+use crate::errors::{
+    ErrorKind,
+    ScrapliError,
+};
 use crate::response::{
     MultiResponse,
     Response,
 };
-use crate::util::strings::{
-    string_contains_any_substring,
-    string_vec_contains_substring,
+use crate::util::strings::string_contains_any_substring;
+use alloc::collections::BinaryHeap;
+use chrono::{
+    Duration as ChronoDuration,
+    Utc,
 };
+use core::cmp::Reverse;
+use core::mem;
+use core::ops::{
+    Deref,
+    DerefMut,
+};
+use core::slice;
 use log::{
     debug,
+    error,
     info,
 };
+use once_cell::sync::OnceCell;
 use regex::bytes::{
     Regex,
     RegexBuilder,
 };
-use std::collections::HashMap;
+use std::collections::{
+    HashMap,
+    HashSet,
+};
+use std::thread;
+use zeroize::Zeroize as _;
 
 const DEFAULT_CONFIGURATION_PRIVILEGE_LEVEL: &str = "configuration";
 
+/// The default cost of moving in to or out of a `PrivilegeLevel`.
+pub const DEFAULT_PRIVILEGE_LEVEL_COST: u32 = 1;
+
+/// The default input used to exit out of a configuration sub-context (ex: "interface" context).
+pub const DEFAULT_CONFIG_CONTEXT_EXIT: &str = "exit";
+
+/// The max number of times we send the `config_context_exit` input when trying to get back to the
+/// root configuration context before giving up.
+const MAX_CONFIG_CONTEXT_EXITS: u8 = 16;
+
 /// Note that this needs to be very high due to lots of use of char classes and obviously just
 /// combining them adds to this... one day it would be nice to somehow ultra simplify things, but
 /// that would be very difficult to do without potentially breaking lots of users.
 const COMBINED_PROMPT_REGEX_COMPILED_BYTES_LIMIT: usize = 25_000_000;
 
-/// The custom type for network driver on open/close callables. The `on_open` callable will be
-/// executed immediately after authentication and before returning from the `open` method, while the
-/// `on_close` variant will be called before closing the transport/channel. This is called *after*
-/// the "generic" driver on *open* callable and *before* the generic driver on *close* callable (if
-/// those are set!).
-pub type NetworkDriverOnXCallable = fn(d: &Driver) -> Result<(), ScrapliError>;
+/// # Panics
+///
+///  Returns (once), the complied default configuration context pattern -- the first capture group
+///  that participates in a match is the "context". Handles "cisco-like" `router(config-if)#`,
+///  junos `[edit interfaces]` (the line above the prompt), srl `--[ interface ethernet-1/1 ]--` and
+///  sros md-cli `*(ex)[/configure router "Base"]` style prompts. This should realisitcally never
+///  panic.
+#[allow(clippy::expect_used)]
+pub fn default_config_context_pattern() -> Regex {
+    static RE: OnceCell<Regex> = OnceCell::new();
+
+    RE.get_or_init(|| {
+        Regex::new(concat!(
+            r"(?m)\((config[^)]*)\)#\s*$",
+            r"|^\[edit\s?([^\]]*)\]",
+            r"|\}--\[\s*([^\]]*?)\s*\]--",
+            r"|^\*?\(\w+\)\[/?([^\]]*)\]",
+        ))
+        .expect("failed compiling pattern, this is a bug")
+    })
+    .clone()
+}
+
+/// The custom type for network driver lifecycle hooks.
+///
+/// Hooks (`pre_open`, `post_auth`, `on_open`, `pre_close` and `on_close`) behave the same as
+/// `GenericDriverOnXCallable` hooks but receive the network driver.
+///
+/// Network driver hooks wrap the generic driver hooks (if those are set!): on open the network
+/// `pre_open` hook is executed before the generic `pre_open` hook, while the network `post_auth`
+/// and `on_open` hooks are executed *after* their generic counterparts. On close the network hooks
+/// are executed *before* the generic hooks.
+pub type NetworkDriverOnXCallable = Box<dyn FnMut(&mut Driver) -> Result<(), ScrapliError> + Send>;
+
+/// The custom type for the network driver `on_error` hook.
+///
+/// The hook is executed with the error if opening, closing or sending to the driver fails (the
+/// generic driver `on_error` hook is *not* executed for network drivers), the error is still
+/// returned after the hook runs. Sends failing within other hooks while opening or closing only run
+/// the hook once, with the error returned from `open`/`close`.
+pub type NetworkDriverOnErrorCallable = Box<dyn FnMut(&mut Driver, &ScrapliError) + Send>;
+
+/// The lifecycle hooks of a network driver.
+#[derive(Debug, Clone, Copy)]
+enum Hook {
+    PreOpen,
+    PostAuth,
+    OnOpen,
+    PreClose,
+    OnClose,
+}
+
+impl Hook {
+    const fn name(self) -> &'static str {
+        match self {
+            Self::PreOpen => "pre_open",
+            Self::PostAuth => "post_auth",
+            Self::OnOpen => "on_open",
+            Self::PreClose => "pre_close",
+            Self::OnClose => "on_close",
+        }
+    }
+}
 
 /// `OperationOptions` holds arguments that apply to `Driver` operations (ex: `send_command`).
 #[derive(Default, Clone)]
@@ -46,10 +140,16 @@ pub struct OperationOptions {
     /// The "generic driver" `OperationOptions` which includes the even "lower level" channel
     /// `OperationOptions`.
     pub generic_driver_operation_options: GenericDriverOperationOptions,
-    /// The privilege level to execute the input in -- this only applies to `send_config`/
-    /// `send_configs` methods as the `send_command`/`send_commands` methods will always acquire the
-    /// `default_desired_privilege_level`.
+    /// The privilege level to execute the input in. For `send_config`/`send_configs` this defaults
+    /// to the "configuration" privilege level, for `send_command` this defaults to the privilege
+    /// level of any active `PrivilegeLevelGuard`, or the `default_desired_privilege_level`.
     pub privilege_level: String,
+    /// Indicates if `send_configs` should return to the "root" configuration context (the context
+    /// we were in right after acquiring the configuration privilege level) before each new block of
+    /// configs -- a block being any line that does *not* start with whitespace. This avoids sub-mode
+    /// state from previous lines (ex: still being in an "interface" context) leaking into the next
+    /// block.
+    pub return_to_root_config_context: bool,
 }
 
 /// `PrivilegeLevel` defines a privilege level, including a name, the pattern used to match a prompt
@@ -76,19 +176,48 @@ pub struct PrivilegeLevel {
     pub escalate_auth: bool,
     /// The prompt to expect if we have to authenticate when acquiring this `PrivilegeLevel`.
     pub escalate_prompt: String,
+    /// The "cost" of escalating into (or de-escalating out of) this `PrivilegeLevel`, when there
+    /// are multiple paths to a target privilege level the path with the lowest total cost is used.
+    /// Typically this can simply be `DEFAULT_PRIVILEGE_LEVEL_COST`.
+    pub cost: u32,
+    /// Jumps are "direct" transitions from this `PrivilegeLevel` to some other (non adjacent)
+    /// `PrivilegeLevel`, for example "end" to go from any configuration mode directly back to
+    /// privilege exec.
+    pub jumps: Vec<PrivilegeLevelJump>,
+}
+
+/// `PrivilegeLevelJump` defines a direct transition from one `PrivilegeLevel` to another that is
+/// not the "previous" privilege level (or a privilege level that has this level as its previous).
+pub struct PrivilegeLevelJump {
+    /// The name of the `PrivilegeLevel` this jump ends up in.
+    pub target_privilege_level: String,
+    /// The command to send to make the jump.
+    pub input: String,
+    /// The "cost" of this jump -- see `PrivilegeLevel.cost`.
+    pub cost: u32,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum PrivilegeAction {
     NoOp,
     Escalate,
     Deescalate,
+    Jump,
+}
+
+/// An edge in the privilege level graph, that is: the action to take to get from one privilege
+/// level to (adjacent) `target` privilege level and the cost of doing so.
+#[derive(Debug, Clone)]
+struct PrivilegeLevelEdge {
+    target: String,
+    action: PrivilegeAction,
+    cost: u32,
 }
 
 /// The (network) `Driver` arguments.
 pub struct Args {
     /// The "secondary" auth password (usually the "enable" password, or "sudo/root" password).
-    pub secondary_password: String,
+    pub secondary_password: Credential,
     /// The mapping of `PrivilegeLevel` for the `Driver` -- defines privilege levels such as "exec",
     /// "configuration", or "shell", etc..
     pub privilege_levels: Vec<PrivilegeLevel>,
@@ -96,12 +225,28 @@ pub struct Args {
     /// be sent at -- this privilege level is acquired automatically at login and before executing
     /// any send_command(s) operations.
     pub default_desired_privilege_level: String,
+    /// The pattern used to parse the configuration "context" (ex: "config-if") out of a prompt, the
+    /// first capture group that participates in a match is used as the context.
+    pub config_context_pattern: Regex,
+    /// The input to send to exit one level of configuration context (ex: "exit" or "up").
+    pub config_context_exit: String,
+    /// The "pre open" callable that is executed (if set) before the (if set) *generic* driver pre
+    /// open callable and before opening the channel.
+    pub(crate) pre_open: Option<NetworkDriverOnXCallable>,
+    /// The "post auth" callable that is executed (if set) after authenticating, and after the (if
+    /// set) *generic* driver post auth callable is executed.
+    pub(crate) post_auth: Option<NetworkDriverOnXCallable>,
     /// The "on open" callable that is executed (if set) after authenticating, and after the (if
     /// set) *generic* driver open callable is executed..
     pub(crate) on_open: Option<NetworkDriverOnXCallable>,
+    /// The "pre close" callable that is executed (if set) before the network driver `on_close`
+    /// callable.
+    pub(crate) pre_close: Option<NetworkDriverOnXCallable>,
     /// The "on close" callable that is executed (if set) right before executing the *generic*
     /// driver close callable and before closing the channel and the underlying transport
     pub(crate) on_close: Option<NetworkDriverOnXCallable>,
+    /// The "on error" callable that is executed (if set) when opening, closing or sending fails.
+    pub(crate) on_error: Option<NetworkDriverOnErrorCallable>,
 }
 
 impl Default for Args {
@@ -110,11 +255,32 @@ impl Default for Args {
     #[must_use]
     fn default() -> Self {
         Self {
-            secondary_password: String::new(),
+            secondary_password: Credential::default(),
             privilege_levels: vec![],
             default_desired_privilege_level: String::new(),
+            config_context_pattern: default_config_context_pattern(),
+            config_context_exit: String::from(DEFAULT_CONFIG_CONTEXT_EXIT),
+            pre_open: None,
+            post_auth: None,
             on_open: None,
+            pre_close: None,
             on_close: None,
+            on_error: None,
+        }
+    }
+}
+
+impl Args {
+    const fn hook(
+        &mut self,
+        hook: Hook,
+    ) -> &mut Option<NetworkDriverOnXCallable> {
+        match hook {
+            Hook::PreOpen => &mut self.pre_open,
+            Hook::PostAuth => &mut self.post_auth,
+            Hook::OnOpen => &mut self.on_open,
+            Hook::PreClose => &mut self.pre_close,
+            Hook::OnClose => &mut self.on_close,
         }
     }
 }
@@ -128,8 +294,57 @@ pub struct Driver {
     /// The `Driver` arguments (typically provided by a user or from a "platform").
     pub args: Args,
 
+    config_context: String,
     current_privilege_level: String,
-    privilege_level_graph: HashMap<String, HashMap<String, bool>>,
+    privilege_level_graph: HashMap<String, Vec<PrivilegeLevelEdge>>,
+    scoped_privilege_level: Option<String>,
+}
+
+/// `PrivilegeLevelGuard` is returned from `Driver::privilege_level_guard`.
+///
+/// While the guard is alive, `send_command` operations are sent at the guarded privilege level
+/// rather than the `default_desired_privilege_level`. When the guard is dropped the driver attempts
+/// to return to the privilege level it was at when the guard was created.
+pub struct PrivilegeLevelGuard<'a> {
+    driver: &'a mut Driver,
+    previous_privilege_level: String,
+    previous_scoped_privilege_level: Option<String>,
+}
+
+impl Deref for PrivilegeLevelGuard<'_> {
+    type Target = Driver;
+
+    fn deref(&self) -> &Self::Target {
+        self.driver
+    }
+}
+
+impl DerefMut for PrivilegeLevelGuard<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.driver
+    }
+}
+
+impl Drop for PrivilegeLevelGuard<'_> {
+    fn drop(&mut self) {
+        self.driver.scoped_privilege_level = self.previous_scoped_privilege_level.take();
+
+        debug!(
+            "privilege level guard dropped, returning to privilege level '{}'",
+            self.previous_privilege_level
+        );
+
+        if let Err(err) = self
+            .driver
+            .acquire_privilege_level(self.previous_privilege_level.as_str())
+        {
+            error!(
+                "failed returning to privilege level '{}' when dropping privilege level guard, \
+                error: {err}",
+                self.previous_privilege_level
+            );
+        }
+    }
 }
 
 impl Driver {
@@ -142,38 +357,206 @@ impl Driver {
         Self {
             generic_driver,
             args,
+            config_context: String::new(),
             current_privilege_level: String::new(),
             privilege_level_graph: HashMap::default(),
+            scoped_privilege_level: None,
         }
     }
 
-    fn build_privilege_level_graph(&mut self) {
+    fn validate_privilege_levels(&self) -> Result<(), ScrapliError> {
+        let mut privilege_level_names: HashSet<&str> = HashSet::new();
+
+        for privilege_level in &self.args.privilege_levels {
+            if !privilege_level_names.insert(privilege_level.name.as_str()) {
+                return Err(ScrapliError {
+                    details: format!(
+                        "privilege level '{}' is defined more than once",
+                        privilege_level.name
+                    ),
+                    kind: ErrorKind::Generic,
+                });
+            }
+        }
+
         for privilege_level in &self.args.privilege_levels {
-            let privilege_level_name = privilege_level.name.clone();
+            if !privilege_level.previous_privilege_level.is_empty()
+                && !privilege_level_names
+                    .contains(privilege_level.previous_privilege_level.as_str())
+            {
+                return Err(ScrapliError {
+                    details: format!(
+                        "privilege level '{}' has unknown previous privilege level '{}'",
+                        privilege_level.name, privilege_level.previous_privilege_level
+                    ),
+                    kind: ErrorKind::Generic,
+                });
+            }
+
+            for jump in &privilege_level.jumps {
+                if !privilege_level_names.contains(jump.target_privilege_level.as_str()) {
+                    return Err(ScrapliError {
+                        details: format!(
+                            "privilege level '{}' has jump to unknown privilege level '{}'",
+                            privilege_level.name, jump.target_privilege_level
+                        ),
+                        kind: ErrorKind::Generic,
+                    });
+                }
+            }
 
-            let previous_privilege_level_name = privilege_level.previous_privilege_level.clone();
+            // walk the "previous" chain, if we ever see a level twice the definitions are cyclic
+            let mut seen_privilege_levels: HashSet<&str> = HashSet::new();
+            let mut next_privilege_level = Some(privilege_level);
+
+            while let Some(walked_privilege_level) = next_privilege_level {
+                if !seen_privilege_levels.insert(walked_privilege_level.name.as_str()) {
+                    return Err(ScrapliError {
+                        details: format!(
+                            "privilege level '{}' previous privilege levels form a cycle",
+                            privilege_level.name
+                        ),
+                        kind: ErrorKind::Generic,
+                    });
+                }
 
-            self.privilege_level_graph
-                .insert(privilege_level_name.clone(), HashMap::new());
+                next_privilege_level = self.args.privilege_levels.iter().find(|candidate| {
+                    candidate.name == walked_privilege_level.previous_privilege_level
+                });
+            }
+        }
+
+        if !self.args.default_desired_privilege_level.is_empty()
+            && !privilege_level_names.contains(self.args.default_desired_privilege_level.as_str())
+        {
+            return Err(ScrapliError {
+                details: format!(
+                    "default desired privilege level '{}' is not a known privilege level",
+                    self.args.default_desired_privilege_level
+                ),
+                kind: ErrorKind::Generic,
+            });
+        }
+
+        Ok(())
+    }
+
+    fn build_privilege_level_graph(&mut self) -> Result<(), ScrapliError> {
+        self.privilege_level_graph.clear();
+
+        for privilege_level in &self.args.privilege_levels {
+            let edges = self
+                .privilege_level_graph
+                .entry(privilege_level.name.clone())
+                .or_default();
+
+            for jump in &privilege_level.jumps {
+                edges.push(PrivilegeLevelEdge {
+                    target: jump.target_privilege_level.clone(),
+                    action: PrivilegeAction::Jump,
+                    cost: jump.cost,
+                });
+            }
 
             if privilege_level.previous_privilege_level.is_empty() {
                 continue;
             }
 
+            edges.push(PrivilegeLevelEdge {
+                target: privilege_level.previous_privilege_level.clone(),
+                action: PrivilegeAction::Deescalate,
+                cost: privilege_level.cost,
+            });
+
             self.privilege_level_graph
-                .entry(privilege_level_name.clone())
+                .entry(privilege_level.previous_privilege_level.clone())
                 .or_default()
-                .insert(previous_privilege_level_name, true);
+                .push(PrivilegeLevelEdge {
+                    target: privilege_level.name.clone(),
+                    action: PrivilegeAction::Escalate,
+                    cost: privilege_level.cost,
+                });
+        }
+
+        // escalate/deescalate edges are always paired, so if every level is reachable from the
+        // first level every level is reachable from every other level too
+        let Some(first_privilege_level) = self.args.privilege_levels.first() else {
+            return Ok(());
+        };
+
+        for privilege_level in &self.args.privilege_levels {
+            if self
+                .find_privilege_level_path(
+                    first_privilege_level.name.as_str(),
+                    privilege_level.name.as_str(),
+                )
+                .is_none()
+            {
+                return Err(ScrapliError {
+                    details: format!(
+                        "privilege level '{}' is not reachable from privilege level '{}'",
+                        privilege_level.name, first_privilege_level.name
+                    ),
+                    kind: ErrorKind::Generic,
+                });
+            }
         }
 
-        for (higher_privilege_level, privilege_level_list) in &self.privilege_level_graph.clone() {
-            for privilege_level in privilege_level_list.keys() {
-                self.privilege_level_graph
-                    .entry(privilege_level.clone())
-                    .or_default()
-                    .insert(higher_privilege_level.clone(), true);
+        Ok(())
+    }
+
+    /// Finds the lowest cost path from `current_privilege_level` to `target_privilege_level` --
+    /// this is a uniform cost (weighted breadth first) search over the privilege level graph. The
+    /// returned edges are the steps to take, in order, to reach the target.
+    fn find_privilege_level_path(
+        &self,
+        current_privilege_level: &str,
+        target_privilege_level: &str,
+    ) -> Option<Vec<PrivilegeLevelEdge>> {
+        let mut frontier: BinaryHeap<Reverse<(u32, usize, String)>> = BinaryHeap::new();
+        let mut paths: HashMap<String, (u32, Vec<PrivilegeLevelEdge>)> = HashMap::new();
+        // tie breaker so that equal cost paths are explored in insertion (breadth first) order
+        let mut sequence: usize = 0;
+
+        paths.insert(current_privilege_level.to_owned(), (0, vec![]));
+        frontier.push(Reverse((0, sequence, current_privilege_level.to_owned())));
+
+        while let Some(Reverse((cost, _, privilege_level))) = frontier.pop() {
+            let (best_cost, path) = paths.get(&privilege_level)?.clone();
+
+            if cost > best_cost {
+                continue;
+            }
+
+            if privilege_level == target_privilege_level {
+                return Some(path);
+            }
+
+            for edge in self
+                .privilege_level_graph
+                .get(&privilege_level)
+                .map_or(&[][..], Vec::as_slice)
+            {
+                let edge_cost = cost.saturating_add(edge.cost);
+
+                if paths
+                    .get(&edge.target)
+                    .is_some_and(|known_path| known_path.0 <= edge_cost)
+                {
+                    continue;
+                }
+
+                let mut edge_path = path.clone();
+                edge_path.push(edge.clone());
+
+                paths.insert(edge.target.clone(), (edge_cost, edge_path));
+
+                sequence += 1;
+                frontier.push(Reverse((edge_cost, sequence, edge.target.clone())));
             }
         }
+
+        None
     }
 
     fn build_joined_prompt_pattern(&mut self) -> Result<(), regex::Error> {
@@ -198,16 +581,108 @@ impl Driver {
         Ok(())
     }
 
-    /// Updates the network driver privilege level information -- that means this function rebuilds
-    /// the internal privilege level graph and also regenerates/sets the "combined" `Channel`
-    /// prompt pattern.
+    /// Updates the network driver privilege level information -- that means this function validates
+    /// the privilege level definitions, rebuilds the internal privilege level graph and also
+    /// regenerates/sets the "combined" `Channel` prompt pattern.
+    ///
+    /// # Errors
+    ///
+    /// Can error if the privilege levels are invalid -- that is if they reference unknown privilege
+    /// levels, if the previous privilege levels form a cycle, or if some privilege level cannot be
+    /// reached from the others. Can also error if for some reason the joined channel prompt pattern
+    /// cannot be compiled.
+    pub fn update_privileges(&mut self) -> Result<(), ScrapliError> {
+        self.validate_privilege_levels()?;
+        self.build_privilege_level_graph()?;
+
+        match self.build_joined_prompt_pattern() {
+            Ok(()) => Ok(()),
+            Err(err) => Err(ScrapliError {
+                details: format!(
+                    "encountered error joining privilege level prompt patterns, error: {err}",
+                ),
+                kind: ErrorKind::Generic,
+            }),
+        }
+    }
+
+    /// Returns the privilege level the driver believes the device is currently at. This is updated
+    /// from the prompt seen at the end of every driver operation -- if the device was interacted
+    /// with directly (for example via `channel.write`) call `refresh_privilege_level` to re-sync.
+    /// An empty string means the current privilege level is not (yet) known.
+    #[must_use]
+    pub const fn current_privilege_level(&self) -> &str {
+        self.current_privilege_level.as_str()
+    }
+
+    /// Returns the configuration "context" (ex: "config-if" or "interfaces ge-0/0/0") parsed from
+    /// the last prompt seen by the driver. An empty string means no context was found in the prompt
+    /// (for example because we are not in a configuration privilege level).
+    #[must_use]
+    pub const fn current_config_context(&self) -> &str {
+        self.config_context.as_str()
+    }
+
+    /// Parses the configuration context from the given prompt -- if the prompt holds no context
+    /// the line the channel saw just above the prompt is checked instead, as some platforms (junos)
+    /// print the context (`[edit interfaces]`) on its own line above the prompt.
+    fn parse_config_context(
+        &self,
+        prompt: &str,
+    ) -> String {
+        let pattern = &self.args.config_context_pattern;
+
+        pattern
+            .captures(prompt.as_bytes())
+            .or_else(|| pattern.captures(self.generic_driver.channel.last_prompt_banner()))
+            .and_then(|captures| captures.iter().skip(1).flatten().next())
+            .map_or_else(String::new, |context| {
+                String::from_utf8_lossy(context.as_bytes())
+                    .trim()
+                    .to_owned()
+            })
+    }
+
+    /// Fetches the current prompt from the device and re-determines the current privilege level,
+    /// updating (and returning) the driver's current privilege level.
     ///
     /// # Errors
     ///
-    /// Can error if for some reason the joined channel prompt pattern cannot be compiled.
-    pub fn update_privileges(&mut self) -> Result<(), regex::Error> {
-        self.build_privilege_level_graph();
-        self.build_joined_prompt_pattern()
+    /// Returns an error if the prompt cannot be fetched (including if the device shows a prompt
+    /// matching none of the privilege levels within `timeout_ops`) or if the prompt does not match
+    /// exactly one privilege level.
+    pub fn refresh_privilege_level(&mut self) -> Result<String, ScrapliError> {
+        let current_prompt = self.generic_driver.get_prompt()?;
+
+        let current_privilege_level =
+            self.determine_current_privilege_level(current_prompt.as_str())?;
+
+        self.current_privilege_level
+            .clone_from(&current_privilege_level);
+        self.config_context = self.parse_config_context(current_prompt.as_str());
+
+        Ok(current_privilege_level)
+    }
+
+    /// Updates the current privilege level (and configuration context) based on the last prompt
+    /// the channel saw -- called at the end of operations so that inputs that change the privilege
+    /// level (like an "end" in a list of configs) are reflected. If the privilege level cannot be
+    /// determined the current privilege level is cleared so the next operation re-acquires its
+    /// target.
+    fn update_privilege_level_from_last_prompt(&mut self) {
+        let last_prompt =
+            String::from_utf8_lossy(self.generic_driver.channel.last_prompt()).into_owned();
+
+        self.config_context = self.parse_config_context(last_prompt.as_str());
+
+        match self.determine_current_privilege_level(last_prompt.as_str()) {
+            Ok(current_privilege_level) => self.current_privilege_level = current_privilege_level,
+            Err(err) => {
+                debug!("failed updating privilege level from last prompt, error: {err}");
+
+                self.current_privilege_level = String::new();
+            }
+        }
     }
 
     /// Open the driver and the underlying channel and transport.
@@ -215,23 +690,44 @@ impl Driver {
     /// # Errors
     ///
     /// Can return an error if opening the underlying `generic_driver` fails. Can also return an
-    /// error if the `on_open` callable is set and it returns an error.
+    /// error if any of the (network or generic) `pre_open`, `post_auth` or `on_open` hooks are set
+    /// and return an error -- as with the generic driver, `post_auth` and `on_open` (usually
+    /// acquiring the default privilege level) run within the `timeout_open` deadline and are
+    /// reported as the prompt discovery phase if they stall. The channel is closed and the
+    /// `on_error` hook (if set) is executed with any error before it is returned.
     ///
     /// This can also return an error if (for some reason?!) the `privilege_levels` and
     /// `default_privilege_level` arguments are not set -- this should *not* happen if creating a
     /// network driver from a platform (which would be the recommended approach).
     pub fn open(&mut self) -> Result<(), ScrapliError> {
-        match self.update_privileges() {
-            Ok(_) => {}
-            Err(err) => {
-                return Err(ScrapliError {
-                    details: format!(
-                        "encountered error joining privilege level prompt patterns, error: {err}",
-                    ),
-                })
+        // set aside so sends failing within the hooks do not run it, it runs once below instead
+        let on_error = self.args.on_error.take();
+
+        let result = self.open_channel();
+
+        self.args.on_error = on_error;
+
+        self.generic_driver.channel.exit_open_phase();
+
+        if let Err(err) = result {
+            // whatever failed, do not leave the transport (and read loop) running
+            if let Err(close_err) = self.generic_driver.channel.close() {
+                error!("failed closing channel after failed open, error: {close_err}");
             }
+
+            self.run_on_error(&err);
+
+            return Err(err);
         }
 
+        info!("connection opened successfully");
+
+        Ok(())
+    }
+
+    fn open_channel(&mut self) -> Result<(), ScrapliError> {
+        self.update_privileges()?;
+
         if self.args.default_desired_privilege_level.is_empty()
             || self.args.privilege_levels.is_empty()
         {
@@ -240,18 +736,59 @@ impl Driver {
                     "default desired privilege level and/or privilege levels are unset, \
                     these are required with 'network' driver",
                 ),
+                kind: ErrorKind::Generic,
             });
         }
 
-        self.generic_driver.open()?;
+        self.run_hook(Hook::PreOpen)?;
 
-        if let Some(f) = self.args.on_open {
-            debug!("network driver `on_open` set, executing");
+        self.generic_driver.open_channel()?;
 
-            f(self)?;
-        }
+        self.run_hook(Hook::PostAuth)?;
 
-        Ok(())
+        self.generic_driver.run_on_open()?;
+
+        self.run_hook(Hook::OnOpen)
+    }
+
+    /// Executes the given hook (if set). The hook is taken out of the args while it executes so
+    /// that it can be handed the (mutable) driver.
+    fn run_hook(
+        &mut self,
+        hook: Hook,
+    ) -> Result<(), ScrapliError> {
+        let Some(mut f) = self.args.hook(hook).take() else {
+            return Ok(());
+        };
+
+        debug!("network driver `{}` set, executing", hook.name());
+
+        let result = f(self);
+
+        *self.args.hook(hook) = Some(f);
+
+        result
+    }
+
+    fn run_on_error(
+        &mut self,
+        err: &ScrapliError,
+    ) {
+        let Some(mut f) = self.args.on_error.take() else {
+            return;
+        };
+
+        debug!("network driver `on_error` set, executing");
+
+        f(self, err);
+
+        self.args.on_error = Some(f);
+    }
+
+    /// Returns a `CancelHandle` that can be used (from any thread) to cancel opening the driver.
+    #[must_use]
+    pub fn cancel_handle(&self) -> CancelHandle {
+        self.generic_driver.cancel_handle()
     }
 
     #[allow(clippy::indexing_slicing)]
@@ -271,6 +808,14 @@ impl Driver {
             }
         }
 
+        // some platforms (junos configuration flavors for example) share prompt patterns across
+        // privilege levels, if we are already in one of the matched levels we simply stay there
+        if possible_current_privilege_levels.len() > 1
+            && possible_current_privilege_levels.contains(&self.current_privilege_level)
+        {
+            return Ok(self.current_privilege_level.clone());
+        }
+
         // note that in scrapli go/py we return a slice of privs but i think we should never
         // match on more than one privilege level... so for now for rust version we'll assume that
         // anything not exactly one priv matched is an error.
@@ -280,57 +825,17 @@ impl Driver {
                 details: format!(
                     "could not determine privilege level from prompt '{current_prompt}', found *no matching privilege levels*"
                 ),
+                kind: ErrorKind::Generic,
             }),
             _ =>  Err(ScrapliError {
                 details: format!(
                     "could not determine privilege level from prompt '{current_prompt}', found *more than one matching privilege level*"
                 ),
+                kind: ErrorKind::Generic,
             })
         }
     }
 
-    #[allow(clippy::expect_used)]
-    fn build_privilege_change_map(
-        &self,
-        current_privilege_level: &str,
-        target_privilege_level: &str,
-        privilege_level_steps: &Vec<String>,
-    ) -> Vec<String> {
-        let mut working_steps = if privilege_level_steps.is_empty() {
-            vec![]
-        } else {
-            privilege_level_steps.clone()
-        };
-
-        working_steps.push(current_privilege_level.to_owned());
-
-        if current_privilege_level == target_privilege_level {
-            return working_steps;
-        }
-
-        for privilege_level in self
-            .privilege_level_graph
-            .get(current_privilege_level)
-            .expect("current privilege level not found in privilege level graph, this is a bug")
-            .keys()
-        {
-            if !string_vec_contains_substring(working_steps.clone(), privilege_level) {
-                let new_working_steps = self.build_privilege_change_map(
-                    privilege_level.as_str(),
-                    target_privilege_level.clone(),
-                    working_steps.as_ref(),
-                );
-
-                if !new_working_steps.is_empty() {
-                    return new_working_steps;
-                }
-            }
-        }
-
-        vec![]
-    }
-
-    #[allow(clippy::indexing_slicing)]
     fn process_acquire_privilege_level(
         &mut self,
         target_privilege_level: &str,
@@ -338,49 +843,36 @@ impl Driver {
     ) -> Result<(PrivilegeAction, String), ScrapliError> {
         let current_privilege_level = self.determine_current_privilege_level(current_prompt)?;
 
-        if current_privilege_level == target_privilege_level {
-            self.current_privilege_level = current_privilege_level.clone();
+        self.current_privilege_level
+            .clone_from(&current_privilege_level);
+        self.config_context = self.parse_config_context(current_prompt);
 
+        if current_privilege_level == target_privilege_level {
             return Ok((PrivilegeAction::NoOp, current_privilege_level));
         };
 
-        let privilege_change_map = self.build_privilege_change_map(
-            current_privilege_level.as_str(),
-            target_privilege_level,
-            &vec![],
-        );
-
-        if privilege_change_map.is_empty() {
+        let Some(first_step) = self
+            .find_privilege_level_path(current_privilege_level.as_str(), target_privilege_level)
+            .and_then(|path| path.into_iter().next())
+        else {
             return Err(ScrapliError {
                 details: format!(
                     "could not build privilege level map to target privilege \
                     level '{target_privilege_level}', this is a bug"
                 ),
+                kind: ErrorKind::Generic,
             });
-        }
-
-        self.current_privilege_level = String::from("unknown");
+        };
 
-        for privilege_level in &self.args.privilege_levels {
-            // can't panic because zero-ith entry is always the destination priv, and we aren't in
-            // the target priv, so there must be at least one more priv to go to!
-            if privilege_level.name != privilege_change_map[1] {
-                continue;
+        match first_step.action {
+            // deescalating means running the deescalate of the level we are *in*
+            PrivilegeAction::Deescalate => {
+                Ok((PrivilegeAction::Deescalate, current_privilege_level))
             }
-
-            if privilege_level.previous_privilege_level != current_privilege_level {
-                return Ok((PrivilegeAction::Deescalate, current_privilege_level));
+            PrivilegeAction::NoOp | PrivilegeAction::Escalate | PrivilegeAction::Jump => {
+                Ok((first_step.action, first_step.target))
             }
-
-            return Ok((PrivilegeAction::Escalate, privilege_level.name.clone()));
         }
-
-        Err(ScrapliError {
-            details: format!(
-                "could not determine action to take to get to privilege level \
-                '{target_privilege_level}', this is a bug"
-            ),
-        })
     }
 
     /// Close the driver and the underlying channel and transport.
@@ -388,15 +880,28 @@ impl Driver {
     /// # Errors
     ///
     /// Can return an error if closing the underlying `generic_driver` fails. Can also return an
-    /// error if the `on_open` callable is set and it returns an error.
+    /// error if any of the (network or generic) `pre_close` or `on_close` hooks are set and return
+    /// an error. The `on_error` hook (if set) is executed with any error before it is returned.
     pub fn close(&mut self) -> Result<(), ScrapliError> {
-        if let Some(f) = self.args.on_close {
-            debug!("network driver `on_close` set, executing");
+        // set aside so sends failing within the hooks do not run it, it runs once below instead
+        let on_error = self.args.on_error.take();
 
-            f(self)?;
+        let result = self
+            .run_hook(Hook::PreClose)
+            .and_then(|()| self.run_hook(Hook::OnClose))
+            .and_then(|()| self.generic_driver.close_channel());
+
+        self.args.on_error = on_error;
+
+        if let Err(err) = result {
+            self.run_on_error(&err);
+
+            return Err(err);
         }
 
-        self.generic_driver.close()
+        info!("connection closed successfully");
+
+        Ok(())
     }
 
     fn deescalate_privilege_level(
@@ -411,7 +916,8 @@ impl Driver {
         {
             None => {
                 return Err(ScrapliError {
-                    details: String::from("unknown privilege leve, this is a bug"),
+                    details: String::from("unknown privilege level, this is a bug"),
+                    kind: ErrorKind::Generic,
                 })
             }
             Some(privilege_level) => privilege_level,
@@ -435,14 +941,15 @@ impl Driver {
         {
             None => {
                 return Err(ScrapliError {
-                    details: String::from("unknown privilege leve, this is a bug"),
+                    details: String::from("unknown privilege level, this is a bug"),
+                    kind: ErrorKind::Generic,
                 })
             }
             Some(privilege_level) => privilege_level,
         };
 
-        if !privilege_level.escalate_auth || self.args.secondary_password.is_empty() {
-            if self.args.secondary_password.is_empty() {
+        if !privilege_level.escalate_auth || !self.args.secondary_password.is_set() {
+            if !self.args.secondary_password.is_set() {
                 info!("no secondary password set, but escalate target may require auth, trying with no password...");
             }
 
@@ -451,25 +958,66 @@ impl Driver {
                 &ChannelOperationOptions::default(),
             )
         } else {
-            let events = &SendInteractiveEvents(vec![
+            let mut events = SendInteractiveEvents(vec![
                 SendInteractiveEvent {
                     input: privilege_level.escalate.clone(),
                     response: privilege_level.escalate_prompt.clone(),
                     hidden: false,
                 },
                 SendInteractiveEvent {
-                    input: self.args.secondary_password.clone(),
+                    input: self
+                        .args
+                        .secondary_password
+                        .secret()?
+                        .expose_secret()
+                        .to_owned(),
                     response: privilege_level.pattern.to_string(),
                     hidden: true,
                 },
             ]);
 
-            self.generic_driver
+            let result = self
+                .generic_driver
                 .channel
-                .send_interactive(events, &ChannelOperationOptions::default())
+                .send_interactive(&events, &ChannelOperationOptions::default());
+
+            // the password event holds a plain copy of the secondary password, zero it rather than
+            // leaving it in memory once it has been sent
+            for event in &mut events.0 {
+                event.input.zeroize();
+            }
+
+            result
         }
     }
 
+    fn jump_privilege_level(
+        &mut self,
+        target_privilege_level: &str,
+    ) -> Result<Vec<u8>, ScrapliError> {
+        let Some(jump) = self
+            .args
+            .privilege_levels
+            .iter()
+            .find(|privilege_level| privilege_level.name == self.current_privilege_level)
+            .and_then(|privilege_level| {
+                privilege_level
+                    .jumps
+                    .iter()
+                    .find(|jump| jump.target_privilege_level == target_privilege_level)
+            })
+        else {
+            return Err(ScrapliError {
+                details: String::from("unknown privilege level jump, this is a bug"),
+                kind: ErrorKind::Generic,
+            });
+        };
+
+        self.generic_driver
+            .channel
+            .send_input(jump.input.as_str(), &ChannelOperationOptions::default())
+    }
+
     /// Acquire the target privilege level, assuming proper configuration of driver privilege levels
     /// this function will handle any escalation/de-escalate required, including entering escalation
     /// credentials (via `args.secondary_password`).
@@ -495,6 +1043,7 @@ impl Driver {
         {
             return Err(ScrapliError{
                 details: format!("requested privilege level '{target_privilege_level}' is not a valid privilege level"),
+                kind: ErrorKind::Generic,
             });
         }
 
@@ -522,6 +1071,11 @@ impl Driver {
 
                     self.deescalate_privilege_level(next_privilege_level.as_str())?;
                 }
+                PrivilegeAction::Jump => {
+                    debug!("acquire privilege determined privilege level jump is best path");
+
+                    self.jump_privilege_level(next_privilege_level.as_str())?;
+                }
             }
 
             action_count += 1;
@@ -531,16 +1085,17 @@ impl Driver {
                     details: format!(
                         "failed to acquire target privilege level '{target_privilege_level}'"
                     ),
+                    kind: ErrorKind::Generic,
                 });
             }
         }
     }
 
     /// Sends the command string to the device and returns a `Response` object. This method will
-    /// always ensure that the the input is sent at the `default_desired_privilege_level`. If the
-    /// current privilege level is *not* the `default_desired_privilege_level` (which is typically
-    /// "privilege-exec" or "exec"), `acquire_privilege_level` will be called with a target
-    /// privilege level of the `default_desired_privilege_level`.
+    /// ensure that the the input is sent at the `default_desired_privilege_level` (or the privilege
+    /// level of an active `PrivilegeLevelGuard`). If the current privilege level is *not* the
+    /// desired privilege level (which is typically "privilege-exec" or "exec"),
+    /// `acquire_privilege_level` will be called with the desired privilege level as the target.
     ///
     /// # Errors
     ///
@@ -554,48 +1109,240 @@ impl Driver {
         self.send_command_with_options(command, &OperationOptions::default())
     }
 
-    /// Sends the command string to the device and returns a `Response` object. This method will
-    /// always ensure that the the input is sent at the `default_desired_privilege_level`. If the
-    /// current privilege level is *not* the `default_desired_privilege_level` (which is typically
-    /// "privilege-exec" or "exec"), `acquire_privilege_level` will be called with a target
-    /// privilege level of the `default_desired_privilege_level`.
+    /// Sends the command string to the device and returns a `Response` object. If the given
+    /// `OperationOptions` sets a `privilege_level` the command is sent at that privilege level,
+    /// otherwise this behaves like `send_command` and the input is sent at the privilege level of
+    /// an active `PrivilegeLevelGuard` or the `default_desired_privilege_level`. The privilege
+    /// level is only acquired for this call, a following `send_command` returns to the default.
     ///
     /// # Errors
     ///
     /// This function returns an error if the underlying generic driver/channel encounter an error
-    /// sending the input. This function does *not* error if any `failed_when_contains` output is
-    /// encountered though, *but*, the returned `Response` will indicate a failed state.
+    /// sending the input, or if an interceptor blocks the command. This function does *not* error
+    /// if any `failed_when_contains` output is encountered though, *but*, the returned `Response`
+    /// will indicate a failed state.
     pub fn send_command_with_options(
         &mut self,
         command: &str,
         options: &OperationOptions,
     ) -> Result<Response, ScrapliError> {
-        if self.current_privilege_level != self.args.default_desired_privilege_level {
-            debug!("send_command requested but not at desired privilege level, attempting to acquire default desired privilege level");
+        self.intercept(Operation::Command(command.to_owned()), |driver, op| {
+            driver.execute_command(op.input()?, options)
+        })
+    }
 
-            self.acquire_privilege_level(
-                self.args.default_desired_privilege_level.clone().as_str(),
-            )?;
+    fn execute_command(
+        &mut self,
+        command: &str,
+        options: &OperationOptions,
+    ) -> Result<Response, ScrapliError> {
+        let target_privilege_level = self.target_privilege_level(options);
+
+        if self.current_privilege_level != target_privilege_level {
+            debug!(
+                "send_command requested but not at desired privilege level, attempting to acquire \
+                privilege level '{target_privilege_level}'"
+            );
+
+            self.acquire_privilege_level(target_privilege_level.as_str())?;
         }
 
-        self.generic_driver
-            .send_command_with_options(command, &options.generic_driver_operation_options)
+        self.execute_input(command, options)
     }
 
-    /// Sends the config lines to the device and returns a `MultiResponse` object. This method will
-    /// ensure that the operation takes place in the `DEFAULT_CONFIGURATION_PRIVILEGE_LEVEL` if no
-    /// privilege level is specified in the given `OperationOptions`.
+    /// Returns the privilege level of the options, the privilege level of any active
+    /// `PrivilegeLevelGuard`, or the `default_desired_privilege_level` -- in that order.
+    fn target_privilege_level(
+        &self,
+        options: &OperationOptions,
+    ) -> String {
+        if options.privilege_level.is_empty() {
+            self.scoped_privilege_level
+                .clone()
+                .unwrap_or_else(|| self.args.default_desired_privilege_level.clone())
+        } else {
+            options.privilege_level.clone()
+        }
+    }
+
+    /// Sends the input via the generic driver (without interceptors) in the current privilege
+    /// level, updating the privilege level and config context from the prompt afterward.
+    fn execute_input(
+        &mut self,
+        input: &str,
+        options: &OperationOptions,
+    ) -> Result<Response, ScrapliError> {
+        let send_result = self
+            .generic_driver
+            .execute_command(input, &options.generic_driver_operation_options);
+
+        self.update_privilege_level_from_last_prompt();
+
+        let mut response = send_result?;
+        response.config_context.clone_from(&self.config_context);
+
+        Ok(response)
+    }
+
+    /// Sends "interactive" events to the device (see the generic driver `send_interactive`) in the
+    /// privilege level of the given `OperationOptions` -- defaulting, like `send_command`, to the
+    /// privilege level of any active `PrivilegeLevelGuard`, or the
+    /// `default_desired_privilege_level`.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the privilege level cannot be acquired, if the underlying
+    /// generic driver/channel encounter an error sending the events, or if an interceptor blocks
+    /// the events.
+    pub fn send_interactive(
+        &mut self,
+        events: &SendInteractiveEvents,
+        options: &OperationOptions,
+    ) -> Result<Response, ScrapliError> {
+        self.intercept(Operation::Interactive(events.clone()), |driver, op| {
+            let target_privilege_level = driver.target_privilege_level(options);
+
+            if driver.current_privilege_level != target_privilege_level {
+                driver.acquire_privilege_level(target_privilege_level.as_str())?;
+            }
+
+            let send_result = driver
+                .generic_driver
+                .execute_interactive(op.events()?, &options.generic_driver_operation_options);
+
+            driver.update_privilege_level_from_last_prompt();
+
+            let mut response = send_result?;
+            response.config_context.clone_from(&driver.config_context);
+
+            Ok(response)
+        })
+    }
+
+    /// Adds an interceptor to the end of the (generic) driver's interceptor pipeline.
+    pub fn add_interceptor<I: Interceptor + 'static>(
+        &mut self,
+        i: I,
+    ) {
+        self.generic_driver.add_interceptor(i);
+    }
+
+    /// Passes the operation through the (generic driver's) interceptors, with `exec` executing the
+    /// (possibly rewritten) operation at the end of the pipeline. The `on_error` hook (if set) is
+    /// executed if the operation fails.
+    fn intercept<F>(
+        &mut self,
+        op: Operation,
+        mut exec: F,
+    ) -> Result<Response, ScrapliError>
+    where
+        F: FnMut(&mut Self, &Operation) -> Result<Response, ScrapliError>,
+    {
+        let mut interceptors = mem::take(&mut self.generic_driver.args.interceptors);
+
+        let result = interceptor::run(&mut interceptors, op, &mut |op| exec(self, op));
+
+        self.generic_driver.args.interceptors = interceptors;
+
+        if let Err(err) = result.as_ref() {
+            self.run_on_error(err);
+        }
+
+        result
+    }
+
+    /// Sends the command with the platform's structured output pipe (ex: "| json") appended at the
+    /// default desired privilege level and returns the parsed json payload. See the generic driver
+    /// `send_command_structured` for details.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the platform does not support structured output, if the privilege level
+    /// cannot be acquired, if the device returns a cli error or if the output cannot be parsed.
+    pub fn send_command_structured(
+        &mut self,
+        command: &str,
+    ) -> Result<serde_json::Value, ScrapliError> {
+        self.send_command_structured_with_options(command, &OperationOptions::default())
+    }
+
+    /// Sends the command with the platform's structured output pipe (ex: "| json") appended and
+    /// returns the parsed json payload -- privilege levels are handled as in
+    /// `send_command_with_options`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the platform does not support structured output, if the privilege level
+    /// cannot be acquired, if the device returns a cli error or if the output cannot be parsed.
+    pub fn send_command_structured_with_options(
+        &mut self,
+        command: &str,
+        options: &OperationOptions,
+    ) -> Result<serde_json::Value, ScrapliError> {
+        let structured_command = self.generic_driver.structured_command(command)?;
+
+        self.send_command_with_options(structured_command.as_str(), options)?
+            .json()
+    }
+
+    /// Acquires the `target_privilege_level` and returns a `PrivilegeLevelGuard` -- while the guard
+    /// is alive any `send_command` operations (sent via the guard) are executed at the target
+    /// privilege level. Once the guard is dropped the driver returns to the privilege level it was
+    /// in prior to this call. Guards can be nested, each restoring its own previous level.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the current privilege level cannot be determined or if the target
+    /// privilege level cannot be acquired.
+    pub fn privilege_level_guard(
+        &mut self,
+        target_privilege_level: &str,
+    ) -> Result<PrivilegeLevelGuard<'_>, ScrapliError> {
+        let previous_privilege_level = if self.current_privilege_level.is_empty() {
+            self.refresh_privilege_level()?
+        } else {
+            self.current_privilege_level.clone()
+        };
+
+        self.acquire_privilege_level(target_privilege_level)?;
+
+        let previous_scoped_privilege_level = self
+            .scoped_privilege_level
+            .replace(target_privilege_level.to_owned());
+
+        Ok(PrivilegeLevelGuard {
+            driver: self,
+            previous_privilege_level,
+            previous_scoped_privilege_level,
+        })
+    }
+
+    /// Sends the config lines to the device and returns a `MultiResponse` object. This method will
+    /// ensure that the operation takes place in the `DEFAULT_CONFIGURATION_PRIVILEGE_LEVEL` if no
+    /// privilege level is specified in the given `OperationOptions`. Each `Response` records the
+    /// configuration context the device was in after the line was sent. If the
+    /// `return_to_root_config_context` option is set, the root configuration context is restored
+    /// before each new (non-indented) block of configs. Each config line is passed through the
+    /// interceptors as its own operation.
     ///
     /// # Errors
     ///
     /// This function returns an error if the underlying generic driver/channel encounter an error
-    /// sending the input. This function does *not* error if any `failed_when_contains` output is
-    /// encountered though, *but*, the returned `Response` will indicate a failed state.
+    /// sending the input, or if an interceptor blocks a config line. This function does *not* error
+    /// if any `failed_when_contains` output is encountered though, *but*, the returned `Response`
+    /// will indicate a failed state.
     pub fn send_configs(
         &mut self,
         configs: &[&str],
         options: &OperationOptions,
     ) -> Result<MultiResponse, ScrapliError> {
+        if configs.is_empty() {
+            return Err(ScrapliError {
+                details: String::from("send_configs called with empty vec of configs"),
+                kind: ErrorKind::Generic,
+            });
+        }
+
         let mut target_privilege_level = &options.privilege_level.as_str();
 
         if target_privilege_level.is_empty() {
@@ -604,7 +1351,548 @@ impl Driver {
 
         self.acquire_privilege_level(target_privilege_level)?;
 
+        let root_config_context = self.config_context.clone();
+
+        info!("send_configs requested, sending '{:?}'", configs);
+
+        let mut multi_response = MultiResponse::new(self.generic_driver.args.host.as_str());
+
+        for (idx, config) in configs.iter().enumerate() {
+            if options.return_to_root_config_context
+                && idx > 0
+                && !config.starts_with(char::is_whitespace)
+            {
+                self.return_to_root_config_context(root_config_context.as_str())?;
+            }
+
+            let response = self
+                .intercept(Operation::Config((*config).to_owned()), |driver, op| {
+                    driver.execute_input(op.input()?, options)
+                })?;
+
+            let failed = response.failed;
+
+            multi_response.record_response(response);
+
+            if options.generic_driver_operation_options.stop_on_failed && failed {
+                info!("stop on failed is true and a config failed, discontinuing send configs operation");
+
+                break;
+            }
+        }
+
+        Ok(multi_response)
+    }
+
+    fn return_to_root_config_context(
+        &mut self,
+        root_config_context: &str,
+    ) -> Result<(), ScrapliError> {
+        let mut exit_count: u8 = 0;
+
+        while self.config_context != root_config_context {
+            if exit_count >= MAX_CONFIG_CONTEXT_EXITS {
+                return Err(ScrapliError {
+                    details: format!(
+                        "failed returning to root config context '{root_config_context}', \
+                        stuck in config context '{}'",
+                        self.config_context
+                    ),
+                    kind: ErrorKind::Generic,
+                });
+            }
+
+            debug!(
+                "in config context '{}', exiting to return to root config context",
+                self.config_context
+            );
+
+            let config_context_exit = self.args.config_context_exit.clone();
+
+            self.generic_driver.channel.send_input(
+                config_context_exit.as_str(),
+                &ChannelOperationOptions::default(),
+            )?;
+
+            self.update_privilege_level_from_last_prompt();
+
+            exit_count += 1;
+        }
+
+        Ok(())
+    }
+
+    /// Reads until the prompt of the target privilege level is seen *after* `search_from` in the
+    /// read buffer, returns the given read buffer extended with everything read.
+    fn read_until_privilege_level_prompt(
+        &mut self,
+        target_privilege_level: &str,
+        options: &ChannelOperationOptions,
+        mut rb: Vec<u8>,
+        search_from: usize,
+    ) -> Result<Vec<u8>, ScrapliError> {
+        let prompt_pattern = match self
+            .args
+            .privilege_levels
+            .iter()
+            .find(|privilege_level| privilege_level.name == target_privilege_level)
+        {
+            None => {
+                return Err(ScrapliError {
+                    details: String::from("unknown privilege level, this is a bug"),
+                    kind: ErrorKind::Generic,
+                })
+            }
+            Some(privilege_level) => privilege_level.pattern.clone(),
+        };
+
+        let timeout = match ChronoDuration::from_std(
+            options
+                .timeout
+                .unwrap_or(self.generic_driver.channel.args.timeout_ops),
+        ) {
+            Ok(timeout) => timeout,
+            Err(err) => {
+                return Err(
+                    ScrapliError{
+                        details: format!("failed casting std Duration to chrono Duration, this shouldn't happen, error: {err}"),
+                        kind: ErrorKind::Generic,
+                    }
+                )
+            }
+        };
+
+        let deadline = Utc::now() + timeout;
+
+        loop {
+            if deadline <= Utc::now() {
+                return Err(ScrapliError {
+                    details: format!(
+                        "timed out waiting for privilege level '{target_privilege_level}' prompt"
+                    ),
+                    kind: ErrorKind::Generic,
+                });
+            }
+
+            let (found, result) = self
+                .generic_driver
+                .channel
+                ._read_and_check_for_any_prompt_full(
+                    rb.as_slice(),
+                    search_from,
+                    slice::from_ref(&prompt_pattern),
+                );
+
+            rb = result?;
+
+            if found {
+                return Ok(rb);
+            }
+
+            thread::sleep(self.generic_driver.channel.args.read_delay);
+        }
+    }
+
+    /// Sends the config "blob" to the device and returns a single `Response` object. The config is
+    /// split into lines which are sent just like `send_configs` would send them, with the outputs
+    /// of each line joined into the returned `Response`.
+    ///
+    /// If the channel operation options `eager` flag is set, the config is sent in "bulk" mode --
+    /// each line is sent without waiting for the device to return to the prompt, and only after the
+    /// final line is sent do we read until we see the prompt of the target privilege level. This
+    /// is *much* faster for large configs, but means any failures are only detected in the combined
+    /// output of all lines. Interceptors see each line in normal mode, but the entire config as a
+    /// single operation in eager mode.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the underlying generic driver/channel encounter an error
+    /// sending the input, or if an interceptor blocks (a line of) the config. This function does
+    /// *not* error if any `failed_when_contains` output is encountered though, *but*, the returned
+    /// `Response` will indicate a failed state.
+    pub fn send_config(
+        &mut self,
+        config: &str,
+        options: &OperationOptions,
+    ) -> Result<Response, ScrapliError> {
+        if options
+            .generic_driver_operation_options
+            .channel_operation_options
+            .eager
+        {
+            return self.intercept(Operation::Config(config.to_owned()), |driver, op| {
+                driver.execute_config_eager(op.input()?, options)
+            });
+        }
+
+        let configs = config.lines().collect::<Vec<&str>>();
+
+        let mut resp = self.new_config_response(config, options);
+
+        let multi_response = self.send_configs(configs.as_slice(), options)?;
+
+        let joined_results = multi_response
+            .responses
+            .iter()
+            .map(|response| response.result.as_str())
+            .collect::<Vec<&str>>()
+            .join("\n");
+
+        resp.record(joined_results.into_bytes());
+        resp.config_context.clone_from(&self.config_context);
+
+        Ok(resp)
+    }
+
+    fn new_config_response(
+        &self,
+        config: &str,
+        options: &OperationOptions,
+    ) -> Response {
+        let generic_driver_operation_options = self
+            .generic_driver
+            .merge_operation_options(&options.generic_driver_operation_options);
+
         self.generic_driver
-            .send_commands_with_options(configs, &options.generic_driver_operation_options)
+            .new_response(config, &generic_driver_operation_options)
+    }
+
+    fn execute_config_eager(
+        &mut self,
+        config: &str,
+        options: &OperationOptions,
+    ) -> Result<Response, ScrapliError> {
+        let configs = config.lines().collect::<Vec<&str>>();
+
+        let mut resp = self.new_config_response(config, options);
+
+        let channel_options = &options
+            .generic_driver_operation_options
+            .channel_operation_options;
+
+        info!(
+            "send_config requested in eager mode, sending {} lines",
+            configs.len()
+        );
+
+        let mut target_privilege_level = options.privilege_level.as_str();
+
+        if target_privilege_level.is_empty() {
+            target_privilege_level = DEFAULT_CONFIGURATION_PRIVILEGE_LEVEL;
+        }
+
+        self.acquire_privilege_level(target_privilege_level)?;
+
+        let mut rb: Vec<u8> = vec![];
+        let mut search_from = 0;
+
+        for line in configs {
+            let line_rb = self
+                .generic_driver
+                .channel
+                .send_input_eager(line, channel_options)?;
+
+            // the prompts of the (intermediate) config modes entered by earlier lines match the
+            // target privilege level too, so only search for the prompt after the last echo (or
+            // after all output preceding the last line if the echo was only "fuzzily" found)
+            search_from = rb.len()
+                + line_rb
+                    .windows(line.len().max(1))
+                    .rposition(|window| window == line.as_bytes())
+                    .map_or(0, |position| position + line.len());
+
+            rb.extend(line_rb);
+        }
+
+        debug!("eager send_config inputs sent, reading until privilege level prompt");
+
+        let output = self.read_until_privilege_level_prompt(
+            target_privilege_level,
+            channel_options,
+            rb,
+            search_from,
+        )?;
+
+        self.update_privilege_level_from_last_prompt();
+
+        resp.record(
+            self.generic_driver
+                .channel
+                .process_output(output.as_slice(), channel_options.strip_prompt),
+        );
+        resp.config_context.clone_from(&self.config_context);
+
+        Ok(resp)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        Args,
+        Driver,
+        PrivilegeAction,
+        PrivilegeLevel,
+        PrivilegeLevelJump,
+        DEFAULT_PRIVILEGE_LEVEL_COST,
+    };
+    use crate::driver::GenericDriverBuilder;
+    use crate::platform::{
+        get_platforms,
+        Platform,
+    };
+    use regex::bytes::Regex;
+    use std::error::Error;
+
+    type TestResult = Result<(), Box<dyn Error>>;
+
+    fn privilege_level(
+        name: &str,
+        previous_privilege_level: &str,
+        jumps: Vec<PrivilegeLevelJump>,
+    ) -> Result<PrivilegeLevel, regex::Error> {
+        Ok(PrivilegeLevel {
+            name: name.to_owned(),
+            pattern: Regex::new(format!(r"(?m)^{name}#$").as_str())?,
+            not_contains: vec![],
+            previous_privilege_level: previous_privilege_level.to_owned(),
+            de_escalate: String::from("exit"),
+            escalate: format!("enter {name}"),
+            escalate_auth: false,
+            escalate_prompt: String::new(),
+            cost: DEFAULT_PRIVILEGE_LEVEL_COST,
+            jumps,
+        })
+    }
+
+    fn jump(
+        target_privilege_level: &str,
+        cost: u32,
+    ) -> PrivilegeLevelJump {
+        PrivilegeLevelJump {
+            target_privilege_level: target_privilege_level.to_owned(),
+            input: format!("jump {target_privilege_level}"),
+            cost,
+        }
+    }
+
+    fn driver(privilege_levels: Vec<PrivilegeLevel>) -> Result<Driver, Box<dyn Error>> {
+        let mut driver = Driver::new(
+            GenericDriverBuilder::new("localhost").build(),
+            Args {
+                privilege_levels,
+                ..Args::default()
+            },
+        );
+
+        driver.update_privileges()?;
+
+        Ok(driver)
+    }
+
+    fn platform_driver(platform_name: &str) -> Result<Driver, Box<dyn Error>> {
+        driver(Platform::new(platform_name)?.privilege_levels()?)
+    }
+
+    fn path(
+        driver: &Driver,
+        current_privilege_level: &str,
+        target_privilege_level: &str,
+    ) -> Option<Vec<(String, PrivilegeAction)>> {
+        driver
+            .find_privilege_level_path(current_privilege_level, target_privilege_level)
+            .map(|edges| {
+                edges
+                    .into_iter()
+                    .map(|edge| (edge.target, edge.action))
+                    .collect()
+            })
+    }
+
+    fn steps(steps: &[(&str, PrivilegeAction)]) -> Option<Vec<(String, PrivilegeAction)>> {
+        Some(
+            steps
+                .iter()
+                .map(|&(target, action)| (target.to_owned(), action))
+                .collect(),
+        )
+    }
+
+    /// root <- mid <- leaf, with the given jumps from leaf.
+    fn chain(leaf_jumps: Vec<PrivilegeLevelJump>) -> Result<Driver, Box<dyn Error>> {
+        driver(vec![
+            privilege_level("root", "", vec![])?,
+            privilege_level("mid", "root", vec![])?,
+            privilege_level("leaf", "mid", leaf_jumps)?,
+        ])
+    }
+
+    #[test]
+    fn platform_privilege_levels_are_valid() -> TestResult {
+        for platform_name in get_platforms().keys() {
+            let platform_driver = platform_driver(platform_name)?;
+
+            assert!(
+                !platform_driver.args.privilege_levels.is_empty(),
+                "platform '{platform_name}' has no privilege levels"
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn iosxe_configuration_to_privilege_exec_uses_end_jump() -> TestResult {
+        let iosxe_driver = platform_driver("cisco_iosxe")?;
+
+        assert_eq!(
+            path(&iosxe_driver, "configuration", "privilege-exec"),
+            steps(&[("privilege-exec", PrivilegeAction::Jump)]),
+            "expected the configuration 'end' jump"
+        );
+
+        let configuration = iosxe_driver
+            .args
+            .privilege_levels
+            .iter()
+            .find(|privilege_level| privilege_level.name == "configuration")
+            .ok_or("no configuration privilege level")?;
+
+        assert_eq!(
+            configuration
+                .jumps
+                .iter()
+                .map(|jump| jump.input.as_str())
+                .collect::<Vec<&str>>(),
+            vec!["end"],
+            "expected the configuration 'end' jump input"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn iosxe_exec_to_configuration_escalates() -> TestResult {
+        let iosxe_driver = platform_driver("cisco_iosxe")?;
+
+        assert_eq!(
+            path(&iosxe_driver, "exec", "configuration"),
+            steps(&[
+                ("privilege-exec", PrivilegeAction::Escalate),
+                ("configuration", PrivilegeAction::Escalate),
+            ]),
+            "expected escalating through privilege-exec"
+        );
+
+        assert_eq!(
+            path(&iosxe_driver, "tclsh", "configuration"),
+            steps(&[
+                ("privilege-exec", PrivilegeAction::Deescalate),
+                ("configuration", PrivilegeAction::Escalate),
+            ]),
+            "expected de-escalating out of tclsh then escalating"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn sros_configuration_with_path_to_exec_uses_quit_config_jump() -> TestResult {
+        let sros_driver = platform_driver("nokia_sros")?;
+
+        assert_eq!(
+            path(&sros_driver, "configuration-with-path", "exec"),
+            steps(&[("exec", PrivilegeAction::Jump)]),
+            "expected the 'quit-config' jump"
+        );
+        assert_eq!(
+            path(&sros_driver, "configuration-with-path", "configuration"),
+            steps(&[("configuration", PrivilegeAction::Jump)]),
+            "expected the 'exit all' jump"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn cheaper_jump_is_preferred() -> TestResult {
+        assert_eq!(
+            path(&chain(vec![jump("root", 1)])?, "leaf", "root"),
+            steps(&[("root", PrivilegeAction::Jump)]),
+            "expected the cheaper jump"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn expensive_jump_is_not_used() -> TestResult {
+        assert_eq!(
+            path(&chain(vec![jump("root", 3)])?, "leaf", "root"),
+            steps(&[
+                ("mid", PrivilegeAction::Deescalate),
+                ("root", PrivilegeAction::Deescalate),
+            ]),
+            "expected de-escalating as it is cheaper than the jump"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn equal_cost_paths_prefer_the_first_explored_edge() -> TestResult {
+        // the jump costs the same as de-escalating twice, jumps are explored first so win the tie
+        assert_eq!(
+            path(&chain(vec![jump("root", 2)])?, "leaf", "root"),
+            steps(&[("root", PrivilegeAction::Jump)]),
+            "expected the jump to win the cost tie"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn current_privilege_level_path_is_empty() -> TestResult {
+        assert_eq!(
+            path(&chain(vec![])?, "mid", "mid"),
+            steps(&[]),
+            "expected no steps"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_privilege_levels_are_rejected() -> TestResult {
+        assert!(
+            driver(vec![privilege_level("leaf", "missing", vec![])?]).is_err(),
+            "expected unknown previous privilege level error"
+        );
+        assert!(
+            driver(vec![
+                privilege_level("a", "b", vec![])?,
+                privilege_level("b", "a", vec![])?,
+            ])
+            .is_err(),
+            "expected cycle error"
+        );
+        assert!(
+            driver(vec![
+                privilege_level("root", "", vec![])?,
+                privilege_level("leaf", "root", vec![jump("missing", 1)])?,
+            ])
+            .is_err(),
+            "expected unknown jump target error"
+        );
+        assert!(
+            driver(vec![
+                privilege_level("root", "", vec![])?,
+                privilege_level("root", "", vec![])?,
+            ])
+            .is_err(),
+            "expected duplicate privilege level error"
+        );
+
+        Ok(())
     }
 }
@@ -5,11 +5,90 @@ use core::fmt::{
 };
 use std::error::Error;
 
+/// `OpenPhase` indicates the phase of opening a connection -- used to report where an open stalled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OpenPhase {
+    /// Opening the transport and waiting for the first output from the device.
+    Connect,
+    /// In channel authentication.
+    Authentication,
+    /// Finding the device prompt after authentication (ex: acquiring the default privilege level).
+    PromptDiscovery,
+}
+
+impl Display for OpenPhase {
+    fn fmt(
+        &self,
+        f: &mut Formatter<'_>,
+    ) -> Result {
+        match self {
+            Self::Connect => write!(f, "connect"),
+            Self::Authentication => write!(f, "authentication"),
+            Self::PromptDiscovery => write!(f, "prompt discovery"),
+        }
+    }
+}
+
+/// `ErrorKind` indicates the flavor of a `ScrapliError` so that callers can handle specific
+/// failures without parsing the error details.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// Any error that does not have a more specific kind.
+    Generic,
+    /// The host key presented by the host does not match the known (stored) key for the host --
+    /// this may indicate a man in the middle attack!
+    HostKeyMismatch {
+        /// The host whose key did not match.
+        host: String,
+        /// The fingerprint of the key presented by the host, empty if it could not be determined.
+        fingerprint: String,
+    },
+    /// In channel authentication saw output indicating that authentication can never succeed (ex:
+    /// "Permission denied" or "Connection refused").
+    AuthenticationFailed {
+        /// The output (line) that matched one of the channel's `auth_fatal_patterns`.
+        output: String,
+    },
+    /// Opening the connection did not complete before the `timeout_open` deadline.
+    OpenTimedOut {
+        /// The phase the open was in when the deadline passed.
+        phase: OpenPhase,
+    },
+    /// Opening the connection was cancelled via a `CancelHandle`.
+    OpenCancelled {
+        /// The phase the open was in when it was cancelled.
+        phase: OpenPhase,
+    },
+    /// The process backing the transport (ex: ssh or docker) exited.
+    ProcessExited {
+        /// The exit code of the process -- 128 plus the signal number if it was killed by a
+        /// signal, as shells report it.
+        code: i32,
+    },
+    /// The host key presented by the host is unknown and was rejected by the host key policy.
+    HostKeyRejected {
+        /// The host whose key was rejected.
+        host: String,
+        /// The fingerprint of the rejected key.
+        fingerprint: String,
+    },
+    /// An operation was blocked by an interceptor policy (ex: a `DenyList` pattern matched the
+    /// input).
+    OperationDenied {
+        /// The input that was denied.
+        input: String,
+        /// The pattern (or policy) that denied the input.
+        pattern: String,
+    },
+}
+
 ///  `ScrapliError` is a base error for all scraplirs errors.
 #[derive(Debug)]
 pub struct ScrapliError {
     /// A string holding details about the error.
     pub details: String,
+    /// The kind of the error.
+    pub kind: ErrorKind,
 }
 
 impl Display for ScrapliError {
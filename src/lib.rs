@@ -63,6 +63,9 @@ pub mod driver {
     /// The generic driver operation options re-exported for convenience.
     pub use crate::driver::generic::driver::OperationOptions as GenericDriverOperationOptions;
 
+    /// Interceptors (middleware) that see (and may rewrite, block or retry) every driver operation.
+    pub mod interceptor;
+
     /// Network driver is a driver that wraps `GenericDriver` and adds "network" things like a basic
     /// understanding of privilege levels.
     pub mod network {
@@ -81,9 +84,22 @@ pub mod driver {
     pub use crate::driver::network::driver::Driver as NetworkDriver;
 }
 
+/// Credentials (passwords and the like) and the providers that supply them.
+pub mod credentials;
+
+/// Layered (defaults file, then `SCRAPLI_*` environment variables) defaults for the driver builders.
+pub mod defaults;
+
 /// Scraplirs errors.
 pub mod errors;
 
+/// Serializable driver options -- for storing connection profiles and building drivers from them.
+pub mod options;
+
+/// Native rust parsers for (some) common show commands -- these produce typed structured data
+/// from `Response` output without relying on external templates.
+pub mod parsers;
+
 /// Module responsible for dealing with "platform" things -- meaning taking a yaml platform
 /// definition and generating a valid scraplirs `GenericDriver` or `NetworkDriver` object.
 pub mod platform;
@@ -97,6 +113,16 @@ pub mod transport {
     /// Base transport module providing trait that all transports must implement.
     pub mod base;
 
+    /// The exec (docker/podman/kubectl exec, or network namespace exec) transport implementation.
+    pub mod exec;
+
+    /// The serial (tty) console transport implementation for out of band device access.
+    pub mod serial;
+
+    /// The raw tcp (optionally tls wrapped) socket transport implementation for already
+    /// authenticated streams.
+    pub mod tcp;
+
     /// The "system" (/bin/ssh wrapper -- the "original") scrapli transport implementation.
     pub mod system;
 }
@@ -109,6 +135,9 @@ pub mod util {
     /// Some string helpers.
     pub(crate) mod strings;
 
+    /// Serde helpers for (de)serializing durations and regex patterns as strings.
+    pub(crate) mod serde_helpers;
+
     /// A simple queue implementation used in the scraplirs channel.
     pub(crate) mod queue;
 
@@ -0,0 +1,549 @@
+use crate::credentials::CredentialSource;
+use crate::driver::{
+    GenericDriver,
+    GenericDriverBuilder,
+    NetworkDriver,
+    NetworkDriverBuilder,
+};
+use crate::errors::{
+    ErrorKind,
+    ScrapliError,
+};
+use crate::transport::base::TransportType;
+use crate::transport::exec::ExecPreset;
+use crate::transport::serial::{
+    FlowControl,
+    Parity,
+    StopBits,
+};
+use crate::util::serde_helpers::{
+    option_duration,
+    option_regex,
+};
+use core::time::Duration;
+use regex::bytes::Regex;
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use serde_yaml::with::singleton_map_recursive;
+
+/// `DriverOptions` is a serializable description of a driver -- for example a per device connection
+/// profile stored in a database -- that can be converted into a generic or network driver.
+///
+/// Durations are human readable strings (ex: "30s" or "1m 30s"), patterns are regex strings and
+/// credentials are `CredentialSource`s. Any option that is not set keeps the builder default.
+#[expect(
+    clippy::module_name_repetitions,
+    reason = "the type names stay meaningful when imported outside of the module"
+)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", default, deny_unknown_fields)]
+pub struct DriverOptions {
+    /// Indicates if in channel authentication is skipped.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auth_bypass: Option<bool>,
+    /// Indicates if the device is reached via a console (terminal) server.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub console_mode: Option<bool>,
+    /// The password for the console login prompt.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub console_password: Option<CredentialSource>,
+    /// The username for the console login prompt.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub console_user: Option<String>,
+    /// The default desired privilege level -- network drivers only.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_desired_privilege_level: Option<String>,
+    /// Exec transport options.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exec: Option<ExecOptions>,
+    /// Strings that when seen in output indicate a failed operation.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub failed_when_contains: Option<Vec<String>>,
+    /// The host to connect to.
+    pub host: String,
+    /// The pattern used to find private key passphrase prompts during in channel authentication.
+    #[serde(with = "option_regex", skip_serializing_if = "Option::is_none")]
+    pub passphrase_pattern: Option<Regex>,
+    /// The password for authenticating to the host.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub password: Option<CredentialSource>,
+    /// The pattern used to find password prompts during in channel authentication.
+    #[serde(with = "option_regex", skip_serializing_if = "Option::is_none")]
+    pub password_pattern: Option<Regex>,
+    /// The platform type (ex: `cisco_iosxe`) of the device.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub platform_type: Option<String>,
+    /// The port to connect to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub port: Option<u16>,
+    /// The pattern used to find the device prompt.
+    #[serde(with = "option_regex", skip_serializing_if = "Option::is_none")]
+    pub prompt_pattern: Option<Regex>,
+    /// The delay between reads of the transport.
+    #[serde(with = "option_duration", skip_serializing_if = "Option::is_none")]
+    pub read_delay: Option<Duration>,
+    /// The character (string) sent as a "return".
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub return_char: Option<String>,
+    /// The secondary (enable/escalate) password -- network drivers only.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub secondary_password: Option<CredentialSource>,
+    /// Serial transport options.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub serial: Option<SerialOptions>,
+    /// The path to an ssh config file to use.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ssh_config_file_path: Option<String>,
+    /// The path to an ssh known hosts file to use.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ssh_known_hosts_file_path: Option<String>,
+    /// The passphrase of the private key.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ssh_private_key_passphrase: Option<CredentialSource>,
+    /// The path to a private key to use for ssh authentication.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ssh_private_key_path: Option<String>,
+    /// Indicates if strict host key checking is enabled for ssh transports.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ssh_strict_key: Option<bool>,
+    /// The pipe (ex: "| json") appended to commands for structured output.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub structured_output_pipe: Option<String>,
+    /// Extra arguments passed to the system transport open binary.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub system_extra_args: Option<Vec<String>>,
+    /// The binary the system transport executes (typically "ssh").
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub system_open_bin: Option<String>,
+    /// Tcp transport options.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tcp: Option<TcpOptions>,
+    /// The overall timeout for opening the connection.
+    #[serde(with = "option_duration", skip_serializing_if = "Option::is_none")]
+    pub timeout_open: Option<Duration>,
+    /// The timeout for driver operations.
+    #[serde(with = "option_duration", skip_serializing_if = "Option::is_none")]
+    pub timeout_ops: Option<Duration>,
+    /// The timeout for the initial socket connection.
+    #[serde(with = "option_duration", skip_serializing_if = "Option::is_none")]
+    pub timeout_socket: Option<Duration>,
+    /// The transport implementation to use.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transport_type: Option<TransportType>,
+    /// The username for authenticating to the host.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+    /// The pattern used to find username prompts during in channel authentication.
+    #[serde(with = "option_regex", skip_serializing_if = "Option::is_none")]
+    pub username_pattern: Option<Regex>,
+}
+
+/// Options specific to the `Exec` transport, see `ExecArgs`.
+#[expect(
+    clippy::module_name_repetitions,
+    reason = "the type names stay meaningful when imported outside of the module"
+)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", default, deny_unknown_fields)]
+pub struct ExecOptions {
+    /// The command (and its arguments) to run in the target.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub command: Option<Vec<String>>,
+    /// The container in the pod.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub container: Option<String>,
+    /// Extra arguments passed to the exec binary before the target.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extra_args: Option<Vec<String>>,
+    /// The kubernetes namespace of the pod.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub namespace: Option<String>,
+    /// The binary to execute, overriding the binary of the preset.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub open_bin: Option<String>,
+    /// The exec flavor to use.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub preset: Option<ExecPreset>,
+    /// The container, pod or network namespace to exec in.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+}
+
+/// Options specific to the `Serial` transport, see `SerialArgs`.
+#[expect(
+    clippy::module_name_repetitions,
+    reason = "the type names stay meaningful when imported outside of the module"
+)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", default, deny_unknown_fields)]
+pub struct SerialOptions {
+    /// The baud rate of the serial line.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub baud_rate: Option<u32>,
+    /// Indicates if a break should be sent after opening the device.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub break_on_open: Option<bool>,
+    /// The tty device to open, ex: "/dev/ttyUSB0".
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub device: Option<String>,
+    /// The flow control of the serial line.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub flow_control: Option<FlowControl>,
+    /// The parity of the serial line.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parity: Option<Parity>,
+    /// The number of stop bits of the serial line.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stop_bits: Option<StopBits>,
+    /// The number of returns sent after opening the device to elicit a prompt.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wake_returns: Option<u8>,
+}
+
+/// Options specific to the `Tcp` transport, see `TcpArgs`.
+#[expect(
+    clippy::module_name_repetitions,
+    reason = "the type names stay meaningful when imported outside of the module"
+)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", default, deny_unknown_fields)]
+pub struct TcpOptions {
+    /// How long a single read waits for data before returning nothing.
+    #[serde(with = "option_duration", skip_serializing_if = "Option::is_none")]
+    pub read_poll_interval: Option<Duration>,
+    /// Indicates if the connection should be wrapped in TLS.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tls: Option<bool>,
+    /// Path to a PEM file of CA certificates to trust.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tls_ca_file: Option<String>,
+    /// Disables verification of the server certificate.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tls_insecure: Option<bool>,
+    /// The server name used for SNI and certificate verification.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tls_server_name: Option<String>,
+}
+
+impl DriverOptions {
+    fn apply_channel_options(
+        &self,
+        mut builder: GenericDriverBuilder,
+    ) -> GenericDriverBuilder {
+        if let Some(prompt_pattern) = self.prompt_pattern.as_ref() {
+            builder = builder.prompt_pattern(prompt_pattern.clone());
+        }
+
+        if let Some(username_pattern) = self.username_pattern.as_ref() {
+            builder = builder.username_pattern(username_pattern.clone());
+        }
+
+        if let Some(password_pattern) = self.password_pattern.as_ref() {
+            builder = builder.password_pattern(password_pattern.clone());
+        }
+
+        if let Some(passphrase_pattern) = self.passphrase_pattern.as_ref() {
+            builder = builder.passphrase_pattern(passphrase_pattern.clone());
+        }
+
+        if let Some(return_char) = self.return_char.as_deref() {
+            builder = builder.return_char(return_char);
+        }
+
+        if let Some(read_delay) = self.read_delay {
+            builder = builder.read_delay(read_delay);
+        }
+
+        if let Some(timeout_open) = self.timeout_open {
+            builder = builder.timeout_open(timeout_open);
+        }
+
+        if let Some(timeout_ops) = self.timeout_ops {
+            builder = builder.timeout_ops(timeout_ops);
+        }
+
+        builder
+    }
+
+    fn apply_transport_options(
+        &self,
+        mut builder: GenericDriverBuilder,
+    ) -> GenericDriverBuilder {
+        if let Some(timeout_socket) = self.timeout_socket {
+            builder = builder.timeout_socket(timeout_socket);
+        }
+
+        if let Some(ssh_strict_key) = self.ssh_strict_key {
+            builder = builder.ssh_strict_key(ssh_strict_key);
+        }
+
+        if let Some(config_file_path) = self.ssh_config_file_path.as_deref() {
+            builder = builder.ssh_config_file_path(config_file_path);
+        }
+
+        if let Some(known_hosts_file_path) = self.ssh_known_hosts_file_path.as_deref() {
+            builder = builder.ssh_known_hosts_file_path(known_hosts_file_path);
+        }
+
+        if let Some(private_key_path) = self.ssh_private_key_path.as_deref() {
+            builder = builder.ssh_private_key_path(private_key_path);
+        }
+
+        if let Some(private_key_passphrase) = self.ssh_private_key_passphrase.as_ref() {
+            builder =
+                builder.ssh_private_key_passphrase_credential(private_key_passphrase.credential());
+        }
+
+        if let Some(open_bin) = self.system_open_bin.as_deref() {
+            builder = builder.system_open_bin(open_bin);
+        }
+
+        if let Some(extra_args) = self.system_extra_args.as_ref() {
+            builder = builder.system_extra_args(extra_args.clone());
+        }
+
+        if let Some(serial) = self.serial.as_ref() {
+            builder = serial.apply(builder);
+        }
+
+        if let Some(exec) = self.exec.as_ref() {
+            builder = exec.apply(builder);
+        }
+
+        if let Some(tcp) = self.tcp.as_ref() {
+            builder = tcp.apply(builder);
+        }
+
+        builder
+    }
+
+    /// Returns `DriverOptions` parsed from a YAML (or JSON) string -- enums (ex: credential
+    /// sources) are single key maps like `password: {env: DEVICE_PASSWORD}` rather than YAML tags.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ScrapliError` if the string is not valid options.
+    pub fn from_yaml(yaml: &str) -> Result<Self, ScrapliError> {
+        singleton_map_recursive::deserialize(serde_yaml::Deserializer::from_str(yaml)).map_err(
+            |err| ScrapliError {
+                details: format!("failed parsing driver options, error: {err}"),
+                kind: ErrorKind::Generic,
+            },
+        )
+    }
+
+    /// Returns a generic driver builder with all set options applied -- for further customizing
+    /// (ex: setting callables) before building.
+    #[must_use]
+    pub fn generic_driver_builder(self) -> GenericDriverBuilder {
+        let mut builder = GenericDriverBuilder::new(self.host.as_str());
+
+        if let Some(port) = self.port {
+            builder = builder.port(port);
+        }
+
+        if let Some(transport_type) = self.transport_type {
+            builder = builder.transport_type(transport_type);
+        }
+
+        if let Some(user) = self.user.as_deref() {
+            builder = builder.user(user);
+        }
+
+        if let Some(password) = self.password.as_ref() {
+            builder = builder.password_credential(password.credential());
+        }
+
+        if let Some(auth_bypass) = self.auth_bypass {
+            builder = builder.auth_bypass(auth_bypass);
+        }
+
+        builder = self.apply_channel_options(builder);
+        builder = self.apply_transport_options(builder);
+
+        if let Some(console_mode) = self.console_mode {
+            builder = builder.console_mode(console_mode);
+        }
+
+        if let Some(console_user) = self.console_user.as_deref() {
+            builder = builder.console_user(console_user);
+        }
+
+        if let Some(console_password) = self.console_password.as_ref() {
+            builder = builder.console_password_credential(console_password.credential());
+        }
+
+        if let Some(platform_type) = self.platform_type.as_deref() {
+            builder = builder.platform_type(platform_type);
+        }
+
+        if let Some(structured_output_pipe) = self.structured_output_pipe.as_deref() {
+            builder = builder.structured_output_pipe(structured_output_pipe);
+        }
+
+        if let Some(failed_when_contains) = self.failed_when_contains {
+            builder = builder.failed_when_contains(failed_when_contains);
+        }
+
+        builder
+    }
+
+    /// Returns a generic driver built from the options.
+    #[must_use]
+    pub fn into_generic_driver(self) -> GenericDriver {
+        self.generic_driver_builder().build()
+    }
+
+    /// Returns a network driver built from the options.
+    #[must_use]
+    pub fn into_network_driver(self) -> NetworkDriver {
+        self.network_driver_builder().build()
+    }
+
+    /// Returns a network driver builder with all set options applied.
+    #[must_use]
+    pub fn network_driver_builder(mut self) -> NetworkDriverBuilder {
+        let secondary_password = self.secondary_password.take();
+        let default_desired_privilege_level = self.default_desired_privilege_level.take();
+
+        let mut builder = NetworkDriverBuilder::new(self.generic_driver_builder());
+
+        if let Some(source) = secondary_password.as_ref() {
+            builder = builder.secondary_password_credential(source.credential());
+        }
+
+        if let Some(privilege_level) = default_desired_privilege_level.as_deref() {
+            builder = builder.default_desired_privilege_level(privilege_level);
+        }
+
+        builder
+    }
+
+    /// Returns the options serialized as YAML (in the same form `from_yaml` reads).
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ScrapliError` if the options cannot be serialized.
+    pub fn to_yaml(&self) -> Result<String, ScrapliError> {
+        let mut yaml = vec![];
+
+        if let Err(err) =
+            singleton_map_recursive::serialize(self, &mut serde_yaml::Serializer::new(&mut yaml))
+        {
+            return Err(ScrapliError {
+                details: format!("failed serializing driver options, error: {err}"),
+                kind: ErrorKind::Generic,
+            });
+        }
+
+        String::from_utf8(yaml).map_err(|err| ScrapliError {
+            details: format!("failed serializing driver options, error: {err}"),
+            kind: ErrorKind::Generic,
+        })
+    }
+}
+
+impl ExecOptions {
+    fn apply(
+        &self,
+        mut builder: GenericDriverBuilder,
+    ) -> GenericDriverBuilder {
+        if let Some(preset) = self.preset {
+            builder = builder.exec_preset(preset);
+        }
+
+        if let Some(open_bin) = self.open_bin.as_deref() {
+            builder = builder.exec_open_bin(open_bin);
+        }
+
+        if let Some(target) = self.target.as_deref() {
+            builder = builder.exec_target(target);
+        }
+
+        if let Some(namespace) = self.namespace.as_deref() {
+            builder = builder.exec_namespace(namespace);
+        }
+
+        if let Some(container) = self.container.as_deref() {
+            builder = builder.exec_container(container);
+        }
+
+        if let Some(command) = self.command.as_ref() {
+            builder = builder.exec_command(command.clone());
+        }
+
+        if let Some(extra_args) = self.extra_args.as_ref() {
+            builder = builder.exec_extra_args(extra_args.clone());
+        }
+
+        builder
+    }
+}
+
+impl SerialOptions {
+    fn apply(
+        &self,
+        mut builder: GenericDriverBuilder,
+    ) -> GenericDriverBuilder {
+        if let Some(device) = self.device.as_deref() {
+            builder = builder.serial_device(device);
+        }
+
+        if let Some(baud_rate) = self.baud_rate {
+            builder = builder.serial_baud_rate(baud_rate);
+        }
+
+        if let Some(parity) = self.parity {
+            builder = builder.serial_parity(parity);
+        }
+
+        if let Some(stop_bits) = self.stop_bits {
+            builder = builder.serial_stop_bits(stop_bits);
+        }
+
+        if let Some(flow_control) = self.flow_control {
+            builder = builder.serial_flow_control(flow_control);
+        }
+
+        if let Some(break_on_open) = self.break_on_open {
+            builder = builder.serial_break_on_open(break_on_open);
+        }
+
+        if let Some(wake_returns) = self.wake_returns {
+            builder = builder.serial_wake_returns(wake_returns);
+        }
+
+        builder
+    }
+}
+
+impl TcpOptions {
+    fn apply(
+        &self,
+        mut builder: GenericDriverBuilder,
+    ) -> GenericDriverBuilder {
+        if let Some(read_poll_interval) = self.read_poll_interval {
+            builder = builder.tcp_read_poll_interval(read_poll_interval);
+        }
+
+        if let Some(tls) = self.tls {
+            builder = builder.tcp_tls(tls);
+        }
+
+        if let Some(tls_server_name) = self.tls_server_name.as_deref() {
+            builder = builder.tcp_tls_server_name(tls_server_name);
+        }
+
+        if let Some(tls_ca_file) = self.tls_ca_file.as_deref() {
+            builder = builder.tcp_tls_ca_file(tls_ca_file);
+        }
+
+        if let Some(tls_insecure) = self.tls_insecure {
+            builder = builder.tcp_tls_insecure(tls_insecure);
+        }
+
+        builder
+    }
+}
@@ -0,0 +1,89 @@
+mod interface_status;
+mod show_version;
+
+pub use interface_status::InterfaceStatus;
+pub use show_version::ShowVersion;
+
+use crate::errors::{
+    ErrorKind,
+    ScrapliError,
+};
+use std::collections::HashMap;
+
+/// The signature of a native parser -- takes the (string) output of a command and returns the
+/// parsed type.
+pub type Parser<T> = fn(output: &str) -> Result<T, ScrapliError>;
+
+/// The registry of parsers for a given type -- keyed by (platform type, normalized command) where
+/// the platform type matches the `Platform.platform_type` (ex: `cisco_iosxe`).
+pub type Registry<T> = HashMap<(&'static str, &'static str), Parser<T>>;
+
+/// `Parsable` is implemented by any type that can be parsed out of command output by a native
+/// parser.
+pub trait Parsable: Sized + 'static {
+    /// Returns the registry of parsers that can produce this type.
+    fn registry() -> &'static Registry<Self>;
+}
+
+/// Normalizes a command so it can be used to look up a parser in a `Registry` -- that is, the
+/// command is lower cased and any leading/trailing/repeated whitespace is removed.
+#[must_use]
+pub fn normalize_command(command: &str) -> String {
+    command
+        .split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+/// Parses `output` of `command` (from a device of `platform_type`) into `T`.
+///
+/// # Errors
+///
+/// Returns a `ScrapliError` if there is no parser registered for the platform type and command or
+/// if the parser fails to parse the output.
+pub fn parse<T: Parsable>(
+    platform_type: &str,
+    command: &str,
+    output: &str,
+) -> Result<T, ScrapliError> {
+    let normalized_command = normalize_command(command);
+
+    let Some(parser) = T::registry().get(&(platform_type, normalized_command.as_str())) else {
+        return Err(ScrapliError {
+            details: format!(
+                "no parser registered for platform type '{platform_type}' and command \
+                '{normalized_command}'"
+            ),
+            kind: ErrorKind::Generic,
+        });
+    };
+
+    parser(output)
+}
+
+/// Returns the data lines of a table -- that is, all lines after the first line containing
+/// `header`, skipping separator lines made up only of dashes, equals, pluses or whitespace.
+fn table_lines<'a>(
+    output: &'a str,
+    header: &str,
+) -> Result<Vec<&'a str>, ScrapliError> {
+    let mut lines = output.lines().skip_while(|line| !line.contains(header));
+
+    if lines.next().is_none() {
+        return Err(ScrapliError {
+            details: format!("failed parsing output, table header '{header}' not found"),
+            kind: ErrorKind::Generic,
+        });
+    }
+
+    Ok(lines
+        .filter(|line| {
+            !line.trim().is_empty()
+                && !line
+                    .trim()
+                    .chars()
+                    .all(|c| matches!(c, '-' | '=' | '+' | ' '))
+        })
+        .collect())
+}
@@ -0,0 +1,281 @@
+use super::{
+    table_lines,
+    Parsable,
+    Parser,
+    Registry,
+};
+use crate::errors::ScrapliError;
+use once_cell::sync::OnceCell;
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+/// `InterfaceStatus` holds the (normalized) status of a single interface. Admin and oper status are
+/// normalized to lower case "up"/"down" where possible.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct InterfaceStatus {
+    /// The name of the interface, ex: `GigabitEthernet1` or `ethernet-1/1`.
+    pub name: String,
+    /// The administrative status of the interface.
+    pub admin_status: String,
+    /// The operational status of the interface.
+    pub oper_status: String,
+    /// The interface description, empty if the platform output does not include it.
+    pub description: String,
+}
+
+impl InterfaceStatus {
+    fn new(
+        name: &str,
+        admin_status: &str,
+        oper_status: &str,
+    ) -> Self {
+        Self {
+            name: name.to_owned(),
+            admin_status: normalize_status(admin_status),
+            oper_status: normalize_status(oper_status),
+            description: String::new(),
+        }
+    }
+}
+
+fn normalize_status(status: &str) -> String {
+    match status.trim().to_lowercase().as_str() {
+        "enable" | "enabled" => String::from("up"),
+        "disable" | "disabled" | "shutdown" => String::from("down"),
+        normalized_status => normalized_status.to_owned(),
+    }
+}
+
+/// Handles "show ip interface brief" style output with columns of: interface, ip address, ok?,
+/// method, status, protocol -- status may be multiple words ("administratively down").
+fn parse_cisco_iosxe(output: &str) -> Result<Vec<InterfaceStatus>, ScrapliError> {
+    let mut interfaces = vec![];
+
+    for line in table_lines(output, "Interface")? {
+        let fields = line.split_whitespace().collect::<Vec<&str>>();
+
+        let (Some(name), Some(protocol), Some(status_fields)) = (
+            fields.first(),
+            fields.last(),
+            fields.get(4..fields.len() - 1),
+        ) else {
+            continue;
+        };
+
+        let status = status_fields.join(" ");
+
+        let admin_status = if status.starts_with("administratively") {
+            "down"
+        } else {
+            "up"
+        };
+
+        interfaces.push(InterfaceStatus::new(name, admin_status, protocol));
+    }
+
+    Ok(interfaces)
+}
+
+/// Handles "show ip interface brief" output with a "protocol-up/link-up/admin-up" status column.
+/// The table is found by the "IP Address" column header, as the `IP Interface Status for VRF ...`
+/// preamble line above the table also holds "Interface Status".
+fn parse_cisco_nxos(output: &str) -> Result<Vec<InterfaceStatus>, ScrapliError> {
+    let mut interfaces = vec![];
+
+    for line in table_lines(output, "IP Address")? {
+        let fields = line.split_whitespace().collect::<Vec<&str>>();
+
+        let (Some(name), Some(status)) = (fields.first(), fields.last()) else {
+            continue;
+        };
+
+        let status_for = |prefix: &str| {
+            status
+                .split('/')
+                .find_map(|part| part.strip_prefix(prefix))
+                .unwrap_or_default()
+        };
+
+        interfaces.push(InterfaceStatus::new(
+            name,
+            status_for("admin-"),
+            status_for("protocol-"),
+        ));
+    }
+
+    Ok(interfaces)
+}
+
+/// Handles "show ip interface brief" output with columns of: interface, ip address, status,
+/// protocol, vrf name.
+fn parse_cisco_iosxr(output: &str) -> Result<Vec<InterfaceStatus>, ScrapliError> {
+    let mut interfaces = vec![];
+
+    for line in table_lines(output, "Interface")? {
+        let fields = line.split_whitespace().collect::<Vec<&str>>();
+
+        let (Some(name), Some(status), Some(protocol)) =
+            (fields.first(), fields.get(2), fields.get(3))
+        else {
+            continue;
+        };
+
+        let admin_status = if status.eq_ignore_ascii_case("shutdown") {
+            "down"
+        } else {
+            "up"
+        };
+
+        interfaces.push(InterfaceStatus::new(name, admin_status, protocol));
+    }
+
+    Ok(interfaces)
+}
+
+/// Handles "show ip interface brief" output with columns of: interface, ip address, status,
+/// protocol, mtu, owner -- status may be multiple words ("admin down").
+fn parse_arista_eos(output: &str) -> Result<Vec<InterfaceStatus>, ScrapliError> {
+    let mut interfaces = vec![];
+
+    for line in table_lines(output, "Interface")? {
+        let fields = line.split_whitespace().collect::<Vec<&str>>();
+
+        // the mtu is the first numeric field after the address, protocol is just before it
+        let Some(mtu_idx) = fields
+            .iter()
+            .skip(2)
+            .position(|field| field.chars().all(|c| c.is_ascii_digit()))
+            .map(|idx| idx + 2)
+        else {
+            continue;
+        };
+
+        let (Some(name), Some(protocol), Some(status_fields)) = (
+            fields.first(),
+            fields.get(mtu_idx - 1),
+            fields.get(2..mtu_idx - 1),
+        ) else {
+            continue;
+        };
+
+        let status = status_fields.join(" ").to_lowercase();
+
+        let admin_status = if status.contains("admin") {
+            "down"
+        } else {
+            "up"
+        };
+
+        interfaces.push(InterfaceStatus::new(name, admin_status, protocol));
+    }
+
+    Ok(interfaces)
+}
+
+/// Handles "show interfaces terse" output with columns of: interface, admin, link, proto, local,
+/// remote.
+fn parse_juniper_junos(output: &str) -> Result<Vec<InterfaceStatus>, ScrapliError> {
+    let mut interfaces = vec![];
+
+    for line in table_lines(output, "Admin Link")? {
+        let fields = line.split_whitespace().collect::<Vec<&str>>();
+
+        let (Some(name), Some(admin_status), Some(oper_status)) =
+            (fields.first(), fields.get(1), fields.get(2))
+        else {
+            continue;
+        };
+
+        interfaces.push(InterfaceStatus::new(name, admin_status, oper_status));
+    }
+
+    Ok(interfaces)
+}
+
+/// Handles "show interface brief" output which is a table with "|" separated cells of: port, admin
+/// state, oper state, speed, type, description.
+fn parse_nokia_srl(output: &str) -> Result<Vec<InterfaceStatus>, ScrapliError> {
+    let mut interfaces = vec![];
+
+    for line in table_lines(output, "Admin State")? {
+        let cells = line.split('|').map(str::trim).collect::<Vec<&str>>();
+
+        let (Some(name), Some(admin_status), Some(oper_status)) =
+            (cells.get(1), cells.get(2), cells.get(3))
+        else {
+            continue;
+        };
+
+        if name.is_empty() {
+            continue;
+        }
+
+        let mut interface = InterfaceStatus::new(name, admin_status, oper_status);
+
+        if let Some(description) = cells.get(6) {
+            (*description).clone_into(&mut interface.description);
+        }
+
+        interfaces.push(interface);
+    }
+
+    Ok(interfaces)
+}
+
+/// Handles "show router interface" output -- interface lines start in the first column with
+/// columns of: interface name, adm, opr(v4/v6), mode, port/sap id, while address lines are
+/// indented.
+fn parse_nokia_sros(output: &str) -> Result<Vec<InterfaceStatus>, ScrapliError> {
+    let mut interfaces = vec![];
+
+    for line in table_lines(output, "Interface-Name")? {
+        if line.starts_with(char::is_whitespace) || line.starts_with("Interfaces :") {
+            continue;
+        }
+
+        let fields = line.split_whitespace().collect::<Vec<&str>>();
+
+        let (Some(name), Some(admin_status), Some(oper_status)) =
+            (fields.first(), fields.get(1), fields.get(2))
+        else {
+            continue;
+        };
+
+        // opr is reported as v4/v6 status, the v4 status is good enough here
+        let ipv4_oper_status = oper_status.split('/').next().unwrap_or_default();
+
+        interfaces.push(InterfaceStatus::new(name, admin_status, ipv4_oper_status));
+    }
+
+    Ok(interfaces)
+}
+
+impl Parsable for Vec<InterfaceStatus> {
+    fn registry() -> &'static Registry<Self> {
+        static REGISTRY: OnceCell<Registry<Vec<InterfaceStatus>>> = OnceCell::new();
+
+        REGISTRY.get_or_init(|| {
+            Registry::from([
+                (
+                    ("cisco_iosxe", "show ip interface brief"),
+                    parse_cisco_iosxe as Parser<Self>,
+                ),
+                (("cisco_nxos", "show ip interface brief"), parse_cisco_nxos),
+                (
+                    ("cisco_iosxr", "show ip interface brief"),
+                    parse_cisco_iosxr,
+                ),
+                (("arista_eos", "show ip interface brief"), parse_arista_eos),
+                (
+                    ("juniper_junos", "show interfaces terse"),
+                    parse_juniper_junos,
+                ),
+                (("nokia_srl", "show interface brief"), parse_nokia_srl),
+                (("nokia_sros", "show router interface"), parse_nokia_sros),
+            ])
+        })
+    }
+}
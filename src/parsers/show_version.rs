@@ -0,0 +1,244 @@
+use super::{
+    Parsable,
+    Parser,
+    Registry,
+};
+use crate::errors::{
+    ErrorKind,
+    ScrapliError,
+};
+use once_cell::sync::OnceCell;
+use regex::Regex;
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+/// `ShowVersion` holds the most commonly useful bits of "show version" output. Any fields that a
+/// platform does not report are left empty.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ShowVersion {
+    /// The operating system version, ex: "17.03.01a".
+    pub os_version: String,
+    /// The uptime as reported by the device -- some platforms report the last boot time instead.
+    pub uptime: String,
+    /// The chassis/processor board serial number.
+    pub serial: String,
+}
+
+/// The compiled patterns a platform's `ShowVersion` parser captures each field with -- the first
+/// capture group of the first matching pattern is the value of the field.
+struct Patterns {
+    os_version: Vec<Regex>,
+    serial: Vec<Regex>,
+    uptime: Vec<Regex>,
+}
+
+impl Patterns {
+    #[allow(clippy::expect_used)]
+    fn new(
+        os_version: &[&str],
+        uptime: &[&str],
+        serial: &[&str],
+    ) -> Self {
+        let compile = |patterns: &[&str]| {
+            patterns
+                .iter()
+                .map(|pattern| {
+                    Regex::new(pattern).expect("failed compiling pattern, this is a bug")
+                })
+                .collect()
+        };
+
+        Self {
+            os_version: compile(os_version),
+            serial: compile(serial),
+            uptime: compile(uptime),
+        }
+    }
+}
+
+/// Captures the first capture group of the first of the given patterns that matches.
+fn capture_first(
+    output: &str,
+    patterns: &[Regex],
+) -> String {
+    patterns
+        .iter()
+        .find_map(|pattern| pattern.captures(output)?.get(1))
+        .map_or_else(String::new, |captured| captured.as_str().trim().to_owned())
+}
+
+fn build(
+    output: &str,
+    patterns: &Patterns,
+) -> Result<ShowVersion, ScrapliError> {
+    let show_version = ShowVersion {
+        os_version: capture_first(output, &patterns.os_version),
+        uptime: capture_first(output, &patterns.uptime),
+        serial: capture_first(output, &patterns.serial),
+    };
+
+    if show_version.os_version.is_empty() {
+        return Err(ScrapliError {
+            details: String::from("failed parsing show version output, no os version found"),
+            kind: ErrorKind::Generic,
+        });
+    }
+
+    Ok(show_version)
+}
+
+fn parse_cisco_iosxe(output: &str) -> Result<ShowVersion, ScrapliError> {
+    static PATTERNS: OnceCell<Patterns> = OnceCell::new();
+
+    build(
+        output,
+        PATTERNS.get_or_init(|| {
+            Patterns::new(
+                &[r"(?m)Cisco IOS[\s-]XE Software, Version\s+(\S+)"],
+                &[r"(?m)^\S+ uptime is (.+)$"],
+                &[r"(?m)^Processor board ID\s+(\S+)"],
+            )
+        }),
+    )
+}
+
+fn parse_cisco_nxos(output: &str) -> Result<ShowVersion, ScrapliError> {
+    static PATTERNS: OnceCell<Patterns> = OnceCell::new();
+
+    build(
+        output,
+        PATTERNS.get_or_init(|| {
+            Patterns::new(
+                &[
+                    r"(?m)^\s*NXOS: version\s+(\S+)",
+                    r"(?m)^\s*system:\s+version\s+(\S+)",
+                ],
+                &["(?m)^Kernel uptime is (.+)$"],
+                &[r"(?m)^\s*Processor Board ID\s+(\S+)"],
+            )
+        }),
+    )
+}
+
+fn parse_cisco_iosxr(output: &str) -> Result<ShowVersion, ScrapliError> {
+    static PATTERNS: OnceCell<Patterns> = OnceCell::new();
+
+    build(
+        output,
+        PATTERNS.get_or_init(|| {
+            Patterns::new(
+                &[
+                    r"(?m)Cisco IOS XR Software, Version\s+(\S+)",
+                    r"(?m)^\s*Version\s+:\s+(\S+)",
+                ],
+                &["(?m)^System uptime is (.+)$", r"(?m)^\S+ uptime is (.+)$"],
+                &[],
+            )
+        }),
+    )
+}
+
+fn parse_arista_eos(output: &str) -> Result<ShowVersion, ScrapliError> {
+    static PATTERNS: OnceCell<Patterns> = OnceCell::new();
+
+    build(
+        output,
+        PATTERNS.get_or_init(|| {
+            Patterns::new(
+                &[r"(?m)^Software image version:\s+(\S+)"],
+                &[r"(?m)^Uptime:\s+(.+)$"],
+                &[r"(?m)^Serial number:\s+(\S+)"],
+            )
+        }),
+    )
+}
+
+fn parse_juniper_junos(output: &str) -> Result<ShowVersion, ScrapliError> {
+    static PATTERNS: OnceCell<Patterns> = OnceCell::new();
+
+    build(
+        output,
+        PATTERNS.get_or_init(|| {
+            Patterns::new(
+                &[r"(?m)^Junos:\s+(\S+)", r"(?m)^JUNOS .*\[(\S+)\]"],
+                &[],
+                &[],
+            )
+        }),
+    )
+}
+
+fn parse_nokia_srl(output: &str) -> Result<ShowVersion, ScrapliError> {
+    static PATTERNS: OnceCell<Patterns> = OnceCell::new();
+
+    build(
+        output,
+        PATTERNS.get_or_init(|| {
+            Patterns::new(
+                &[r"(?m)^Software Version\s*:\s+(\S+)"],
+                &[r"(?m)^Last Booted\s*:\s+(.+)$"],
+                &[r"(?m)^Serial Number\s*:\s+(.+)$"],
+            )
+        }),
+    )
+}
+
+fn parse_nokia_sros(output: &str) -> Result<ShowVersion, ScrapliError> {
+    static PATTERNS: OnceCell<Patterns> = OnceCell::new();
+
+    build(
+        output,
+        PATTERNS.get_or_init(|| {
+            Patterns::new(
+                &[r"(?m)^TiMOS-(\S+)"],
+                &[r"(?m)^System Up Time\s*:\s+(.+)$"],
+                &[r"(?m)^Serial Number\s*:\s+(\S+)"],
+            )
+        }),
+    )
+}
+
+/// Handles "show system information" output -- the version is reported in the "System Version"
+/// field rather than the `TiMOS` banner, and there is no serial number.
+fn parse_nokia_sros_system_information(output: &str) -> Result<ShowVersion, ScrapliError> {
+    static PATTERNS: OnceCell<Patterns> = OnceCell::new();
+
+    build(
+        output,
+        PATTERNS.get_or_init(|| {
+            Patterns::new(
+                &[r"(?m)^System Version\s*:\s+(\S+)"],
+                &[r"(?m)^System Up Time\s*:\s+(.+?)(?:\s+\(hr:min:sec\))?$"],
+                &[],
+            )
+        }),
+    )
+}
+
+impl Parsable for ShowVersion {
+    fn registry() -> &'static Registry<Self> {
+        static REGISTRY: OnceCell<Registry<ShowVersion>> = OnceCell::new();
+
+        REGISTRY.get_or_init(|| {
+            Registry::from([
+                (
+                    ("cisco_iosxe", "show version"),
+                    parse_cisco_iosxe as Parser<Self>,
+                ),
+                (("cisco_nxos", "show version"), parse_cisco_nxos),
+                (("cisco_iosxr", "show version"), parse_cisco_iosxr),
+                (("arista_eos", "show version"), parse_arista_eos),
+                (("juniper_junos", "show version"), parse_juniper_junos),
+                (("nokia_srl", "show version"), parse_nokia_srl),
+                (("nokia_sros", "show version"), parse_nokia_sros),
+                (
+                    ("nokia_sros", "show system information"),
+                    parse_nokia_sros_system_information,
+                ),
+            ])
+        })
+    }
+}
@@ -1,5 +1,16 @@
-use crate::errors::ScrapliError;
+extern crate alloc;
+use crate::driver::network::driver::{
+    PrivilegeLevel,
+    PrivilegeLevelJump,
+    DEFAULT_PRIVILEGE_LEVEL_COST,
+};
+use crate::errors::{
+    ErrorKind,
+    ScrapliError,
+};
+use alloc::collections::BTreeMap;
 use once_cell::sync::OnceCell;
+use regex::bytes::Regex;
 use serde::{
     Deserialize,
     Serialize,
@@ -21,7 +32,7 @@ pub fn get_platforms() -> &'static HashMap<&'static str, &'static str> {
 
     PLATFORMS.get_or_init(|| {
         HashMap::from([
-            ("arista_eos:", ARISTA_EOS_PLATFORM_YAML),
+            ("arista_eos", ARISTA_EOS_PLATFORM_YAML),
             ("cisco_iosxe", CISCO_IOSXE_PLATFORM_YAML),
             ("cisco_iosxr", CISCO_IOSXR_PLATFORM_YAML),
             ("cisco_nxos", CISCO_NXOS_PLATFORM_YAML),
@@ -60,20 +71,159 @@ pub struct Definition {
     pub variants: HashMap<String, Platform>,
 }
 
+/// `PrivilegeLevelJumpDefinition` is the platform definition form of a network driver
+/// `PrivilegeLevelJump`.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct PrivilegeLevelJumpDefinition {
+    /// The "cost" of the jump, defaults to `DEFAULT_PRIVILEGE_LEVEL_COST`.
+    #[serde(default)]
+    pub cost: Option<u32>,
+    /// The input to send to make the jump.
+    pub input: String,
+    /// The name of the privilege level the jump ends up in.
+    pub target: String,
+}
+
+/// `PrivilegeLevelDefinition` is the platform definition form of a network driver
+/// `PrivilegeLevel`.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct PrivilegeLevelDefinition {
+    /// The "cost" of moving in to or out of the privilege level, defaults to
+    /// `DEFAULT_PRIVILEGE_LEVEL_COST`.
+    #[serde(default)]
+    pub cost: Option<u32>,
+    /// The input to de-escalate out of the privilege level.
+    #[serde(default)]
+    pub deescalate: Option<String>,
+    /// The input to escalate into the privilege level.
+    #[serde(default)]
+    pub escalate: Option<String>,
+    /// Indicates if escalating into the privilege level requires authentication.
+    #[serde(default)]
+    pub escalate_auth: bool,
+    /// The prompt to expect when escalating requires authentication.
+    #[serde(default)]
+    pub escalate_prompt: Option<String>,
+    /// Direct transitions to other (non adjacent) privilege levels.
+    #[serde(default)]
+    pub jumps: Vec<PrivilegeLevelJumpDefinition>,
+    /// The name of the privilege level.
+    pub name: String,
+    /// Strings that negate a `pattern` match for the privilege level.
+    #[serde(default)]
+    pub not_contains: Vec<String>,
+    /// The prompt pattern of the privilege level.
+    pub pattern: String,
+    /// The "previous" or "lower" privilege level, if any.
+    #[serde(default, rename = "previous-priv")]
+    pub previous_privilege_level: Option<String>,
+}
+
+impl PrivilegeLevelDefinition {
+    /// Returns the network driver `PrivilegeLevel` of this definition.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the prompt pattern cannot be compiled.
+    pub fn privilege_level(&self) -> Result<PrivilegeLevel, ScrapliError> {
+        let pattern = match Regex::new(self.pattern.as_str()) {
+            Ok(pattern) => pattern,
+            Err(err) => {
+                return Err(ScrapliError {
+                    details: format!(
+                        "privilege level '{}' pattern could not be compiled, error: {err}",
+                        self.name
+                    ),
+                    kind: ErrorKind::Generic,
+                })
+            }
+        };
+
+        Ok(PrivilegeLevel {
+            name: self.name.clone(),
+            pattern,
+            not_contains: self.not_contains.clone(),
+            previous_privilege_level: self.previous_privilege_level.clone().unwrap_or_default(),
+            de_escalate: self.deescalate.clone().unwrap_or_default(),
+            escalate: self.escalate.clone().unwrap_or_default(),
+            escalate_auth: self.escalate_auth,
+            escalate_prompt: self.escalate_prompt.clone().unwrap_or_default(),
+            cost: self.cost.unwrap_or(DEFAULT_PRIVILEGE_LEVEL_COST),
+            jumps: self
+                .jumps
+                .iter()
+                .map(|jump| PrivilegeLevelJump {
+                    target_privilege_level: jump.target.clone(),
+                    input: jump.input.clone(),
+                    cost: jump.cost.unwrap_or(DEFAULT_PRIVILEGE_LEVEL_COST),
+                })
+                .collect(),
+        })
+    }
+}
+
+/// `NetworkDefinition` holds the "network" driver parts of a platform definition.
+#[derive(Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct NetworkDefinition {
+    /// Patterns of platform specific output that means in channel authentication can never
+    /// succeed, for example a vendor's "authentication failed" message. These are checked in
+    /// addition to the channel's `auth_fatal_patterns`.
+    #[serde(default)]
+    pub auth_fatal_patterns: Vec<String>,
+    /// The privilege level operations are executed in by default.
+    #[serde(default)]
+    pub default_desired_privilege_level: String,
+    /// The privilege levels of the platform keyed by name, empty for "generic" platforms.
+    #[serde(default)]
+    pub privilege_levels: BTreeMap<String, PrivilegeLevelDefinition>,
+}
+
 /// `Platform` is a struct that contains JSON or YAML data that represent the attributes required to
 /// create a generic or network driver to connect to a given device type.
 #[derive(Serialize, Deserialize)]
 #[serde(rename_all = "kebab-case")]
 pub struct Platform {
+    /// The network driver definition (privilege levels) of the platform.
+    #[serde(default, rename = "default")]
+    pub network: NetworkDefinition,
+
     /// The type of the platform, for example "nokia_srl" or "cisco_iosxe".
     pub platform_type: String,
 
+    /// The pipe (ex: "| json") appended to commands to have the device emit structured (json)
+    /// output, empty if the platform does not support structured output.
+    #[serde(default)]
+    pub structured_output_pipe: String,
+
     /// The driver type for the platform, either "generic" or "network".
     #[serde(skip)]
     driver_type: DriverType,
 }
 
 impl Platform {
+    /// Returns the compiled `auth_fatal_patterns` of the platform.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any auth fatal pattern cannot be compiled.
+    pub fn auth_fatal_patterns(&self) -> Result<Vec<Regex>, ScrapliError> {
+        self.network
+            .auth_fatal_patterns
+            .iter()
+            .map(|pattern| {
+                Regex::new(pattern).map_err(|err| ScrapliError {
+                    details: format!(
+                        "auth fatal pattern '{pattern}' could not be compiled, error: {err}"
+                    ),
+                    kind: ErrorKind::Generic,
+                })
+            })
+            .collect()
+    }
+
     /// Returns an instance of `Platform` generated from the given `platform_name`.
     ///
     /// # Errors
@@ -86,18 +236,74 @@ impl Platform {
             || {
                 Err(ScrapliError {
                     details: format!("unknown platform name '{platform_name}'"),
+                    kind: ErrorKind::Generic,
                 })
             },
             |platform_str| match serde_yaml::from_str(platform_str) {
                 Ok(platform) => Ok(platform),
                 Err(err) => Err(ScrapliError {
                     details: format!("failed serializing embedded platform type, error: {err}"),
+                    kind: ErrorKind::Generic,
                 }),
             },
         )
     }
 
+    /// Returns the network driver `PrivilegeLevel`s of the platform, ordered by name.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any privilege level prompt pattern cannot be compiled.
+    pub fn privilege_levels(&self) -> Result<Vec<PrivilegeLevel>, ScrapliError> {
+        self.network
+            .privilege_levels
+            .values()
+            .map(PrivilegeLevelDefinition::privilege_level)
+            .collect()
+    }
+
     // fn get_generic_driver() -> Result<(), ScrapliError> {}
     //
     // fn get_network_driver() -> Result<(), ScrapliError> {}
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        get_platforms,
+        Platform,
+    };
+    use std::error::Error;
+
+    #[test]
+    fn embedded_platform_patterns_compile() -> Result<(), Box<dyn Error>> {
+        for platform_name in get_platforms().keys() {
+            let platform = Platform::new(platform_name)?;
+
+            platform.auth_fatal_patterns()?;
+            platform.privilege_levels()?;
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn auth_fatal_patterns_are_loaded() -> Result<(), Box<dyn Error>> {
+        let patterns = Platform::new("cisco_iosxe")?.auth_fatal_patterns()?;
+
+        assert!(
+            patterns
+                .iter()
+                .any(|pattern| pattern.is_match(b"% Authentication failed")),
+            "cisco_iosxe should treat '% Authentication failed' as fatal"
+        );
+        assert!(
+            Platform::new("nokia_srl")?
+                .auth_fatal_patterns()?
+                .is_empty(),
+            "nokia_srl has no auth fatal patterns"
+        );
+
+        Ok(())
+    }
+}
@@ -1,9 +1,67 @@
 extern crate chrono;
+
+/// Helpers for producing (unified) diffs of `Response` and `MultiResponse` outputs.
+pub mod diff;
+
+use crate::errors::{
+    ErrorKind,
+    ScrapliError,
+};
+use crate::parsers::{
+    self,
+    Parsable,
+};
 use chrono::offset::Utc;
 use chrono::{
     Duration,
     NaiveDateTime,
 };
+use regex::Regex;
+
+/// The severity of a `FailurePattern` -- only `Error` severity failures mark a `Response` as
+/// failed, `Warning` severity failures are recorded but otherwise ignored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FailureSeverity {
+    /// Output matching the pattern means the operation failed.
+    Error,
+    /// Output matching the pattern is noteworthy, but does not mean the operation failed.
+    Warning,
+}
+
+/// `FailurePattern` is a regex pattern that, if seen in an output, indicates that the originating
+/// input/command "failed" (or warned, depending on the `severity`).
+#[derive(Debug, Clone)]
+pub struct FailurePattern {
+    /// The pattern to search for in the output.
+    pub pattern: Regex,
+    /// The severity of the failure if the pattern is matched.
+    pub severity: FailureSeverity,
+}
+
+impl FailurePattern {
+    /// Returns a new instance of `FailurePattern`.
+    #[must_use]
+    pub const fn new(
+        pattern: Regex,
+        severity: FailureSeverity,
+    ) -> Self {
+        Self { pattern, severity }
+    }
+}
+
+/// `Failure` holds information about a `failed_when_contains` or `FailurePattern` match in the
+/// output of an operation.
+#[derive(Debug, Clone)]
+pub struct Failure {
+    /// The substring or pattern that was matched.
+    pub pattern: String,
+    /// The severity of the failure, `failed_when_contains` matches are always `Error` severity.
+    pub severity: FailureSeverity,
+    /// The full line of output the match was found in.
+    pub line: String,
+    /// The (byte) offset of the start of the match in the `Response` result.
+    pub offset: usize,
+}
 
 /// Response is an object returned from "successful" (as in no *errors*) scraplirs driver
 /// operations.
@@ -28,10 +86,24 @@ pub struct Response {
     /// A list of strings that, if seen in an output, indicate that the originating input/command
     /// "failed".
     pub failed_when_contains: Vec<String>,
+    /// A list of regex patterns that, if seen in an output, indicate that the originating
+    /// input/command "failed" or warned (based on the severity of the pattern).
+    pub failure_patterns: Vec<FailurePattern>,
     /// Indicates if the operation was a success or failure. Failure in this case means we saw some
-    /// `failed_when_contains` output in the response, *not* that there was an unrecoverable error.
-    /// The latter case would result in an error being returned not a `Response` object.
+    /// `failed_when_contains` output (or an `Error` severity `failure_patterns` match) in the
+    /// response, *not* that there was an unrecoverable error. The latter case would result in an
+    /// error being returned not a `Response` object.
     pub failed: bool,
+    /// All `failed_when_contains` and `failure_patterns` matches seen in the output, including
+    /// `Warning` severity matches that do not mark the `Response` as failed.
+    pub failures: Vec<Failure>,
+    /// The configuration "context" (ex: "config-if") the device was in after the operation, parsed
+    /// from the prompt. This is only populated by the network driver, and is empty if no context
+    /// could be found in the prompt.
+    pub config_context: String,
+    /// The platform type (ex: `cisco_iosxe`) of the device that produced the `Response` -- used to
+    /// select a native parser in `parse`. Empty if the driver was not given a platform type.
+    pub platform_type: String,
 }
 
 impl Response {
@@ -53,7 +125,11 @@ impl Response {
             end_time: Utc::now().naive_utc(),
             elapsed_time: Duration::zero(),
             failed_when_contains,
+            failure_patterns: vec![],
             failed: true,
+            failures: vec![],
+            config_context: String::new(),
+            platform_type: String::new(),
         }
     }
 
@@ -74,24 +150,169 @@ impl Response {
         self.raw_result = b.clone();
         self.result = String::from_utf8(b).expect("invalid utf-8 in result");
 
-        let mut is_failed: bool = false;
+        self.failures = vec![];
+
+        for failed_when_contains_item in &self.failed_when_contains {
+            if let Some(offset) = self.result.find(failed_when_contains_item.as_str()) {
+                self.failures.push(Failure {
+                    pattern: failed_when_contains_item.clone(),
+                    severity: FailureSeverity::Error,
+                    line: line_at_offset(self.result.as_str(), offset),
+                    offset,
+                });
+            }
+        }
+
+        for failure_pattern in &self.failure_patterns {
+            if let Some(matched) = failure_pattern.pattern.find(self.result.as_str()) {
+                self.failures.push(Failure {
+                    pattern: failure_pattern.pattern.to_string(),
+                    severity: failure_pattern.severity,
+                    line: line_at_offset(self.result.as_str(), matched.start()),
+                    offset: matched.start(),
+                });
+            }
+        }
+
+        self.failed = self
+            .failures
+            .iter()
+            .any(|failure| failure.severity == FailureSeverity::Error);
+    }
 
-        for failed_when_contains_item in self.failed_when_contains.clone() {
-            if !self.result.contains(&failed_when_contains_item) {
+    /// Parses the result of the `Response` into `T` with the native parser registered for the
+    /// `platform_type` and input (command) of the `Response`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ScrapliError` if no parser is registered for the platform type and command, or if
+    /// parsing the output fails.
+    pub fn parse<T: Parsable>(&self) -> Result<T, ScrapliError> {
+        parsers::parse(&self.platform_type, &self.input, &self.result)
+    }
+
+    /// Parses the result of the `Response` as json -- typically the output of a command sent with a
+    /// platform's structured output pipe (ex: "| json"). Any banner or prompt lines surrounding the
+    /// json payload are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ScrapliError` if the `Response` is failed (the device most likely returned a cli
+    /// error rather than structured output) or if no json payload can be parsed from the result.
+    pub fn json(&self) -> Result<serde_json::Value, ScrapliError> {
+        self.raise_for_status()?;
+
+        let mut parse_error = None;
+
+        for (offset, _) in self.result.match_indices(['{', '[']) {
+            let line_start = self.result.get(..offset).unwrap_or_default();
+
+            // the payload always starts a line, skips over any braces in banners and the like
+            if !line_start
+                .rsplit('\n')
+                .next()
+                .unwrap_or_default()
+                .trim()
+                .is_empty()
+            {
                 continue;
             }
 
-            is_failed = true;
+            let payload = self.result.get(offset..).unwrap_or_default();
 
-            break;
+            match serde_json::Deserializer::from_str(payload)
+                .into_iter::<serde_json::Value>()
+                .next()
+            {
+                Some(Ok(value)) => return Ok(value),
+                Some(Err(err)) => parse_error = Some(err),
+                None => {}
+            }
         }
 
-        if !is_failed {
-            self.failed = false;
+        let details = parse_error.map_or_else(
+            || {
+                format!(
+                    "no structured output found in response to input '{}', output: '{}'",
+                    self.input,
+                    self.result.trim()
+                )
+            },
+            |err| {
+                format!(
+                    "failed parsing structured output in response to input '{}', error: {err}",
+                    self.input
+                )
+            },
+        );
+
+        Err(ScrapliError {
+            details,
+            kind: ErrorKind::Generic,
+        })
+    }
+
+    /// Returns true if any `Warning` severity `failure_patterns` were matched in the output.
+    #[must_use]
+    pub fn warned(&self) -> bool {
+        self.failures
+            .iter()
+            .any(|failure| failure.severity == FailureSeverity::Warning)
+    }
+
+    /// Converts a failed `Response` into a `ScrapliError` -- returns `Ok` if the `Response` did not
+    /// fail, otherwise returns an error describing the input and the first failing output line.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ScrapliError` if the `Response` is failed.
+    pub fn raise_for_status(&self) -> Result<(), ScrapliError> {
+        if !self.failed {
+            return Ok(());
         }
+
+        let failure_details = self
+            .failures
+            .iter()
+            .find(|failure| failure.severity == FailureSeverity::Error)
+            .map_or_else(String::new, |failure| {
+                format!(
+                    ", matched '{}' at offset {} in line '{}'",
+                    failure.pattern, failure.offset, failure.line
+                )
+            });
+
+        Err(ScrapliError {
+            details: format!(
+                "operation failed for input '{}' on host '{}'{failure_details}",
+                self.input, self.host
+            ),
+            kind: ErrorKind::Generic,
+        })
     }
 }
 
+/// Returns the full line of `s` that contains the byte at `offset`.
+fn line_at_offset(
+    s: &str,
+    offset: usize,
+) -> String {
+    let line_start = s
+        .get(..offset)
+        .and_then(|before| before.rfind('\n'))
+        .map_or(0, |idx| idx + 1);
+
+    let line_end = s
+        .get(offset..)
+        .and_then(|after| after.find('\n'))
+        .map_or(s.len(), |idx| offset + idx);
+
+    s.get(line_start..line_end)
+        .unwrap_or_default()
+        .trim_end_matches('\r')
+        .to_owned()
+}
+
 /// Response is an object returned from "successful" (as in no *errors*) scraplirs driver "multi"
 /// operation -- that is a plural operation like `send_commands` or `send_configs` -- it holds the
 /// individual `Response` objects for all steps/operations of the parent operation.
@@ -142,4 +363,18 @@ impl MultiResponse {
 
         self.responses.push(response);
     }
+
+    /// Converts a failed `MultiResponse` into a `ScrapliError` -- returns `Ok` if none of the
+    /// responses failed, otherwise returns the error of the first failed `Response`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ScrapliError` if the `MultiResponse` is failed.
+    pub fn raise_for_status(&self) -> Result<(), ScrapliError> {
+        for response in &self.responses {
+            response.raise_for_status()?;
+        }
+
+        Ok(())
+    }
 }
@@ -0,0 +1,300 @@
+use crate::response::{
+    MultiResponse,
+    Response,
+};
+use core::fmt;
+use regex::Regex;
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use similar::{
+    ChangeTag,
+    TextDiff,
+};
+
+/// The default number of unchanged lines to show around changed lines in a diff.
+pub const DEFAULT_DIFF_CONTEXT_LINES: usize = 3;
+
+/// The string that any diff `Options.masks` matches are replaced with prior to diffing.
+pub const DIFF_MASK_REPLACEMENT: &str = "<masked>";
+
+/// Holds options to use when diffing `Response` or `MultiResponse` objects.
+#[derive(Clone)]
+pub struct Options {
+    /// The number of unchanged lines to show around changed lines.
+    pub context_lines: usize,
+    /// Vec of regex patterns that are "masked" (replaced with `DIFF_MASK_REPLACEMENT`) in both
+    /// outputs before diffing -- use this to ignore things like timestamps, uptimes or counters.
+    pub masks: Vec<Regex>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            context_lines: DEFAULT_DIFF_CONTEXT_LINES,
+            masks: vec![],
+        }
+    }
+}
+
+/// Indicates if a `Line` of a diff is unchanged, inserted or deleted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum LineKind {
+    /// The line only exists in the "old" output.
+    Delete,
+    /// The line is the same in both outputs.
+    Equal,
+    /// The line only exists in the "new" output.
+    Insert,
+}
+
+/// A single line of a diff `Hunk`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Line {
+    /// The content of the line (after masking), without the trailing newline.
+    pub content: String,
+    /// If the line is unchanged, inserted or deleted.
+    pub kind: LineKind,
+    /// The (one indexed) line number of the line in the "new" output, if it exists there.
+    pub new_line_number: Option<usize>,
+    /// The (one indexed) line number of the line in the "old" output, if it exists there.
+    pub old_line_number: Option<usize>,
+}
+
+/// A group of changed lines (and their surrounding context lines) in a diff.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Hunk {
+    /// The lines that make up the hunk.
+    pub lines: Vec<Line>,
+    /// The number of "new" output lines in the hunk.
+    pub new_len: usize,
+    /// The (one indexed) starting line of the hunk in the "new" output -- if the hunk holds no
+    /// "new" lines this is the line *before* the hunk.
+    pub new_start: usize,
+    /// The number of "old" output lines in the hunk.
+    pub old_len: usize,
+    /// The (one indexed) starting line of the hunk in the "old" output -- if the hunk holds no
+    /// "old" lines this is the line *before* the hunk.
+    pub old_start: usize,
+}
+
+/// `ResponseDiff` is the diff of the result of two `Response` objects. Use `Display` (or
+/// `to_string`) to render it as a unified diff, or serialize it for reports.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[expect(
+    clippy::module_name_repetitions,
+    reason = "the type names stay meaningful when imported outside of the module"
+)]
+pub struct ResponseDiff {
+    /// The hunks (groups of changes) of the diff, empty if the outputs are the same.
+    pub hunks: Vec<Hunk>,
+    /// The label of the "new" `Response` -- its host and input.
+    pub new_label: String,
+    /// The label of the "old" `Response` -- its host and input.
+    pub old_label: String,
+}
+
+impl ResponseDiff {
+    /// Returns true if there are any differences between the diffed outputs.
+    #[must_use]
+    pub const fn has_changes(&self) -> bool {
+        !self.hunks.is_empty()
+    }
+}
+
+impl fmt::Display for ResponseDiff {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter,
+    ) -> fmt::Result {
+        writeln!(f, "--- {}", self.old_label)?;
+        writeln!(f, "+++ {}", self.new_label)?;
+
+        for hunk in &self.hunks {
+            writeln!(
+                f,
+                "@@ -{},{} +{},{} @@",
+                hunk.old_start, hunk.old_len, hunk.new_start, hunk.new_len
+            )?;
+
+            for line in &hunk.lines {
+                let prefix = match line.kind {
+                    LineKind::Delete => '-',
+                    LineKind::Equal => ' ',
+                    LineKind::Insert => '+',
+                };
+
+                writeln!(f, "{prefix}{}", line.content)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// `MultiResponseDiff` is the diff of two `MultiResponse` objects -- the individual responses are
+/// diffed pairwise in order.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[expect(
+    clippy::module_name_repetitions,
+    reason = "the type names stay meaningful when imported outside of the module"
+)]
+pub struct MultiResponseDiff {
+    /// The diffs of each pair of responses.
+    pub diffs: Vec<ResponseDiff>,
+}
+
+impl MultiResponseDiff {
+    /// Returns true if there are any differences between any of the diffed responses.
+    #[must_use]
+    pub fn has_changes(&self) -> bool {
+        self.diffs.iter().any(ResponseDiff::has_changes)
+    }
+}
+
+impl fmt::Display for MultiResponseDiff {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter,
+    ) -> fmt::Result {
+        for diff in &self.diffs {
+            write!(f, "{diff}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Response {
+    /// Returns a `ResponseDiff` of the result of this ("old") `Response` and the `other` ("new")
+    /// `Response` -- handy for comparing "before" and "after" outputs around a change.
+    #[must_use]
+    pub fn diff(
+        &self,
+        other: &Self,
+        options: &Options,
+    ) -> ResponseDiff {
+        diff_results(Some(self), Some(other), options)
+    }
+}
+
+impl MultiResponse {
+    /// Returns a `MultiResponseDiff` of this ("old") `MultiResponse` and the `other` ("new")
+    /// `MultiResponse`. Responses are diffed pairwise in order, if one `MultiResponse` holds more
+    /// responses than the other the extra responses are diffed against an empty output.
+    #[must_use]
+    pub fn diff(
+        &self,
+        other: &Self,
+        options: &Options,
+    ) -> MultiResponseDiff {
+        let response_count = self.responses.len().max(other.responses.len());
+
+        MultiResponseDiff {
+            diffs: (0..response_count)
+                .map(|idx| diff_results(self.responses.get(idx), other.responses.get(idx), options))
+                .collect(),
+        }
+    }
+}
+
+fn mask(
+    output: &str,
+    masks: &[Regex],
+) -> String {
+    let mut masked = output.to_owned();
+
+    for pattern in masks {
+        masked = pattern
+            .replace_all(masked.as_str(), DIFF_MASK_REPLACEMENT)
+            .into_owned();
+    }
+
+    masked
+}
+
+fn label(response: Option<&Response>) -> String {
+    response.map_or_else(
+        || String::from("<no response>"),
+        |labeled_response| format!("{}: {}", labeled_response.host, labeled_response.input),
+    )
+}
+
+/// Returns the (one indexed) start line of a hunk range given its zero indexed start -- an empty
+/// range (nothing deleted or nothing inserted) starts at the line *before* the range, as in the
+/// unified diff format (ex: `@@ -3,0 +4,2 @@`).
+const fn hunk_start(
+    start: usize,
+    len: usize,
+) -> usize {
+    if len == 0 {
+        start
+    } else {
+        start + 1
+    }
+}
+
+fn diff_results(
+    old: Option<&Response>,
+    new: Option<&Response>,
+    options: &Options,
+) -> ResponseDiff {
+    let old_result = mask(
+        old.map_or("", |response| response.result.as_str()),
+        &options.masks,
+    );
+    let new_result = mask(
+        new.map_or("", |response| response.result.as_str()),
+        &options.masks,
+    );
+
+    let text_diff = TextDiff::from_lines(old_result.as_str(), new_result.as_str());
+
+    let mut hunks = vec![];
+
+    for group in text_diff.grouped_ops(options.context_lines) {
+        let (Some(first_op), Some(last_op)) = (group.first(), group.last()) else {
+            continue;
+        };
+
+        let mut lines = vec![];
+
+        for op in &group {
+            for change in text_diff.iter_changes(op) {
+                lines.push(Line {
+                    content: change.value().trim_end_matches(['\r', '\n']).to_owned(),
+                    kind: match change.tag() {
+                        ChangeTag::Delete => LineKind::Delete,
+                        ChangeTag::Equal => LineKind::Equal,
+                        ChangeTag::Insert => LineKind::Insert,
+                    },
+                    new_line_number: change.new_index().map(|idx| idx + 1),
+                    old_line_number: change.old_index().map(|idx| idx + 1),
+                });
+            }
+        }
+
+        let old_len = last_op.old_range().end - first_op.old_range().start;
+        let new_len = last_op.new_range().end - first_op.new_range().start;
+
+        hunks.push(Hunk {
+            lines,
+            new_len,
+            new_start: hunk_start(first_op.new_range().start, new_len),
+            old_len,
+            old_start: hunk_start(first_op.old_range().start, old_len),
+        });
+    }
+
+    ResponseDiff {
+        hunks,
+        new_label: label(new),
+        old_label: label(old),
+    }
+}
@@ -1,5 +1,21 @@
-use crate::errors::ScrapliError;
+extern crate alloc;
+
+use crate::credentials::Credential;
+use crate::errors::{
+    ErrorKind,
+    ScrapliError,
+};
+use alloc::sync::Arc;
+use core::fmt::{
+    Debug,
+    Formatter,
+    Result as FmtResult,
+};
 use core::time::Duration;
+use serde::{
+    Deserialize,
+    Serialize,
+};
 
 /// The default port for scraplirs operations -- defaults to the standard ssh port "22".
 pub const DEFAULT_PORT: u16 = 22;
@@ -16,8 +32,67 @@ pub const DEFAULT_TERM_HEIGHT: u16 = 255;
 /// The default terminal width for transports (if applicable).
 pub const DEFAULT_TERM_WIDTH: u16 = 80;
 
-/// The default ssh "strict key" setting (true, try to verify ssh key authenticity).
-pub const DEFAULT_SSH_STRICT_KEY: bool = true;
+/// The default ssh host key policy (strict, only connect to hosts with a known and matching key).
+pub const DEFAULT_SSH_HOST_KEY_POLICY: HostKeyPolicy = HostKeyPolicy::Strict;
+
+/// The custom type for `HostKeyPolicy::Callback` callables.
+///
+/// The callable receives the host and the fingerprint (ex: "SHA256:...") of an unknown host key
+/// and returns true to accept (and persist) the key or false to reject it. Closures may capture
+/// state, for example a set of pinned fingerprints.
+pub type HostKeyCallback = Arc<dyn Fn(&str, &str) -> bool + Send + Sync>;
+
+/// `HostKeyPolicy` defines how ssh host keys are verified.
+#[derive(Clone)]
+pub enum HostKeyPolicy {
+    /// Only connect to hosts whose key is known and matches ("StrictHostKeyChecking=yes").
+    Strict,
+    /// Let ssh automatically add keys of unknown hosts to the known hosts file, but refuse changed
+    /// keys ("StrictHostKeyChecking=accept-new").
+    AcceptNew,
+    /// Accept (and persist to the known hosts file) the key of unknown hosts when ssh asks about
+    /// them during in channel authentication, refuse changed keys.
+    TrustOnFirstUse,
+    /// Like `TrustOnFirstUse`, but the callable decides if an unknown host key is accepted (and
+    /// persisted) based on the host and key fingerprint. Changed keys are always refused.
+    Callback(HostKeyCallback),
+    /// Do not verify host keys at all ("StrictHostKeyChecking=no" and no known hosts file).
+    Insecure,
+}
+
+impl Debug for HostKeyPolicy {
+    fn fmt(
+        &self,
+        f: &mut Formatter<'_>,
+    ) -> FmtResult {
+        match *self {
+            Self::Strict => write!(f, "Strict"),
+            Self::AcceptNew => write!(f, "AcceptNew"),
+            Self::TrustOnFirstUse => write!(f, "TrustOnFirstUse"),
+            Self::Callback(_) => write!(f, "Callback"),
+            Self::Insecure => write!(f, "Insecure"),
+        }
+    }
+}
+
+impl HostKeyPolicy {
+    /// Returns the ssh `StrictHostKeyChecking` option value for the policy.
+    #[must_use]
+    pub const fn strict_host_key_checking(&self) -> &'static str {
+        match self {
+            Self::Strict => "yes",
+            Self::AcceptNew => "accept-new",
+            Self::TrustOnFirstUse | Self::Callback(_) => "ask",
+            Self::Insecure => "no",
+        }
+    }
+
+    /// Returns true if the policy is `HostKeyPolicy::Strict` -- the default policy.
+    #[must_use]
+    pub const fn is_strict(&self) -> bool {
+        matches!(self, Self::Strict)
+    }
+}
 
 /// Transport is the trait all scraplirs transports must implement in order to be consumed/used by
 /// a channel and ultimately drivers.
@@ -72,12 +147,34 @@ pub trait Transport {
     fn get_port(&self) -> u16;
     /// Returns info used for in channel authentication -- typically only called by the Channel.
     fn in_channel_auth_data(&self) -> InChannelAuthData;
+    /// Sends a break on the underlying transport (ex: a serial line break to get a device into its
+    /// boot loader). Transports that have no concept of a break return an error.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ScrapliError` if the transport does not support breaks or sending the break
+    /// fails.
+    fn send_break(&mut self) -> Result<(), ScrapliError> {
+        Err(ScrapliError {
+            details: String::from("transport does not support sending a break"),
+            kind: ErrorKind::Generic,
+        })
+    }
 }
 
 /// An enum defining valid transport implementations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
 pub enum TransportType {
     /// System is the "standard"/default transport implementation.
     System,
+    /// Serial is the serial (tty) console transport implementation.
+    Serial,
+    /// Exec is the exec (docker/podman/kubectl exec, or network namespace exec) transport
+    /// implementation.
+    Exec,
+    /// Tcp is the raw tcp (optionally tls wrapped) socket transport implementation.
+    Tcp,
 }
 
 /// A struct hodling generic arguments that apply to all transport flavors.
@@ -86,14 +183,20 @@ pub struct TransportArgs {
     pub host: String,
     /// The port to connect to the host on.
     pub port: u16,
+    /// Indicates if `port` was explicitly set (via the builder or defaults) rather than holding
+    /// `DEFAULT_PORT` -- transports merging the arguments with other sources (ex: the `System`
+    /// transport and an ssh config file) only let explicitly set values win.
+    pub port_set: bool,
     /// The username for authetnicating to the host (if applicable).
     pub user: String,
     /// The password for password or keyboard interactive authentication (if applicable).
-    pub password: String,
+    pub password: Credential,
 
     /// The timeout duration for initial socket connection -- see specific transports for exact
     /// implementation.
     pub timeout_socket: Duration,
+    /// Indicates if `timeout_socket` was explicitly set, see `port_set`.
+    pub timeout_socket_set: bool,
     /// The read size for each read of the transport (can leave this to the default!).
     pub read_size: u16,
     /// The terminal height to set on the transport object (not applicable to all transports).
@@ -110,9 +213,11 @@ impl TransportArgs {
         Self {
             host: host.to_owned(),
             port: DEFAULT_PORT,
+            port_set: false,
             user: String::new(),
-            password: String::new(),
+            password: Credential::default(),
             timeout_socket: Duration::from_secs(DEFAULT_TIMEOUT_SOCKET_SECONDS),
+            timeout_socket_set: false,
             read_size: DEFAULT_READ_SIZE,
             term_height: DEFAULT_TERM_HEIGHT,
             term_width: DEFAULT_TERM_WIDTH,
@@ -122,31 +227,90 @@ impl TransportArgs {
 
 /// A struct holding ssh specific arguments for transports.
 pub struct TransportSSHArgs {
-    /// Indicate if ssh strict key checking should be enabled or not.
-    pub strict_key: bool,
+    /// The policy for verifying the host key of the host.
+    pub host_key_policy: HostKeyPolicy,
     /// A path to a private key to use for authentication.
     pub private_key_path: String,
     /// An (optional) passphrase for use with a private key.
-    pub private_key_passphrase: String,
+    pub private_key_passphrase: Credential,
     /// The path to an ssh config file to use.
     pub config_file_path: String,
     /// The path to an ssh known hosts file to use.
     pub known_hosts_file_path: String,
     /// Indicate if this is a netconf connection or not (should not be set by users).
     pub netconf_connection: bool,
+    /// The chain of jump hosts (bastions) to hop through to reach the host, in the order they are
+    /// connected to -- empty to connect directly.
+    pub jump_hosts: Vec<JumpHost>,
 }
 
 impl Default for TransportSSHArgs {
     fn default() -> Self {
         Self {
-            strict_key: DEFAULT_SSH_STRICT_KEY,
+            host_key_policy: DEFAULT_SSH_HOST_KEY_POLICY,
             private_key_path: String::new(),
-            private_key_passphrase: String::new(),
+            private_key_passphrase: Credential::default(),
             config_file_path: String::new(),
             known_hosts_file_path: String::new(),
             netconf_connection: false,
+            jump_hosts: vec![],
+        }
+    }
+}
+
+/// A single hop of a jump host (bastion) chain used to reach the host of a transport. Each hop has
+/// its own credentials and host key settings.
+#[derive(Clone)]
+pub struct JumpHost {
+    /// The jump host to connect to.
+    pub host: String,
+    /// The port to connect to the jump host on.
+    pub port: u16,
+    /// The username for authenticating to the jump host, if empty the ssh default is used.
+    pub user: String,
+    /// The password for authenticating to the jump host (if applicable).
+    pub password: Credential,
+    /// A path to a private key to use for authenticating to the jump host.
+    pub private_key_path: String,
+    /// The policy for verifying the host key of the jump host.
+    pub host_key_policy: HostKeyPolicy,
+    /// The path to an ssh known hosts file to use for the jump host.
+    pub known_hosts_file_path: String,
+}
+
+impl JumpHost {
+    /// Return a new instance of `JumpHost` for the given host with default settings.
+    #[must_use]
+    pub fn new(host: &str) -> Self {
+        Self {
+            host: host.to_owned(),
+            port: DEFAULT_PORT,
+            user: String::new(),
+            password: Credential::default(),
+            private_key_path: String::new(),
+            host_key_policy: DEFAULT_SSH_HOST_KEY_POLICY,
+            known_hosts_file_path: String::new(),
+        }
+    }
+
+    /// Returns the jump host in ssh "destination" form, ex: "user@host:port".
+    #[must_use]
+    pub fn destination(&self) -> String {
+        if self.user.is_empty() {
+            format!("{}:{}", self.host, self.port)
+        } else {
+            format!("{}@{}:{}", self.user, self.host, self.port)
         }
     }
+
+    /// Returns true if the jump host only sets a host, port and/or user (and password), meaning
+    /// it can be expressed as a plain ssh "-J" hop without any per hop options.
+    #[must_use]
+    pub const fn is_plain(&self) -> bool {
+        self.private_key_path.is_empty()
+            && self.known_hosts_file_path.is_empty()
+            && self.host_key_policy.is_strict()
+    }
 }
 
 /// An enum indicating the type of *in channel* authentication to use for a transport.
@@ -156,6 +320,9 @@ pub enum InChannelAuthType {
     Telnet,
     /// SSH in channel auth.
     SSH,
+    /// No in channel auth at all (ex: exec'ing into a container) -- the channel simply waits for
+    /// the prompt.
+    None,
 }
 
 /// A struct hodling data necessary for a `Channel` object to handle in channel authentication for
@@ -163,10 +330,20 @@ pub enum InChannelAuthType {
 pub struct InChannelAuthData {
     /// Indicates the flavor of in channel authentication.
     pub auth_type: InChannelAuthType,
+    /// The host being authenticated to.
+    pub host: String,
     /// The user to use for authenticaiton.
     pub user: String,
-    /// The password to use for authentication.
-    pub password: String,
+    /// The password to use for authentication -- the credential is only asked for its secret when
+    /// a password prompt is seen.
+    pub password: Credential,
     /// The ssh passphrase to use for authentication.
-    pub private_key_passphrase: String,
+    pub private_key_passphrase: Credential,
+    /// The host key policy of the host -- used to answer ssh "are you sure you want to continue
+    /// connecting" prompts.
+    pub host_key_policy: HostKeyPolicy,
+    /// The jump hosts the transport connects through -- their passwords are sent when a password
+    /// prompt for the jump host is seen during in channel authentication, and their host key
+    /// policies are used for host key prompts for the jump host.
+    pub jump_hosts: Vec<JumpHost>,
 }
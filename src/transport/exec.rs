@@ -0,0 +1,450 @@
+use crate::errors::{
+    ErrorKind,
+    ScrapliError,
+};
+use crate::transport::base::{
+    InChannelAuthData,
+    InChannelAuthType,
+    Transport,
+    TransportArgs,
+    TransportSSHArgs,
+    DEFAULT_SSH_HOST_KEY_POLICY,
+};
+use crate::transport::system::{
+    System,
+    SystemArgs,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+/// The default command run in the target of an `Exec` transport -- a plain shell.
+pub const DEFAULT_EXEC_COMMAND: &str = "/bin/sh";
+
+/// The flavor of exec an `Exec` transport uses to reach its target.
+#[expect(
+    clippy::module_name_repetitions,
+    reason = "the type names stay meaningful when imported outside of the module"
+)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ExecPreset {
+    /// The `open_bin` is executed with the extra args, target and command as is.
+    Custom,
+    /// "docker exec -it" into a container.
+    Docker,
+    /// "kubectl exec -it" into a pod (optionally in a namespace and/or a specific container).
+    Kubectl,
+    /// "ip netns exec" in a network namespace.
+    Netns,
+    /// "podman exec -it" into a container.
+    Podman,
+}
+
+impl ExecPreset {
+    /// Returns the binary executed by the preset.
+    #[must_use]
+    pub const fn open_bin(&self) -> &'static str {
+        match *self {
+            Self::Custom => "",
+            Self::Docker => "docker",
+            Self::Kubectl => "kubectl",
+            Self::Netns => "ip",
+            Self::Podman => "podman",
+        }
+    }
+}
+
+/// A struct holding arguments specific to the `Exec` transport implementation.
+#[expect(
+    clippy::module_name_repetitions,
+    reason = "the type names stay meaningful when imported outside of the module"
+)]
+pub struct ExecArgs {
+    /// The command (and its arguments) to run in the target, ex: `["sr_cli"]` for SR Linux nodes.
+    pub command: Vec<String>,
+    /// The container in the pod (`Kubectl` preset only).
+    pub container: String,
+    /// Extra arguments passed to the exec binary before the target, ex: `["--user", "admin"]`.
+    pub extra_args: Vec<String>,
+    /// The kubernetes namespace of the pod (`Kubectl` preset only).
+    pub namespace: String,
+    /// The binary to execute, if empty the binary of the preset is used -- required for the
+    /// `Custom` preset.
+    pub open_bin: String,
+    /// The exec flavor to use.
+    pub preset: ExecPreset,
+    /// The container, pod or network namespace to exec in, if empty the `host` of the transport
+    /// args is used.
+    pub target: String,
+}
+
+impl Default for ExecArgs {
+    fn default() -> Self {
+        Self {
+            command: vec![DEFAULT_EXEC_COMMAND.to_owned()],
+            container: String::new(),
+            extra_args: vec![],
+            namespace: String::new(),
+            open_bin: String::new(),
+            preset: ExecPreset::Docker,
+            target: String::new(),
+        }
+    }
+}
+
+impl ExecArgs {
+    /// Returns the binary and the arguments to execute to exec into the `target` (or `host` if the
+    /// target is unset).
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ScrapliError` if the preset is `Custom` and no `open_bin` is set.
+    pub fn open_bin_and_args(
+        &self,
+        host: &str,
+    ) -> Result<(String, Vec<String>), ScrapliError> {
+        let open_bin = if self.open_bin.is_empty() {
+            self.preset.open_bin().to_owned()
+        } else {
+            self.open_bin.clone()
+        };
+
+        if open_bin.is_empty() {
+            return Err(ScrapliError {
+                details: String::from("exec transport 'custom' preset requires an open bin"),
+                kind: ErrorKind::Generic,
+            });
+        }
+
+        let target = if self.target.is_empty() {
+            host.to_owned()
+        } else {
+            self.target.clone()
+        };
+
+        let mut open_args = vec![];
+
+        match self.preset {
+            ExecPreset::Custom => {
+                open_args.extend(self.extra_args.clone());
+                open_args.push(target);
+                open_args.extend(self.command.clone());
+            }
+            ExecPreset::Docker | ExecPreset::Podman => {
+                open_args.extend([String::from("exec"), String::from("-it")]);
+                open_args.extend(self.extra_args.clone());
+                open_args.push(target);
+                open_args.extend(self.command.clone());
+            }
+            ExecPreset::Kubectl => {
+                open_args.extend([String::from("exec"), String::from("-it")]);
+
+                if !self.namespace.is_empty() {
+                    open_args.extend([String::from("-n"), self.namespace.clone()]);
+                }
+
+                if !self.container.is_empty() {
+                    open_args.extend([String::from("-c"), self.container.clone()]);
+                }
+
+                open_args.extend(self.extra_args.clone());
+                open_args.push(target);
+                open_args.push(String::from("--"));
+                open_args.extend(self.command.clone());
+            }
+            ExecPreset::Netns => {
+                open_args.extend([String::from("netns"), String::from("exec")]);
+                open_args.extend(self.extra_args.clone());
+                open_args.push(target);
+                open_args.extend(self.command.clone());
+            }
+        }
+
+        Ok((open_bin, open_args))
+    }
+}
+
+/// The exec transport object -- execs into a container, pod or network namespace (ex: containerlab
+/// nodes) via docker, podman, kubectl or "ip netns".
+///
+/// Exec transports do no in channel authentication, the channel simply waits for the prompt.
+pub struct Exec {
+    // why the exec command line could not be built (ex: a `Custom` preset without an `open_bin`),
+    // returned when opening
+    invalid_reason: Option<String>,
+    system: System,
+}
+
+impl Exec {
+    /// Returns the exit code of the exec process if it has exited -- 128 plus the signal number if
+    /// it was killed by a signal.
+    pub fn exit_code(&mut self) -> Option<i32> {
+        self.system.exit_code()
+    }
+
+    /// Returns a new `Exec` instance -- if the exec command line cannot be built from the
+    /// `exec_args` opening the transport fails.
+    #[must_use]
+    pub fn new(
+        args: TransportArgs,
+        exec_args: &ExecArgs,
+    ) -> Self {
+        let (system_args, invalid_reason) = match exec_args.open_bin_and_args(args.host.as_str()) {
+            Ok((open_bin, open_args)) => (
+                SystemArgs {
+                    open_bin,
+                    open_args,
+                    extra_args: vec![],
+                },
+                None,
+            ),
+            Err(err) => (SystemArgs::default(), Some(err.details)),
+        };
+
+        Self {
+            invalid_reason,
+            system: System::new(args, TransportSSHArgs::default(), system_args),
+        }
+    }
+}
+
+impl Transport for Exec {
+    fn alive(&mut self) -> bool {
+        self.system.alive()
+    }
+
+    fn close(&mut self) -> Result<(), ScrapliError> {
+        self.system.close()
+    }
+
+    fn get_host(&self) -> String {
+        self.system.get_host()
+    }
+
+    fn get_port(&self) -> u16 {
+        self.system.get_port()
+    }
+
+    fn get_transport_args(self) -> TransportArgs {
+        self.system.get_transport_args()
+    }
+
+    fn in_channel_auth_data(&self) -> InChannelAuthData {
+        let system_auth_data = self.system.in_channel_auth_data();
+
+        InChannelAuthData {
+            auth_type: InChannelAuthType::None,
+            host_key_policy: DEFAULT_SSH_HOST_KEY_POLICY,
+            jump_hosts: vec![],
+            ..system_auth_data
+        }
+    }
+
+    fn open(&mut self) -> Result<(), ScrapliError> {
+        if let Some(invalid_reason) = self.invalid_reason.as_ref() {
+            return Err(ScrapliError {
+                details: invalid_reason.clone(),
+                kind: ErrorKind::Generic,
+            });
+        }
+
+        self.system.open()
+    }
+
+    fn read(&mut self) -> Result<Vec<u8>, ScrapliError> {
+        self.system.read()
+    }
+
+    fn read_n(
+        &mut self,
+        n: u16,
+    ) -> Result<Vec<u8>, ScrapliError> {
+        self.system.read_n(n)
+    }
+
+    fn write(
+        &mut self,
+        b: &[u8],
+    ) -> Result<(), ScrapliError> {
+        self.system.write(b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        Exec,
+        ExecArgs,
+        ExecPreset,
+    };
+    use crate::errors::ErrorKind;
+    use crate::transport::base::{
+        Transport as _,
+        TransportArgs,
+    };
+    use core::time::Duration;
+    use std::error::Error;
+    use std::time::Instant;
+
+    type TestResult = Result<(), Box<dyn Error>>;
+
+    fn strings(values: &[&str]) -> Vec<String> {
+        values.iter().map(|&value| value.to_owned()).collect()
+    }
+
+    fn exec_args(preset: ExecPreset) -> ExecArgs {
+        ExecArgs {
+            extra_args: strings(&["--user", "admin"]),
+            preset,
+            ..ExecArgs::default()
+        }
+    }
+
+    #[test]
+    fn custom_open_bin_and_args() -> TestResult {
+        let args = ExecArgs {
+            open_bin: String::from("lxc-attach"),
+            ..exec_args(ExecPreset::Custom)
+        };
+
+        assert_eq!(
+            args.open_bin_and_args("srl1")?,
+            (
+                String::from("lxc-attach"),
+                strings(&["--user", "admin", "srl1", "/bin/sh"])
+            ),
+            "custom argv"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn custom_without_open_bin_errors() {
+        assert!(
+            exec_args(ExecPreset::Custom)
+                .open_bin_and_args("srl1")
+                .is_err(),
+            "custom preset without an open bin"
+        );
+    }
+
+    #[test]
+    fn custom_without_open_bin_fails_to_open() {
+        let mut exec = Exec::new(TransportArgs::new("srl1"), &exec_args(ExecPreset::Custom));
+
+        assert!(exec.open().is_err(), "opening without an open bin");
+    }
+
+    #[test]
+    fn docker_open_bin_and_args() -> TestResult {
+        assert_eq!(
+            exec_args(ExecPreset::Docker).open_bin_and_args("srl1")?,
+            (
+                String::from("docker"),
+                strings(&["exec", "-it", "--user", "admin", "srl1", "/bin/sh"])
+            ),
+            "docker argv"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn kubectl_open_bin_and_args() -> TestResult {
+        let args = ExecArgs {
+            command: strings(&["sr_cli", "-d"]),
+            container: String::from("srl"),
+            namespace: String::from("lab"),
+            target: String::from("srl1-pod"),
+            ..exec_args(ExecPreset::Kubectl)
+        };
+
+        assert_eq!(
+            args.open_bin_and_args("srl1")?,
+            (
+                String::from("kubectl"),
+                strings(&[
+                    "exec", "-it", "-n", "lab", "-c", "srl", "--user", "admin", "srl1-pod", "--",
+                    "sr_cli", "-d"
+                ])
+            ),
+            "kubectl argv"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn netns_open_bin_and_args() -> TestResult {
+        assert_eq!(
+            exec_args(ExecPreset::Netns).open_bin_and_args("clab-srl1")?,
+            (
+                String::from("ip"),
+                strings(&["netns", "exec", "--user", "admin", "clab-srl1", "/bin/sh"])
+            ),
+            "netns argv"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn non_zero_exit_reports_process_exited() -> TestResult {
+        // "sh -c <script> <target> <command>" -- the target and command are the script's $0 and $1
+        let mut exec = Exec::new(
+            TransportArgs::new("srl1"),
+            &ExecArgs {
+                extra_args: strings(&["-c", "exit 3"]),
+                open_bin: String::from("sh"),
+                preset: ExecPreset::Custom,
+                ..ExecArgs::default()
+            },
+        );
+
+        exec.open()?;
+
+        let deadline = Instant::now()
+            .checked_add(Duration::from_secs(5))
+            .ok_or("deadline overflow")?;
+
+        let kind = loop {
+            if let Err(err) = exec.read() {
+                break Some(err.kind);
+            }
+
+            if Instant::now() >= deadline {
+                break None;
+            }
+        };
+
+        assert_eq!(
+            kind,
+            Some(ErrorKind::ProcessExited { code: 3 }),
+            "reading after the exec process exited"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn podman_open_bin_and_args() -> TestResult {
+        let args = ExecArgs {
+            open_bin: String::from("/usr/local/bin/podman"),
+            ..exec_args(ExecPreset::Podman)
+        };
+
+        assert_eq!(
+            args.open_bin_and_args("srl1")?,
+            (
+                String::from("/usr/local/bin/podman"),
+                strings(&["exec", "-it", "--user", "admin", "srl1", "/bin/sh"])
+            ),
+            "podman argv"
+        );
+
+        Ok(())
+    }
+}
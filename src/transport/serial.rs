@@ -0,0 +1,647 @@
+extern crate nix;
+
+use crate::credentials::Credential;
+use crate::errors::{
+    ErrorKind,
+    ScrapliError,
+};
+use crate::transport::base::{
+    HostKeyPolicy,
+    InChannelAuthData,
+    InChannelAuthType,
+    Transport,
+    TransportArgs,
+};
+use log::debug;
+#[cfg(test)]
+use nix::errno::Errno;
+use nix::libc::O_NOCTTY;
+use nix::poll::{
+    poll,
+    PollFd,
+    PollFlags,
+};
+use nix::sys::termios::{
+    cfmakeraw,
+    cfsetspeed,
+    tcflush,
+    tcgetattr,
+    tcsendbreak,
+    tcsetattr,
+    BaudRate,
+    ControlFlags,
+    FlushArg,
+    InputFlags,
+    SetArg,
+    Termios,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use std::fs::{
+    File,
+    OpenOptions,
+};
+use std::io::{
+    Read as _,
+    Write as _,
+};
+use std::os::unix::fs::OpenOptionsExt as _;
+use std::os::unix::io::AsRawFd as _;
+#[cfg(test)]
+use std::os::unix::io::RawFd;
+
+/// The default baud rate for the `Serial` transport -- 9600, the default of most device consoles.
+pub const DEFAULT_SERIAL_BAUD_RATE: u32 = 9_600;
+
+/// The default number of "wake" returns sent after opening a `Serial` transport.
+pub const DEFAULT_SERIAL_WAKE_RETURNS: u8 = 1;
+
+/// The return sent to "wake" a serial console -- a carriage return, as sent by a terminal's enter
+/// key.
+const SERIAL_WAKE_RETURN: &[u8] = b"\r";
+
+/// The parity setting of a serial line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Parity {
+    /// Even parity.
+    Even,
+    /// No parity bit.
+    None,
+    /// Odd parity.
+    Odd,
+}
+
+/// The number of stop bits of a serial line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum StopBits {
+    /// One stop bit.
+    One,
+    /// Two stop bits.
+    Two,
+}
+
+/// The flow control setting of a serial line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum FlowControl {
+    /// Hardware (RTS/CTS) flow control.
+    Hardware,
+    /// No flow control.
+    None,
+    /// Software (XON/XOFF) flow control.
+    Software,
+}
+
+/// A struct holding arguments specific to the `Serial` transport implementation.
+#[expect(
+    clippy::module_name_repetitions,
+    reason = "the type names stay meaningful when imported outside of the module"
+)]
+pub struct SerialArgs {
+    /// The baud rate of the serial line.
+    pub baud_rate: u32,
+    /// Indicates if a break should be sent after opening the device (before any wake returns).
+    pub break_on_open: bool,
+    /// The tty device to open, ex: "/dev/ttyUSB0" -- if empty the `host` of the transport args is
+    /// used as the device.
+    pub device: String,
+    /// The flow control of the serial line.
+    pub flow_control: FlowControl,
+    /// The parity of the serial line.
+    pub parity: Parity,
+    /// The number of stop bits of the serial line.
+    pub stop_bits: StopBits,
+    /// The number of returns sent after opening the device to elicit a prompt -- consoles usually
+    /// sit silently until some input is received.
+    pub wake_returns: u8,
+}
+
+impl Default for SerialArgs {
+    fn default() -> Self {
+        Self {
+            baud_rate: DEFAULT_SERIAL_BAUD_RATE,
+            break_on_open: false,
+            device: String::new(),
+            flow_control: FlowControl::None,
+            parity: Parity::None,
+            stop_bits: StopBits::One,
+            wake_returns: DEFAULT_SERIAL_WAKE_RETURNS,
+        }
+    }
+}
+
+/// The serial (tty) console transport object -- for out of band access to devices. Serial
+/// transports authenticate in channel like telnet (username and password prompts).
+pub struct Serial {
+    args: TransportArgs,
+    // ptys silently ignore breaks, so tests swap this out to see when a break is sent
+    #[cfg(test)]
+    break_sender: fn(RawFd) -> Result<(), Errno>,
+    file: Option<File>,
+    line_args: SerialArgs,
+    original_termios: Option<Termios>,
+}
+
+impl Serial {
+    /// Applies the line settings (baud rate, parity, stop bits and flow control) to `termios`,
+    /// putting it in raw mode.
+    fn apply_line_settings(
+        &self,
+        termios: &mut Termios,
+    ) -> Result<(), ScrapliError> {
+        cfmakeraw(termios);
+
+        cfsetspeed(termios, baud_rate(self.line_args.baud_rate)?).map_err(|err| ScrapliError {
+            details: format!("failed setting serial device baud rate, error: {err}"),
+            kind: ErrorKind::Generic,
+        })?;
+
+        termios.control_flags |= ControlFlags::CLOCAL | ControlFlags::CREAD;
+
+        termios
+            .control_flags
+            .set(ControlFlags::PARENB, self.line_args.parity != Parity::None);
+        termios
+            .control_flags
+            .set(ControlFlags::PARODD, self.line_args.parity == Parity::Odd);
+        termios.control_flags.set(
+            ControlFlags::CSTOPB,
+            self.line_args.stop_bits == StopBits::Two,
+        );
+        termios.control_flags.set(
+            ControlFlags::CRTSCTS,
+            self.line_args.flow_control == FlowControl::Hardware,
+        );
+        termios.input_flags.set(
+            InputFlags::IXON | InputFlags::IXOFF,
+            self.line_args.flow_control == FlowControl::Software,
+        );
+
+        Ok(())
+    }
+
+    /// Applies the line settings to the open device, putting it in raw mode. Returns the original
+    /// settings of the device.
+    fn configure_line(
+        &self,
+        file: &File,
+    ) -> Result<Termios, ScrapliError> {
+        let fd = file.as_raw_fd();
+
+        let original_termios = tcgetattr(fd).map_err(|err| ScrapliError {
+            details: format!("failed reading serial device settings, error: {err}"),
+            kind: ErrorKind::Generic,
+        })?;
+
+        let mut termios = original_termios.clone();
+
+        self.apply_line_settings(&mut termios)?;
+
+        tcsetattr(fd, SetArg::TCSANOW, &termios).map_err(|err| ScrapliError {
+            details: format!("failed applying serial device settings, error: {err}"),
+            kind: ErrorKind::Generic,
+        })?;
+
+        // discard anything sitting in the buffers from before we configured the line
+        tcflush(fd, FlushArg::TCIOFLUSH).map_err(|err| ScrapliError {
+            details: format!("failed flushing serial device, error: {err}"),
+            kind: ErrorKind::Generic,
+        })?;
+
+        Ok(original_termios)
+    }
+
+    const fn device(&self) -> &str {
+        if self.line_args.device.is_empty() {
+            self.args.host.as_str()
+        } else {
+            self.line_args.device.as_str()
+        }
+    }
+
+    fn file(&mut self) -> Result<&mut File, ScrapliError> {
+        self.file.as_mut().ok_or_else(|| ScrapliError {
+            details: String::from("attempting to use serial transport with no open device!"),
+            kind: ErrorKind::Generic,
+        })
+    }
+
+    /// Returns a new `Serial` instance.
+    #[must_use]
+    pub const fn new(
+        args: TransportArgs,
+        line_args: SerialArgs,
+    ) -> Self {
+        Self {
+            args,
+            #[cfg(test)]
+            break_sender: |fd| tcsendbreak(fd, 0),
+            file: None,
+            line_args,
+            original_termios: None,
+        }
+    }
+
+    /// Sends the configured number of "wake" returns to elicit a prompt from the console.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ScrapliError` if the device is not open or writing to it fails.
+    pub fn wake(&mut self) -> Result<(), ScrapliError> {
+        for _ in 0..self.line_args.wake_returns {
+            self.write(SERIAL_WAKE_RETURN)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Transport for Serial {
+    fn alive(&mut self) -> bool {
+        self.file.is_some()
+    }
+
+    fn close(&mut self) -> Result<(), ScrapliError> {
+        let Some(file) = self.file.take() else {
+            return Err(ScrapliError {
+                details: String::from("trying to close serial transport with no open device"),
+                kind: ErrorKind::Generic,
+            });
+        };
+
+        // put the line back how we found it for whoever uses the device next
+        if let Some(original_termios) = self.original_termios.take() {
+            if let Err(err) = tcsetattr(file.as_raw_fd(), SetArg::TCSANOW, &original_termios) {
+                debug!("failed restoring serial device settings, error: {err}");
+            }
+        }
+
+        Ok(())
+    }
+
+    fn get_host(&self) -> String {
+        self.args.host.clone()
+    }
+
+    fn get_port(&self) -> u16 {
+        self.args.port
+    }
+
+    fn get_transport_args(self) -> TransportArgs {
+        self.args
+    }
+
+    fn in_channel_auth_data(&self) -> InChannelAuthData {
+        InChannelAuthData {
+            auth_type: InChannelAuthType::Telnet,
+            host: self.args.host.clone(),
+            user: self.args.user.clone(),
+            password: self.args.password.clone(),
+            private_key_passphrase: Credential::default(),
+            host_key_policy: HostKeyPolicy::Insecure,
+            jump_hosts: vec![],
+        }
+    }
+
+    fn open(&mut self) -> Result<(), ScrapliError> {
+        debug!(
+            "opening serial transport on device '{}' at {} baud",
+            self.device(),
+            self.line_args.baud_rate
+        );
+
+        let file = match OpenOptions::new()
+            .read(true)
+            .write(true)
+            .custom_flags(O_NOCTTY)
+            .open(self.device())
+        {
+            Ok(file) => file,
+            Err(err) => {
+                return Err(ScrapliError {
+                    details: format!(
+                        "failed opening serial device '{}', error: {err}",
+                        self.device()
+                    ),
+                    kind: ErrorKind::Generic,
+                })
+            }
+        };
+
+        self.original_termios = Some(self.configure_line(&file)?);
+        self.file = Some(file);
+
+        if self.line_args.break_on_open {
+            self.send_break()?;
+        }
+
+        self.wake()
+    }
+
+    fn read(&mut self) -> Result<Vec<u8>, ScrapliError> {
+        self.read_n(self.args.read_size)
+    }
+
+    /// Read up to `n` bytes from the transport.
+    fn read_n(
+        &mut self,
+        n: u16,
+    ) -> Result<Vec<u8>, ScrapliError> {
+        let file = self.file()?;
+
+        let fd = PollFd::new(file.as_raw_fd(), PollFlags::POLLIN);
+
+        match poll(&mut [fd], 5) {
+            Ok(ready) => {
+                if ready != 1 {
+                    return Ok(vec![]);
+                }
+            }
+            Err(err) => {
+                return Err(ScrapliError {
+                    details: format!("error while polling serial device, error: {err}"),
+                    kind: ErrorKind::Generic,
+                })
+            }
+        }
+
+        let mut buf = vec![0_u8; usize::from(n)];
+
+        match file.read(buf.as_mut_slice()) {
+            Ok(read_n) => {
+                buf.truncate(read_n);
+
+                Ok(buf)
+            }
+            Err(err) => Err(ScrapliError {
+                details: format!("error when reading from serial device, error: {err}"),
+                kind: ErrorKind::Generic,
+            }),
+        }
+    }
+
+    fn send_break(&mut self) -> Result<(), ScrapliError> {
+        let fd = self.file()?.as_raw_fd();
+
+        debug!("sending break on serial device '{}'", self.device());
+
+        #[cfg(not(test))]
+        #[expect(
+            clippy::cfg_not_test,
+            reason = "ptys silently ignore breaks, so tests send theirs with the break sender"
+        )]
+        let result = tcsendbreak(fd, 0);
+        #[cfg(test)]
+        let result = (self.break_sender)(fd);
+
+        result.map_err(|err| ScrapliError {
+            details: format!("failed sending break on serial device, error: {err}"),
+            kind: ErrorKind::Generic,
+        })
+    }
+
+    fn write(
+        &mut self,
+        b: &[u8],
+    ) -> Result<(), ScrapliError> {
+        let file = self.file()?;
+
+        if let Err(err) = file.write_all(b) {
+            return Err(ScrapliError {
+                details: format!("failed writing to serial device, error: {err}"),
+                kind: ErrorKind::Generic,
+            });
+        }
+
+        match file.flush() {
+            Ok(()) => Ok(()),
+            Err(err) => Err(ScrapliError {
+                details: format!("failed flushing serial device, error: {err}"),
+                kind: ErrorKind::Generic,
+            }),
+        }
+    }
+}
+
+/// Returns the `BaudRate` for the numeric baud rate `i`.
+#[expect(
+    clippy::single_call_fn,
+    reason = "keeps the baud rate table out of the line settings"
+)]
+fn baud_rate(i: u32) -> Result<BaudRate, ScrapliError> {
+    let baud_rate = match i {
+        1_200 => BaudRate::B1200,
+        2_400 => BaudRate::B2400,
+        4_800 => BaudRate::B4800,
+        9_600 => BaudRate::B9600,
+        19_200 => BaudRate::B19200,
+        38_400 => BaudRate::B38400,
+        57_600 => BaudRate::B57600,
+        115_200 => BaudRate::B115200,
+        230_400 => BaudRate::B230400,
+        _ => {
+            return Err(ScrapliError {
+                details: format!("unsupported serial baud rate '{i}'"),
+                kind: ErrorKind::Generic,
+            })
+        }
+    };
+
+    Ok(baud_rate)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        Parity,
+        Serial,
+        SerialArgs,
+        StopBits,
+    };
+    use crate::transport::base::{
+        Transport,
+        TransportArgs,
+    };
+    use core::time::Duration;
+    use nix::errno::Errno;
+    use nix::poll::{
+        poll,
+        PollFd,
+        PollFlags,
+    };
+    use nix::pty::openpty;
+    use nix::sys::termios::{
+        tcgetattr,
+        ControlFlags,
+    };
+    use nix::unistd::{
+        close,
+        read,
+        ttyname,
+        write,
+    };
+    use std::error::Error;
+    use std::os::unix::io::RawFd;
+    use std::time::Instant;
+
+    /// Stands in for a line break -- writes a marker to the device so the pty master sees when
+    /// the break was sent relative to any other output.
+    fn write_break_marker(fd: RawFd) -> Result<(), Errno> {
+        write(fd, b"<break>").map(|_| ())
+    }
+
+    /// Reads from `fd` until `want` bytes were read or a second passes.
+    fn read_at_least(
+        fd: RawFd,
+        want: usize,
+    ) -> Result<Vec<u8>, Box<dyn Error>> {
+        let deadline = Instant::now() + Duration::from_secs(1);
+        let mut output = vec![];
+        let mut buf = [0_u8; 64];
+
+        while output.len() < want && Instant::now() < deadline {
+            if poll(&mut [PollFd::new(fd, PollFlags::POLLIN)], 10)? == 1 {
+                let read_n = read(fd, &mut buf)?;
+
+                output.extend(buf.iter().take(read_n));
+            }
+        }
+
+        Ok(output)
+    }
+
+    fn open_serial(
+        serial_args: SerialArgs,
+        with_break_marker: bool,
+    ) -> Result<(Serial, RawFd, RawFd), Box<dyn Error>> {
+        let pty = openpty(None, None)?;
+        let device = ttyname(pty.slave)?;
+
+        let mut serial = Serial::new(
+            TransportArgs::new(device.to_string_lossy().as_ref()),
+            serial_args,
+        );
+
+        if with_break_marker {
+            serial.break_sender = write_break_marker;
+        }
+
+        serial.open()?;
+
+        Ok((serial, pty.master, pty.slave))
+    }
+
+    #[test]
+    fn break_on_open_is_sent_before_wake_returns() -> Result<(), Box<dyn Error>> {
+        let (mut serial, master, slave) = open_serial(
+            SerialArgs {
+                break_on_open: true,
+                wake_returns: 2,
+                ..SerialArgs::default()
+            },
+            true,
+        )?;
+
+        let output = read_at_least(master, 9)?;
+
+        serial.close()?;
+        close(master)?;
+        close(slave)?;
+
+        assert_eq!(
+            output, b"<break>\r\r",
+            "expected the break then the wake returns"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn no_break_without_break_on_open() -> Result<(), Box<dyn Error>> {
+        let (mut serial, master, slave) = open_serial(SerialArgs::default(), true)?;
+
+        let output = read_at_least(master, 2)?;
+
+        serial.close()?;
+        close(master)?;
+        close(slave)?;
+
+        assert_eq!(
+            output, b"\r",
+            "expected only the single default wake return"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn real_break_on_pty_does_not_error() -> Result<(), Box<dyn Error>> {
+        let (mut serial, master, slave) = open_serial(
+            SerialArgs {
+                break_on_open: true,
+                ..SerialArgs::default()
+            },
+            false,
+        )?;
+
+        let sent = serial.send_break();
+
+        serial.close()?;
+        close(master)?;
+        close(slave)?;
+
+        assert!(sent.is_ok(), "expected sending a break to succeed");
+
+        Ok(())
+    }
+
+    #[test]
+    fn parity_sets_parity_enable() -> Result<(), Box<dyn Error>> {
+        // the pty driver always clears PARENB on the device, so check the settings we apply
+        let pty = openpty(None, None)?;
+        let original_termios = tcgetattr(pty.slave)?;
+
+        close(pty.master)?;
+        close(pty.slave)?;
+
+        for (parity, enabled, odd) in [
+            (Parity::None, false, false),
+            (Parity::Even, true, false),
+            (Parity::Odd, true, true),
+        ] {
+            let serial = Serial::new(
+                TransportArgs::new("/dev/null"),
+                SerialArgs {
+                    parity,
+                    stop_bits: StopBits::Two,
+                    ..SerialArgs::default()
+                },
+            );
+
+            let mut termios = original_termios.clone();
+
+            serial.apply_line_settings(&mut termios)?;
+
+            assert_eq!(
+                termios.control_flags.contains(ControlFlags::PARENB),
+                enabled,
+                "unexpected parity enable for {parity:?}"
+            );
+            assert_eq!(
+                termios.control_flags.contains(ControlFlags::PARODD),
+                odd,
+                "unexpected odd parity for {parity:?}"
+            );
+            assert!(
+                termios.control_flags.contains(ControlFlags::CSTOPB),
+                "expected two stop bits"
+            );
+        }
+
+        Ok(())
+    }
+}
@@ -1,11 +1,24 @@
 extern crate nix;
-use crate::errors::ScrapliError;
+
+/// Lookup, addition and removal of host keys in ssh known hosts files.
+pub mod known_hosts;
+
+/// A (minimal) ssh config parser used to merge ssh config file settings with transport arguments.
+pub mod ssh_config;
+
+use crate::errors::{
+    ErrorKind,
+    ScrapliError,
+};
 use crate::transport::base::{
+    HostKeyPolicy,
     InChannelAuthData,
     InChannelAuthType,
+    JumpHost,
     Transport,
     TransportArgs,
     TransportSSHArgs,
+    DEFAULT_PORT,
 };
 use crate::util::ptyprocess::PtyProcess;
 use log::debug;
@@ -29,10 +42,19 @@ use std::os::unix::io::{
     FromRawFd,
 };
 use std::process::Command;
+use std::thread;
+use std::time::{
+    Duration,
+    Instant,
+};
 
 /// The default binary to use for the `System` transport -- "ssh".
 pub const DEFAULT_SSH_OPEN_BIN: &str = "ssh";
 
+/// How long a failed read waits for the process to be reapable -- the pty read can fail a moment
+/// before the exited process can be waited on.
+const PROCESS_EXIT_GRACE: Duration = Duration::from_millis(100);
+
 /// A struct holding arguments specific to the `System` transport implementation.
 #[allow(clippy::module_name_repetitions)]
 pub struct SystemArgs {
@@ -57,6 +79,188 @@ impl Default for SystemArgs {
     }
 }
 
+/// Quotes `s` so that it is passed as a single word by a posix shell -- ssh runs `ProxyCommand`
+/// values via the shell.
+fn shell_quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', r"'\''"))
+}
+
+/// Returns the (shell quoted) ssh options implementing the host key `policy` for a `ProxyCommand`.
+fn host_key_open_args(
+    policy: &HostKeyPolicy,
+    known_hosts_file_path: &str,
+) -> Vec<String> {
+    let mut open_args = vec![
+        String::from("-o"),
+        format!(
+            "StrictHostKeyChecking={}",
+            policy.strict_host_key_checking()
+        ),
+    ];
+
+    if matches!(policy, HostKeyPolicy::Insecure) {
+        open_args.extend([
+            String::from("-o"),
+            String::from("UserKnownHostsFile=/dev/null"),
+        ]);
+    } else if !known_hosts_file_path.is_empty() {
+        open_args.extend([
+            String::from("-o"),
+            shell_quote(format!("UserKnownHostsFile={known_hosts_file_path}").as_str()),
+        ]);
+    }
+
+    open_args
+}
+
+/// Returns the `ProxyCommand` that connects to the last hop of `jump_hosts` (through all prior
+/// hops) and forwards stdin/stdout to the final destination ("-W %h:%p").
+fn jump_host_proxy_command(
+    open_bin: &str,
+    jump_hosts: &[JumpHost],
+) -> String {
+    let Some((jump_host, prior_jump_hosts)) = jump_hosts.split_last() else {
+        return String::new();
+    };
+
+    let mut proxy_command = vec![
+        shell_quote(open_bin),
+        String::from("-p"),
+        format!("{}", jump_host.port),
+    ];
+
+    if !jump_host.user.is_empty() {
+        proxy_command.extend([String::from("-l"), shell_quote(jump_host.user.as_str())]);
+    }
+
+    if !jump_host.private_key_path.is_empty() {
+        proxy_command.extend([
+            String::from("-i"),
+            shell_quote(jump_host.private_key_path.as_str()),
+        ]);
+    }
+
+    proxy_command.extend(host_key_open_args(
+        &jump_host.host_key_policy,
+        jump_host.known_hosts_file_path.as_str(),
+    ));
+
+    if !prior_jump_hosts.is_empty() {
+        // the nested proxy command is token expanded once more by this hop's ssh, so escape "%"
+        let nested_proxy_command =
+            jump_host_proxy_command(open_bin, prior_jump_hosts).replace('%', "%%");
+
+        proxy_command.extend([
+            String::from("-o"),
+            shell_quote(format!("ProxyCommand={nested_proxy_command}").as_str()),
+        ]);
+    }
+
+    proxy_command.extend([
+        String::from("-W"),
+        String::from("%h:%p"),
+        shell_quote(jump_host.host.as_str()),
+    ]);
+
+    proxy_command.join(" ")
+}
+
+/// Returns the ssh arguments to connect through the chain of `jump_hosts`.
+///
+/// If all hops are "plain" (see `JumpHost::is_plain`) this is a single "-J" argument, otherwise hops need their own options
+/// so a (nested) `ProxyCommand` using `open_bin` is returned instead. Returns an empty vec if there
+/// are no jump hosts.
+#[must_use]
+pub fn jump_host_open_args(
+    open_bin: &str,
+    jump_hosts: &[JumpHost],
+) -> Vec<String> {
+    if jump_hosts.is_empty() {
+        return vec![];
+    }
+
+    if jump_hosts.iter().all(JumpHost::is_plain) {
+        return vec![
+            String::from("-J"),
+            jump_hosts
+                .iter()
+                .map(JumpHost::destination)
+                .collect::<Vec<String>>()
+                .join(","),
+        ];
+    }
+
+    vec![
+        String::from("-o"),
+        format!(
+            "ProxyCommand={}",
+            jump_host_proxy_command(open_bin, jump_hosts)
+        ),
+    ]
+}
+
+/// Indicates where an `EffectiveSetting` value came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingSource {
+    /// The value was explicitly set via the builder/transport args.
+    Builder,
+    /// The value came from the ssh config file -- it is *not* passed on the command line so ssh
+    /// applies the setting from the file itself.
+    ConfigFile,
+    /// Neither the builder nor the ssh config file set the value, the scraplirs default is used.
+    Default,
+}
+
+/// A single effective setting of a `System` transport and where its value came from.
+#[derive(Debug, Clone)]
+pub struct EffectiveSetting<T> {
+    /// The effective value.
+    pub value: T,
+    /// Where the value came from.
+    pub source: SettingSource,
+}
+
+impl<T> EffectiveSetting<T> {
+    const fn new(
+        value: T,
+        source: SettingSource,
+    ) -> Self {
+        Self { value, source }
+    }
+}
+
+/// `EffectiveSettings` holds the settings a `System` transport connects with.
+///
+/// The settings are the transport arguments merged with the ssh config file (`config_file_path`)
+/// settings for the host. Arguments explicitly set via the builder (or defaults) win over the
+/// config file, even if set to the default value (ex: port 22), and the config file wins over
+/// scraplirs defaults.
+#[derive(Debug, Clone)]
+#[allow(clippy::module_name_repetitions)]
+pub struct EffectiveSettings {
+    /// The host name actually connected to -- may differ from the host if the config file sets a
+    /// `HostName` for the host (alias).
+    pub host_name: EffectiveSetting<String>,
+    /// The user to authenticate as, empty if not set anywhere (ssh uses the local user).
+    pub user: EffectiveSetting<String>,
+    /// The port to connect to.
+    pub port: EffectiveSetting<u16>,
+    /// The connect timeout in seconds.
+    pub connect_timeout: EffectiveSetting<u64>,
+    /// The server alive interval in seconds.
+    pub server_alive_interval: EffectiveSetting<u64>,
+    /// The `StrictHostKeyChecking` setting, ex: "yes" or "no".
+    pub strict_host_key_checking: EffectiveSetting<String>,
+    /// The known hosts file, empty if ssh should use its default.
+    pub user_known_hosts_file: EffectiveSetting<String>,
+    /// The identity (private key) files, in the order ssh tries them.
+    pub identity_files: EffectiveSetting<Vec<String>>,
+    /// The `ProxyJump` setting (or the chain of jump hosts set via the builder), empty if not set.
+    pub proxy_jump: EffectiveSetting<String>,
+    /// The `KexAlgorithms` setting, empty if not set.
+    pub kex_algorithms: EffectiveSetting<String>,
+}
+
 /// The "system" (/bin/ssh, or "original" scrapli) transport object.
 pub struct System {
     args: TransportArgs,
@@ -67,6 +271,7 @@ pub struct System {
     file_handle: RawFd,
     reader: Option<BufReader<File>>,
     writer: Option<BufWriter<File>>,
+    exit_status: Option<WaitStatus>,
 }
 
 impl System {
@@ -86,68 +291,259 @@ impl System {
             file_handle: -1,
             reader: None,
             writer: None,
+            exit_status: None,
         }
     }
 
-    fn build_open_args(&mut self) {
-        if !self.system_args.open_args.is_empty() {
-            self.system_args.open_args = vec![];
+    /// Returns the exit code of the (ssh or other `open_bin`) process if it has exited -- 128 plus
+    /// the signal number if it was killed by a signal, as shells report it.
+    pub fn exit_code(&mut self) -> Option<i32> {
+        match self.poll_exit_status()? {
+            WaitStatus::Exited(_, code) => Some(code),
+            WaitStatus::Signaled(_, signal, _) => Some(128 + signal as i32),
+            _ => None,
         }
+    }
 
-        self.system_args.open_args = vec![
-            self.args.host.clone(),
-            String::from("-p"),
-            format!("{}", self.args.port),
-            String::from("-o"),
-            format!("ConnectTimeout={}", self.args.timeout_socket.as_secs()),
-            String::from("-o"),
-            format!("ServerAliveInterval={}", self.args.timeout_socket.as_secs()),
-        ];
+    /// Returns the status of the process if it has exited (or been killed). The status is cached
+    /// as the process can only be reaped (waited on) once.
+    fn poll_exit_status(&mut self) -> Option<WaitStatus> {
+        if self.exit_status.is_none() {
+            let status = self.process.as_ref()?.status()?;
 
-        if !self.args.user.is_empty() {
-            self.system_args
-                .open_args
-                .extend([String::from("-l"), self.args.user.clone()]);
+            if matches!(status, WaitStatus::Exited(..) | WaitStatus::Signaled(..)) {
+                self.exit_status = Some(status);
+            }
         }
 
-        if self.ssh_args.strict_key {
-            self.system_args.open_args.extend([
-                String::from("-o"),
-                String::from("StrictHostKeyChecking=yes"),
-            ]);
+        self.exit_status
+    }
+
+    /// Returns the `EffectiveSettings` the transport connects with -- that is the transport
+    /// arguments merged with any ssh config file settings that apply to the host. This is computed
+    /// on each call, so it reflects the current contents of the ssh config file.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ScrapliError` if the ssh config file cannot be read or parsed.
+    pub fn effective_settings(&self) -> Result<EffectiveSettings, ScrapliError> {
+        let host_options = if self.ssh_args.config_file_path.is_empty() {
+            ssh_config::HostOptions::default()
+        } else {
+            ssh_config::Config::from_file(self.ssh_args.config_file_path.as_str())?
+                .resolve(self.args.host.as_str(), self.args.user.as_str())
+        };
+
+        let from_config_file = |keyword: &str| {
+            host_options
+                .get(keyword)
+                .map(|value| EffectiveSetting::new(value.to_owned(), SettingSource::ConfigFile))
+        };
+
+        let timeout_socket_explicit = self.args.timeout_socket_set;
 
-            if !self.ssh_args.known_hosts_file_path.is_empty() {
-                self.system_args.open_args.extend([
-                    String::from("-o"),
-                    format!("UserKnownHostsFile={}", self.ssh_args.known_hosts_file_path),
-                ]);
+        let timeout_setting = |keyword: &str| {
+            let config_file_value = host_options
+                .get(keyword)
+                .and_then(|value| value.parse::<u64>().ok());
+
+            match config_file_value {
+                Some(value) if !timeout_socket_explicit => {
+                    EffectiveSetting::new(value, SettingSource::ConfigFile)
+                }
+                _ => EffectiveSetting::new(
+                    self.args.timeout_socket.as_secs(),
+                    if timeout_socket_explicit {
+                        SettingSource::Builder
+                    } else {
+                        SettingSource::Default
+                    },
+                ),
+            }
+        };
+
+        let config_file_port = host_options
+            .get("port")
+            .and_then(|value| value.parse::<u16>().ok());
+
+        let port = match config_file_port {
+            _ if self.args.port_set => {
+                EffectiveSetting::new(self.args.port, SettingSource::Builder)
             }
+            Some(port) => EffectiveSetting::new(port, SettingSource::ConfigFile),
+            None => EffectiveSetting::new(DEFAULT_PORT, SettingSource::Default),
+        };
+
+        let user = if self.args.user.is_empty() {
+            from_config_file("user")
+                .unwrap_or_else(|| EffectiveSetting::new(String::new(), SettingSource::Default))
         } else {
-            self.system_args.open_args.extend([
+            EffectiveSetting::new(self.args.user.clone(), SettingSource::Builder)
+        };
+
+        let strict_host_key_checking = if self.ssh_args.host_key_policy.is_strict() {
+            from_config_file("stricthostkeychecking").unwrap_or_else(|| {
+                EffectiveSetting::new(String::from("yes"), SettingSource::Default)
+            })
+        } else {
+            EffectiveSetting::new(
+                self.ssh_args
+                    .host_key_policy
+                    .strict_host_key_checking()
+                    .to_owned(),
+                SettingSource::Builder,
+            )
+        };
+
+        let user_known_hosts_file =
+            if matches!(self.ssh_args.host_key_policy, HostKeyPolicy::Insecure) {
+                EffectiveSetting::new(String::from("/dev/null"), SettingSource::Builder)
+            } else if !self.ssh_args.known_hosts_file_path.is_empty() {
+                EffectiveSetting::new(
+                    self.ssh_args.known_hosts_file_path.clone(),
+                    SettingSource::Builder,
+                )
+            } else {
+                from_config_file("userknownhostsfile")
+                    .unwrap_or_else(|| EffectiveSetting::new(String::new(), SettingSource::Default))
+            };
+
+        // ssh tries identity files passed with "-i" before any in the config file
+        let mut identity_files = vec![];
+
+        if !self.ssh_args.private_key_path.is_empty() {
+            identity_files.push(self.ssh_args.private_key_path.clone());
+        }
+
+        identity_files.extend_from_slice(host_options.get_all("identityfile"));
+
+        let identity_files_source = if !self.ssh_args.private_key_path.is_empty() {
+            SettingSource::Builder
+        } else if identity_files.is_empty() {
+            SettingSource::Default
+        } else {
+            SettingSource::ConfigFile
+        };
+
+        Ok(EffectiveSettings {
+            host_name: from_config_file("hostname").unwrap_or_else(|| {
+                EffectiveSetting::new(self.args.host.clone(), SettingSource::Builder)
+            }),
+            user,
+            port,
+            connect_timeout: timeout_setting("connecttimeout"),
+            server_alive_interval: timeout_setting("serveraliveinterval"),
+            strict_host_key_checking,
+            user_known_hosts_file,
+            identity_files: EffectiveSetting::new(identity_files, identity_files_source),
+            proxy_jump: self.effective_proxy_jump(&host_options),
+            kex_algorithms: from_config_file("kexalgorithms")
+                .unwrap_or_else(|| EffectiveSetting::new(String::new(), SettingSource::Default)),
+        })
+    }
+
+    fn effective_proxy_jump(
+        &self,
+        host_options: &ssh_config::HostOptions,
+    ) -> EffectiveSetting<String> {
+        if self.ssh_args.jump_hosts.is_empty() {
+            return host_options.get("proxyjump").map_or_else(
+                || EffectiveSetting::new(String::new(), SettingSource::Default),
+                |proxy_jump| {
+                    EffectiveSetting::new(proxy_jump.to_owned(), SettingSource::ConfigFile)
+                },
+            );
+        }
+
+        EffectiveSetting::new(
+            self.ssh_args
+                .jump_hosts
+                .iter()
+                .map(JumpHost::destination)
+                .collect::<Vec<String>>()
+                .join(","),
+            SettingSource::Builder,
+        )
+    }
+
+    fn build_open_args(&mut self) -> Result<(), ScrapliError> {
+        let effective_settings = self.effective_settings()?;
+
+        debug!("system transport effective settings: {effective_settings:?}");
+
+        // settings from the config file are left off the command line so that ssh applies them
+        // from the file itself, all other settings are passed explicitly
+        let mut open_args = vec![self.args.host.clone()];
+
+        if effective_settings.port.source != SettingSource::ConfigFile {
+            open_args.extend([
+                String::from("-p"),
+                format!("{}", effective_settings.port.value),
+            ]);
+        }
+
+        if effective_settings.connect_timeout.source != SettingSource::ConfigFile {
+            open_args.extend([
                 String::from("-o"),
-                String::from("StrictHostKeyChecking=no"),
+                format!(
+                    "ConnectTimeout={}",
+                    effective_settings.connect_timeout.value
+                ),
+            ]);
+        }
+
+        if effective_settings.server_alive_interval.source != SettingSource::ConfigFile {
+            open_args.extend([
                 String::from("-o"),
-                String::from("UserKnownHostsFile=/dev/null"),
+                format!(
+                    "ServerAliveInterval={}",
+                    effective_settings.server_alive_interval.value
+                ),
+            ]);
+        }
+
+        if effective_settings.user.source == SettingSource::Builder {
+            open_args.extend([String::from("-l"), effective_settings.user.value]);
+        }
+
+        if effective_settings.strict_host_key_checking.source != SettingSource::ConfigFile {
+            open_args.extend([
+                String::from("-o"),
+                format!(
+                    "StrictHostKeyChecking={}",
+                    effective_settings.strict_host_key_checking.value
+                ),
+            ]);
+        }
+
+        if effective_settings.user_known_hosts_file.source == SettingSource::Builder {
+            open_args.extend([
+                String::from("-o"),
+                format!(
+                    "UserKnownHostsFile={}",
+                    effective_settings.user_known_hosts_file.value
+                ),
             ]);
         }
 
         if !self.ssh_args.config_file_path.is_empty() {
-            self.system_args
-                .open_args
-                .extend([String::from("-F"), self.ssh_args.config_file_path.clone()]);
+            open_args.extend([String::from("-F"), self.ssh_args.config_file_path.clone()]);
         }
 
         if !self.ssh_args.private_key_path.is_empty() {
-            self.system_args
-                .open_args
-                .extend([String::from("-i"), self.ssh_args.private_key_path.clone()]);
+            open_args.extend([String::from("-i"), self.ssh_args.private_key_path.clone()]);
         }
 
-        if !self.system_args.extra_args.is_empty() {
-            self.system_args
-                .open_args
-                .extend(self.system_args.extra_args.clone());
-        }
+        open_args.extend(jump_host_open_args(
+            self.system_args.open_bin.as_str(),
+            &self.ssh_args.jump_hosts,
+        ));
+
+        open_args.extend(self.system_args.extra_args.clone());
+
+        self.system_args.open_args = open_args;
+
+        Ok(())
     }
 
     fn setup_reader_writer(&mut self) -> Result<(), ScrapliError> {
@@ -159,6 +555,7 @@ impl System {
             Err(err) => {
                 return Err(ScrapliError {
                     details: format!("encountered error spawning pty process, error: {err}"),
+                    kind: ErrorKind::Generic,
                 })
             }
         };
@@ -170,6 +567,7 @@ impl System {
                     details: format!(
                         "encountered error duplicated pty process file handle, error: {err}"
                     ),
+                    kind: ErrorKind::Generic,
                 })
             }
         };
@@ -186,6 +584,7 @@ impl System {
                     details: format!(
                         "failed cloning pty file handle for writer object, error: {err}"
                     ),
+                    kind: ErrorKind::Generic,
                 })
             }
         };
@@ -199,6 +598,7 @@ impl System {
                     details: format!(
                         "failed cloning pty file handle for reader object, error: {err}"
                     ),
+                    kind: ErrorKind::Generic,
                 })
             }
         };
@@ -210,12 +610,33 @@ impl System {
 
         Ok(())
     }
+
+    /// Returns the exit code of the process once it has exited, waiting up to `timeout` for it to
+    /// do so.
+    fn wait_exit_code(
+        &mut self,
+        timeout: Duration,
+    ) -> Option<i32> {
+        let deadline = Instant::now().checked_add(timeout)?;
+
+        loop {
+            if let Some(code) = self.exit_code() {
+                return Some(code);
+            }
+
+            if Instant::now() >= deadline {
+                return None;
+            }
+
+            thread::sleep(Duration::from_millis(1));
+        }
+    }
 }
 
 impl Transport for System {
     fn open(&mut self) -> Result<(), ScrapliError> {
         if self.system_args.open_args.is_empty() {
-            self.build_open_args();
+            self.build_open_args()?;
         }
 
         debug!(
@@ -233,6 +654,7 @@ impl Transport for System {
             None => {
                 return Err(ScrapliError {
                     details: String::from("trying to close transport with no process created"),
+                    kind: ErrorKind::Generic,
                 })
             }
             Some(process) => process,
@@ -242,16 +664,13 @@ impl Transport for System {
             Ok(_) => Ok(()),
             Err(err) => Err(ScrapliError {
                 details: format!("failed closing pty process, error: {err}"),
+                kind: ErrorKind::Generic,
             }),
         }
     }
 
     fn alive(&mut self) -> bool {
-        self.process.as_mut().map_or(false, |process| {
-            process.status().map_or(false, |status| {
-                matches!(status, WaitStatus::Continued(_) | WaitStatus::StillAlive)
-            })
-        })
+        self.process.is_some() && self.poll_exit_status().is_none()
     }
 
     fn read(&mut self) -> Result<Vec<u8>, ScrapliError> {
@@ -280,6 +699,7 @@ impl Transport for System {
             Err(err) => {
                 return Err(ScrapliError {
                     details: format!("error while polling fd, error: {err}"),
+                    kind: ErrorKind::Generic,
                 })
             }
         }
@@ -290,17 +710,34 @@ impl Transport for System {
             None => {
                 return Err(ScrapliError {
                     details: String::from("attempting to read from transport with no process!"),
+                    kind: ErrorKind::Generic,
                 })
             }
             Some(ref mut reader) => reader,
         };
 
-        return match reader.read(b.as_mut_slice()) {
+        let read_result = reader.read(b.as_mut_slice());
+
+        match read_result {
             Ok(read_n) => Ok(b[0..read_n].to_owned()),
-            Err(err) => Err(ScrapliError {
-                details: format!("error when reading after polling fd, error: {err}"),
-            }),
-        };
+            Err(err) => {
+                // reads fail once the process exits, in which case its exit code is what matters
+                if let Some(code) = self.wait_exit_code(PROCESS_EXIT_GRACE) {
+                    return Err(ScrapliError {
+                        details: format!(
+                            "process '{}' exited with code {code}",
+                            self.system_args.open_bin
+                        ),
+                        kind: ErrorKind::ProcessExited { code },
+                    });
+                }
+
+                Err(ScrapliError {
+                    details: format!("error when reading after polling fd, error: {err}"),
+                    kind: ErrorKind::Generic,
+                })
+            }
+        }
     }
 
     fn write(
@@ -311,6 +748,7 @@ impl Transport for System {
             None => {
                 return Err(ScrapliError {
                     details: String::from("attempting to write to transport with no process!"),
+                    kind: ErrorKind::Generic,
                 })
             }
             Some(ref mut writer) => writer,
@@ -321,6 +759,7 @@ impl Transport for System {
             Err(err) => {
                 return Err(ScrapliError {
                     details: format!("failed writing to transport, error: {err}"),
+                    kind: ErrorKind::Generic,
                 })
             }
         };
@@ -329,6 +768,7 @@ impl Transport for System {
             Ok(_) => Ok(()),
             Err(err) => Err(ScrapliError {
                 details: format!("failed flushing transport, error: {err}"),
+                kind: ErrorKind::Generic,
             }),
         }
     }
@@ -348,9 +788,12 @@ impl Transport for System {
     fn in_channel_auth_data(&self) -> InChannelAuthData {
         InChannelAuthData {
             auth_type: InChannelAuthType::SSH,
+            host: self.args.host.clone(),
             user: self.args.user.clone(),
             password: self.args.password.clone(),
             private_key_passphrase: self.ssh_args.private_key_passphrase.clone(),
+            host_key_policy: self.ssh_args.host_key_policy.clone(),
+            jump_hosts: self.ssh_args.jump_hosts.clone(),
         }
     }
 }
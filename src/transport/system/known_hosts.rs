@@ -0,0 +1,296 @@
+use super::ssh_config::pattern_list_matches;
+use crate::errors::{
+    ErrorKind,
+    ScrapliError,
+};
+use crate::transport::base::DEFAULT_PORT;
+use base64::engine::general_purpose::{
+    STANDARD,
+    STANDARD_NO_PAD,
+};
+use base64::Engine as _;
+use ring::digest::{
+    digest,
+    SHA256,
+};
+use ring::hmac;
+use std::env;
+use std::fs;
+use std::io::{
+    ErrorKind as IoErrorKind,
+    Write as _,
+};
+use std::path::PathBuf;
+
+/// The prefix of hashed host names ("|1|salt|hash") in known hosts files.
+const HASHED_HOST_PREFIX: &str = "|1|";
+
+/// `KnownHost` is a host key entry of a known hosts file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KnownHost {
+    /// The base64 encoded public key.
+    pub key: String,
+    /// The key type (ex: "ssh-ed25519").
+    pub key_type: String,
+    /// The (one indexed) line of the entry in the known hosts file.
+    pub line_number: usize,
+    /// The marker of the entry ("@cert-authority" or "@revoked"), empty if the entry is unmarked.
+    pub marker: String,
+    /// The host patterns (or the hashed host name) of the entry, as written in the file.
+    pub patterns: Vec<String>,
+}
+
+impl KnownHost {
+    /// Returns the (ssh style) fingerprint of the key, ex: "SHA256:nThbg6kXUpJWGl7E1IGOCspRomTxd".
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ScrapliError` if the key is not valid base64.
+    pub fn fingerprint(&self) -> Result<String, ScrapliError> {
+        let key = decode(self.key.as_str())?;
+
+        Ok(format!(
+            "SHA256:{}",
+            STANDARD_NO_PAD.encode(digest(&SHA256, key.as_slice()))
+        ))
+    }
+
+    /// Returns true if the entry applies to the given host name -- the host name is the host, or
+    /// "[host]:port" for any non default port, as written by ssh.
+    fn matches(
+        &self,
+        host_name: &str,
+    ) -> bool {
+        self.patterns
+            .first()
+            .and_then(|pattern| pattern.strip_prefix(HASHED_HOST_PREFIX))
+            .map_or_else(
+                || pattern_list_matches(&self.patterns, host_name),
+                // hashed hosts are "salt|hash" (both base64), the hash is the hmac-sha1 of the host
+                // name keyed with the salt
+                |hashed_host| {
+                    let Some((encoded_salt, encoded_hash)) = hashed_host.split_once('|') else {
+                        return false;
+                    };
+
+                    let (Ok(salt), Ok(hash)) =
+                        (STANDARD.decode(encoded_salt), STANDARD.decode(encoded_hash))
+                    else {
+                        return false;
+                    };
+
+                    hmac::verify(
+                        &hmac::Key::new(hmac::HMAC_SHA1_FOR_LEGACY_USE_ONLY, salt.as_slice()),
+                        host_name.as_bytes(),
+                        hash.as_slice(),
+                    )
+                    .is_ok()
+                },
+            )
+    }
+
+    /// Parses a known hosts file line, comments, blank and invalid lines yield `None`.
+    fn parse(
+        line: &str,
+        line_number: usize,
+    ) -> Option<Self> {
+        let mut fields = line.split_whitespace().peekable();
+
+        let marker = fields
+            .next_if(|field| field.starts_with('@'))
+            .unwrap_or_default();
+
+        let (Some(patterns), Some(key_type), Some(key)) =
+            (fields.next(), fields.next(), fields.next())
+        else {
+            return None;
+        };
+
+        if patterns.starts_with('#') {
+            return None;
+        }
+
+        Some(Self {
+            key: key.to_owned(),
+            key_type: key_type.to_owned(),
+            line_number,
+            marker: marker.to_owned(),
+            patterns: patterns.split(',').map(str::to_owned).collect(),
+        })
+    }
+}
+
+/// Returns the path of the known hosts file -- the given path, or ssh's default of
+/// `~/.ssh/known_hosts` if the path is empty (as with an unset `known_hosts_file_path`).
+fn known_hosts_path(path: &str) -> Result<PathBuf, ScrapliError> {
+    if !path.is_empty() {
+        return Ok(PathBuf::from(path));
+    }
+
+    env::var("HOME")
+        .map(|home| PathBuf::from(home).join(".ssh").join("known_hosts"))
+        .map_err(|err| ScrapliError {
+            details: format!(
+                "no known hosts file path set and failed determining home directory, error: {err}"
+            ),
+            kind: ErrorKind::Generic,
+        })
+}
+
+/// Reads the known hosts file, a missing file has no entries.
+fn read_known_hosts(path: &PathBuf) -> Result<String, ScrapliError> {
+    match fs::read_to_string(path) {
+        Ok(content) => Ok(content),
+        Err(err) if err.kind() == IoErrorKind::NotFound => Ok(String::new()),
+        Err(err) => Err(ScrapliError {
+            details: format!(
+                "failed reading known hosts file '{}', error: {err}",
+                path.display()
+            ),
+            kind: ErrorKind::Generic,
+        }),
+    }
+}
+
+/// Returns the host name as ssh writes it to (and looks it up in) known hosts files -- the host for
+/// the default port, "[host]:port" otherwise.
+fn host_name(
+    host: &str,
+    port: u16,
+) -> String {
+    if port == DEFAULT_PORT {
+        host.to_owned()
+    } else {
+        format!("[{host}]:{port}")
+    }
+}
+
+fn decode(encoded: &str) -> Result<Vec<u8>, ScrapliError> {
+    STANDARD.decode(encoded).map_err(|err| ScrapliError {
+        details: format!("invalid base64 in known hosts entry, error: {err}"),
+        kind: ErrorKind::Generic,
+    })
+}
+
+/// Returns the entries of the known hosts file at `path` that apply to `host` on `port`.
+///
+/// Plain, wildcard and hashed host entries are all considered. An empty `path` means ssh's default
+/// `~/.ssh/known_hosts`, so the `known_hosts_file_path` ssh argument can be passed as is. A missing
+/// file has no entries.
+///
+/// # Errors
+///
+/// Returns a `ScrapliError` if the file cannot be read.
+pub fn lookup(
+    path: &str,
+    host: &str,
+    port: u16,
+) -> Result<Vec<KnownHost>, ScrapliError> {
+    let content = read_known_hosts(&known_hosts_path(path)?)?;
+    let host_name = host_name(host, port);
+
+    Ok(content
+        .lines()
+        .zip(1..)
+        .filter_map(|(line, line_number)| KnownHost::parse(line, line_number))
+        .filter(|known_host| known_host.matches(host_name.as_str()))
+        .collect())
+}
+
+/// Adds the key of `host` on `port` to the known hosts file at `path`.
+///
+/// The key is of `key_type` and base64 encoded, as shown by `ssh-keyscan`. As with `lookup` an
+/// empty `path` means ssh's default `~/.ssh/known_hosts`, the file is created if it does not
+/// exist. Existing entries of the host are left in place, `remove` them first to replace a changed
+/// key.
+///
+/// # Errors
+///
+/// Returns a `ScrapliError` if the key is not valid base64 or if the file cannot be written.
+pub fn add(
+    path: &str,
+    host: &str,
+    port: u16,
+    key_type: &str,
+    key: &str,
+) -> Result<(), ScrapliError> {
+    decode(key)?;
+
+    let known_hosts_file = known_hosts_path(path)?;
+    let content = read_known_hosts(&known_hosts_file)?;
+
+    let separator = if content.is_empty() || content.ends_with('\n') {
+        ""
+    } else {
+        "\n"
+    };
+
+    fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&known_hosts_file)
+        .and_then(|mut file| {
+            writeln!(
+                file,
+                "{separator}{} {key_type} {key}",
+                host_name(host, port)
+            )
+        })
+        .map_err(|err| ScrapliError {
+            details: format!(
+                "failed writing known hosts file '{}', error: {err}",
+                known_hosts_file.display()
+            ),
+            kind: ErrorKind::Generic,
+        })
+}
+
+/// Removes all entries of `host` on `port` from the known hosts file at `path`, returns the number
+/// of removed entries.
+///
+/// As with `lookup` an empty `path` means ssh's default `~/.ssh/known_hosts`. As with
+/// `ssh-keygen -R`, entries listing other hosts besides `host` are removed entirely, and
+/// "@cert-authority" and "@revoked" entries are kept.
+///
+/// # Errors
+///
+/// Returns a `ScrapliError` if the file cannot be read or written.
+pub fn remove(
+    path: &str,
+    host: &str,
+    port: u16,
+) -> Result<usize, ScrapliError> {
+    let known_hosts_file = known_hosts_path(path)?;
+    let content = read_known_hosts(&known_hosts_file)?;
+    let host_name = host_name(host, port);
+
+    let mut removed = 0;
+    let mut kept = String::with_capacity(content.len());
+
+    for (line, line_number) in content.lines().zip(1..) {
+        if KnownHost::parse(line, line_number).is_some_and(|known_host| {
+            known_host.marker.is_empty() && known_host.matches(host_name.as_str())
+        }) {
+            removed += 1;
+
+            continue;
+        }
+
+        kept.push_str(line);
+        kept.push('\n');
+    }
+
+    if removed == 0 {
+        return Ok(0);
+    }
+
+    fs::write(&known_hosts_file, kept).map_err(|err| ScrapliError {
+        details: format!(
+            "failed writing known hosts file '{}', error: {err}",
+            known_hosts_file.display()
+        ),
+        kind: ErrorKind::Generic,
+    })?;
+
+    Ok(removed)
+}
@@ -0,0 +1,426 @@
+extern crate alloc;
+
+use crate::errors::{
+    ErrorKind,
+    ScrapliError,
+};
+use alloc::collections::BTreeMap;
+use core::mem;
+use log::debug;
+use std::env;
+use std::fs;
+
+/// Keywords that may be set multiple times with all values being used (rather than the first
+/// obtained value winning).
+const ACCUMULATING_KEYWORDS: [&str; 7] = [
+    "certificatefile",
+    "dynamicforward",
+    "identityfile",
+    "localforward",
+    "remoteforward",
+    "sendenv",
+    "setenv",
+];
+
+/// A single criterion of a `Match` block, ex: `host "*.lab"` or `!user admin`.
+struct MatchCriterion {
+    keyword: String,
+    negated: bool,
+    patterns: Vec<String>,
+}
+
+/// A block of options in an ssh config -- options set before any `Host` or `Match` line are in a
+/// block without host patterns or match criteria, and apply to all hosts.
+struct Block {
+    /// The (possibly negated) host patterns of a `Host` block.
+    host_patterns: Option<Vec<String>>,
+    /// The criteria of a `Match` block, all criteria must match for the block to apply.
+    match_criteria: Option<Vec<MatchCriterion>>,
+    options: Vec<(String, Vec<String>)>,
+}
+
+/// `Config` is a parsed ssh config file (as passed to ssh with "-F").
+///
+/// Only the parts of ssh config that scraplirs cares about are evaluated -- `Host` and `Match`
+/// blocks (`all`, `host`, `originalhost`, `user`, `localuser`, `canonical` and `final` criteria)
+/// and the options in them.
+/// `Include` directives and `Match` criteria that require executing commands are ignored.
+pub struct Config {
+    blocks: Vec<Block>,
+}
+
+/// `HostOptions` holds the options of a `Config` that apply to a given host.
+///
+/// Keywords are lower cased. As with ssh itself, the first obtained value for a keyword wins, with
+/// the exception of keywords like `IdentityFile` which accumulate all values.
+#[derive(Debug, Clone, Default)]
+pub struct HostOptions {
+    /// The resolved options, keyed by lower cased keyword.
+    pub options: BTreeMap<String, Vec<String>>,
+}
+
+impl HostOptions {
+    /// Returns the (first) value of the `keyword` option, if set.
+    #[must_use]
+    pub fn get(
+        &self,
+        keyword: &str,
+    ) -> Option<&str> {
+        self.options
+            .get(keyword.to_lowercase().as_str())
+            .and_then(|values| values.first())
+            .map(String::as_str)
+    }
+
+    /// Returns all values of the `keyword` option, empty if the option is not set.
+    #[must_use]
+    pub fn get_all(
+        &self,
+        keyword: &str,
+    ) -> &[String] {
+        self.options
+            .get(keyword.to_lowercase().as_str())
+            .map_or(&[], Vec::as_slice)
+    }
+
+    fn insert(
+        &mut self,
+        keyword: &str,
+        values: &[String],
+    ) {
+        if ACCUMULATING_KEYWORDS.contains(&keyword) {
+            self.options
+                .entry(keyword.to_owned())
+                .or_default()
+                .extend_from_slice(values);
+        } else {
+            self.options
+                .entry(keyword.to_owned())
+                .or_insert_with(|| values.to_vec());
+        }
+    }
+}
+
+impl Config {
+    /// Reads and parses the ssh config file at `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ScrapliError` if the file cannot be read or is invalid.
+    pub fn from_file(path: &str) -> Result<Self, ScrapliError> {
+        match fs::read_to_string(path) {
+            Ok(config) => Self::parse(config.as_str()),
+            Err(err) => Err(ScrapliError {
+                details: format!("failed reading ssh config file '{path}', error: {err}"),
+                kind: ErrorKind::Generic,
+            }),
+        }
+    }
+
+    /// Parses the ssh config in the string `config`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ScrapliError` if the config is invalid -- for example a keyword is missing its
+    /// arguments or a `Match` criterion is missing its patterns.
+    pub fn parse(config: &str) -> Result<Self, ScrapliError> {
+        let mut blocks = vec![Block {
+            host_patterns: None,
+            match_criteria: None,
+            options: vec![],
+        }];
+
+        for (line_idx, raw_line) in config.lines().enumerate() {
+            let line = raw_line.trim();
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (keyword, args) = split_line(line);
+
+            if args.is_empty() {
+                return Err(ScrapliError {
+                    details: format!(
+                        "invalid ssh config, keyword '{keyword}' on line {} has no arguments",
+                        line_idx + 1
+                    ),
+                    kind: ErrorKind::Generic,
+                });
+            }
+
+            match keyword.as_str() {
+                "host" => blocks.push(Block {
+                    host_patterns: Some(args),
+                    match_criteria: None,
+                    options: vec![],
+                }),
+                "match" => blocks.push(Block {
+                    host_patterns: None,
+                    match_criteria: Some(parse_match_criteria(&args, line_idx + 1)?),
+                    options: vec![],
+                }),
+                "include" => {
+                    debug!(
+                        "ignoring ssh config include directive on line {}, includes are not \
+                        evaluated",
+                        line_idx + 1
+                    );
+                }
+                _ => {
+                    if let Some(block) = blocks.last_mut() {
+                        block.options.push((keyword, args));
+                    }
+                }
+            }
+        }
+
+        Ok(Self { blocks })
+    }
+
+    /// Returns the `HostOptions` that apply when connecting to `host` (as given on the command
+    /// line, so possibly an alias) as `user` -- if `user` is empty any "User" set in the config (or
+    /// the local user) is used when evaluating `Match user` criteria.
+    #[must_use]
+    pub fn resolve(
+        &self,
+        host: &str,
+        user: &str,
+    ) -> HostOptions {
+        let local_user = env::var("USER").unwrap_or_default();
+
+        let mut host_options = HostOptions::default();
+
+        for block in &self.blocks {
+            let host_name = host_options.get("hostname").map_or_else(
+                || host.to_owned(),
+                |host_name| host_name.replace("%h", host),
+            );
+
+            let match_user = if user.is_empty() {
+                host_options
+                    .get("user")
+                    .unwrap_or(local_user.as_str())
+                    .to_owned()
+            } else {
+                user.to_owned()
+            };
+
+            let applies = block
+                .host_patterns
+                .as_ref()
+                .is_none_or(|patterns| pattern_list_matches(patterns, host))
+                && block.match_criteria.as_ref().is_none_or(|criteria| {
+                    criteria.iter().all(|criterion| {
+                        criterion_matches(
+                            criterion,
+                            host,
+                            host_name.as_str(),
+                            match_user.as_str(),
+                            local_user.as_str(),
+                        )
+                    })
+                });
+
+            if !applies {
+                continue;
+            }
+
+            for option in &block.options {
+                host_options.insert(&option.0, &option.1);
+            }
+        }
+
+        if let Some(host_name) = host_options.options.get_mut("hostname") {
+            for value in host_name.iter_mut() {
+                *value = value.replace("%h", host);
+            }
+        }
+
+        host_options
+    }
+}
+
+/// Splits a config line into its (lower cased) keyword and arguments -- keywords may be separated
+/// from arguments by whitespace or "=", arguments may be double-quoted.
+#[expect(
+    clippy::single_call_fn,
+    reason = "keeps line splitting out of the parse loop"
+)]
+fn split_line(line: &str) -> (String, Vec<String>) {
+    let keyword_end = line
+        .find(|character: char| character.is_whitespace() || character == '=')
+        .unwrap_or(line.len());
+
+    let (keyword, rest) = line.split_at(keyword_end);
+
+    let trimmed_rest = rest.trim_start();
+    let args_str = trimmed_rest.strip_prefix('=').unwrap_or(trimmed_rest);
+
+    let mut args = vec![];
+    let mut current_arg = String::new();
+    let mut in_quotes = false;
+
+    for character in args_str.chars() {
+        match character {
+            '"' => in_quotes = !in_quotes,
+            _ if character.is_whitespace() && !in_quotes => {
+                if !current_arg.is_empty() {
+                    args.push(mem::take(&mut current_arg));
+                }
+            }
+            _ => current_arg.push(character),
+        }
+    }
+
+    if !current_arg.is_empty() {
+        args.push(current_arg);
+    }
+
+    (keyword.to_lowercase(), args)
+}
+
+#[expect(
+    clippy::single_call_fn,
+    reason = "keeps match criteria parsing out of the parse loop"
+)]
+fn parse_match_criteria(
+    args: &[String],
+    line_number: usize,
+) -> Result<Vec<MatchCriterion>, ScrapliError> {
+    let mut criteria = vec![];
+    let mut args_iter = args.iter();
+
+    while let Some(arg) = args_iter.next() {
+        let lowered_arg = arg.to_lowercase();
+
+        let (negated, keyword) = lowered_arg
+            .strip_prefix('!')
+            .map_or((false, lowered_arg.as_str()), |keyword| (true, keyword));
+
+        let patterns = match keyword {
+            "all" | "canonical" | "final" => vec![],
+            _ => {
+                let Some(patterns) = args_iter.next() else {
+                    return Err(ScrapliError {
+                        details: format!(
+                            "invalid ssh config, match criterion '{keyword}' on line \
+                            {line_number} has no arguments"
+                        ),
+                        kind: ErrorKind::Generic,
+                    });
+                };
+
+                patterns.split(',').map(str::to_owned).collect()
+            }
+        };
+
+        criteria.push(MatchCriterion {
+            keyword: keyword.to_owned(),
+            negated,
+            patterns,
+        });
+    }
+
+    Ok(criteria)
+}
+
+#[expect(
+    clippy::single_call_fn,
+    reason = "keeps criterion matching out of the block resolution loop"
+)]
+fn criterion_matches(
+    criterion: &MatchCriterion,
+    original_host: &str,
+    host_name: &str,
+    user: &str,
+    local_user: &str,
+) -> bool {
+    let matched = match criterion.keyword.as_str() {
+        "all" | "final" => true,
+        // scraplirs does not canonicalize host names, so canonical blocks never apply
+        "canonical" => false,
+        "host" => pattern_list_matches(&criterion.patterns, host_name),
+        "originalhost" => pattern_list_matches(&criterion.patterns, original_host),
+        "user" => pattern_list_matches(&criterion.patterns, user),
+        "localuser" => pattern_list_matches(&criterion.patterns, local_user),
+        unsupported_keyword => {
+            debug!(
+                "ssh config match criterion '{unsupported_keyword}' is not supported, treating \
+                as not matching"
+            );
+
+            return false;
+        }
+    };
+
+    matched != criterion.negated
+}
+
+/// Returns true if `value` matches any pattern in `patterns` and none of the negated ("!")
+/// patterns.
+#[expect(
+    clippy::pub_with_shorthand,
+    reason = "rustfmt rewrites `pub(in super)` to the shorthand form"
+)]
+pub(super) fn pattern_list_matches(
+    patterns: &[String],
+    value: &str,
+) -> bool {
+    let mut matched = false;
+
+    for pattern in patterns {
+        match pattern.strip_prefix('!') {
+            Some(negated_pattern) => {
+                if pattern_matches(negated_pattern, value) {
+                    return false;
+                }
+            }
+            None => matched = matched || pattern_matches(pattern, value),
+        }
+    }
+
+    matched
+}
+
+/// Case insensitive glob match supporting the "*" and "?" wildcards as used in ssh config.
+fn pattern_matches(
+    pattern: &str,
+    value: &str,
+) -> bool {
+    let pattern_chars = pattern.to_lowercase().chars().collect::<Vec<char>>();
+    let value_chars = value.to_lowercase().chars().collect::<Vec<char>>();
+
+    let mut pattern_idx = 0;
+    let mut value_idx = 0;
+    let mut last_star: Option<(usize, usize)> = None;
+
+    while value_idx < value_chars.len() {
+        match pattern_chars.get(pattern_idx) {
+            Some(&'*') => {
+                last_star = Some((pattern_idx, value_idx));
+                pattern_idx += 1;
+            }
+            Some(&pattern_char)
+                if pattern_char == '?' || value_chars.get(value_idx) == Some(&pattern_char) =>
+            {
+                pattern_idx += 1;
+                value_idx += 1;
+            }
+            _ => {
+                // backtrack, letting the last star consume one more char
+                let Some((star_pattern_idx, star_value_idx)) = last_star else {
+                    return false;
+                };
+
+                pattern_idx = star_pattern_idx + 1;
+                value_idx = star_value_idx + 1;
+                last_star = Some((star_pattern_idx, value_idx));
+            }
+        }
+    }
+
+    pattern_chars
+        .get(pattern_idx..)
+        .is_some_and(|remaining| remaining.iter().all(|&character| character == '*'))
+}
@@ -0,0 +1,522 @@
+extern crate alloc;
+
+use crate::credentials::Credential;
+use crate::errors::{
+    ErrorKind,
+    ScrapliError,
+};
+use crate::transport::base::{
+    InChannelAuthData,
+    InChannelAuthType,
+    Transport,
+    TransportArgs,
+    DEFAULT_SSH_HOST_KEY_POLICY,
+};
+use alloc::sync::Arc;
+use core::time::Duration;
+use log::debug;
+use rustls::client::danger::{
+    HandshakeSignatureValid,
+    ServerCertVerified,
+    ServerCertVerifier,
+};
+use rustls::crypto::{
+    ring,
+    verify_tls12_signature,
+    verify_tls13_signature,
+    CryptoProvider,
+};
+use rustls::pki_types::pem::PemObject as _;
+use rustls::pki_types::{
+    CertificateDer,
+    ServerName,
+    UnixTime,
+};
+use rustls::{
+    ClientConfig,
+    ClientConnection,
+    DigitallySignedStruct,
+    RootCertStore,
+    SignatureScheme,
+    Stream,
+};
+use std::io::{
+    ErrorKind as IoErrorKind,
+    Read,
+    Result as IoResult,
+    Write,
+};
+use std::net::{
+    Shutdown,
+    TcpStream,
+    ToSocketAddrs as _,
+};
+
+/// The default for how long a single read of a `Tcp` transport waits for data before returning
+/// nothing.
+pub const DEFAULT_TCP_READ_POLL_INTERVAL: Duration = Duration::from_millis(5);
+
+/// A struct holding arguments specific to the `Tcp` transport implementation.
+#[expect(
+    clippy::module_name_repetitions,
+    reason = "the type names stay meaningful when imported outside of the module"
+)]
+pub struct TcpArgs {
+    /// How long a single read waits for data before returning nothing. The channel read loop holds
+    /// the transport while reading, so longer intervals mean fewer wake ups of an idle connection
+    /// but delay writes by up to the interval.
+    pub read_poll_interval: Duration,
+    /// Indicates if the connection should be wrapped in TLS.
+    pub tls: bool,
+    /// Path to a PEM file of CA certificates to trust, if empty the bundled webpki (Mozilla) roots
+    /// are trusted.
+    pub tls_ca_file: String,
+    /// Disables verification of the server certificate -- only for labs with self-signed certs!
+    pub tls_insecure: bool,
+    /// The server name used for SNI and certificate verification, if empty the `host` of the
+    /// transport args is used.
+    pub tls_server_name: String,
+}
+
+impl Default for TcpArgs {
+    fn default() -> Self {
+        Self {
+            read_poll_interval: DEFAULT_TCP_READ_POLL_INTERVAL,
+            tls: false,
+            tls_ca_file: String::new(),
+            tls_insecure: false,
+            tls_server_name: String::new(),
+        }
+    }
+}
+
+/// The underlying stream of a `Tcp` transport -- the socket, and the TLS session wrapping it if
+/// TLS is enabled.
+struct TcpConnection {
+    socket: TcpStream,
+    tls: Option<ClientConnection>,
+}
+
+impl Read for TcpConnection {
+    fn read(
+        &mut self,
+        buf: &mut [u8],
+    ) -> IoResult<usize> {
+        match self.tls.as_mut() {
+            Some(tls) => Stream::new(tls, &mut self.socket).read(buf),
+            None => self.socket.read(buf),
+        }
+    }
+}
+
+impl Write for TcpConnection {
+    fn flush(&mut self) -> IoResult<()> {
+        match self.tls.as_mut() {
+            Some(tls) => Stream::new(tls, &mut self.socket).flush(),
+            None => self.socket.flush(),
+        }
+    }
+
+    fn write(
+        &mut self,
+        buf: &[u8],
+    ) -> IoResult<usize> {
+        match self.tls.as_mut() {
+            Some(tls) => Stream::new(tls, &mut self.socket).write(buf),
+            None => self.socket.write(buf),
+        }
+    }
+}
+
+/// A `ServerCertVerifier` that accepts any server certificate, used when `tls_insecure` is set.
+/// Handshake signatures are still checked so the peer must at least hold the certificate's key.
+#[derive(Debug)]
+struct InsecureServerCertVerifier {
+    provider: Arc<CryptoProvider>,
+}
+
+impl ServerCertVerifier for InsecureServerCertVerifier {
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.provider
+            .signature_verification_algorithms
+            .supported_schemes()
+    }
+
+    fn verify_server_cert(
+        &self,
+        _end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        _server_name: &ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        verify_tls12_signature(
+            message,
+            cert,
+            dss,
+            &self.provider.signature_verification_algorithms,
+        )
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        verify_tls13_signature(
+            message,
+            cert,
+            dss,
+            &self.provider.signature_verification_algorithms,
+        )
+    }
+}
+
+/// The raw TCP transport object -- for already authenticated streams, optionally wrapped in TLS.
+///
+/// Useful for terminal servers exposing consoles on raw TCP ports, device simulators, or anything
+/// else speaking a plain CLI over a socket.
+///
+/// Tcp transports do no in channel authentication, the channel simply waits for the prompt.
+pub struct Tcp {
+    args: TransportArgs,
+    connection: Option<TcpConnection>,
+    connection_args: TcpArgs,
+}
+
+impl Tcp {
+    /// Connects to the first reachable address the host resolves to, each attempt bounded by
+    /// `timeout_socket`.
+    fn connect(&self) -> Result<TcpStream, ScrapliError> {
+        let addrs = match (self.args.host.as_str(), self.args.port).to_socket_addrs() {
+            Ok(addrs) => addrs,
+            Err(err) => {
+                return Err(ScrapliError {
+                    details: format!("failed resolving host '{}', error: {err}", self.args.host),
+                    kind: ErrorKind::Generic,
+                })
+            }
+        };
+
+        let mut last_err = None;
+
+        for addr in addrs {
+            debug!("attempting tcp connection to {addr}");
+
+            match TcpStream::connect_timeout(&addr, self.args.timeout_socket) {
+                Ok(stream) => return Ok(stream),
+                Err(err) => last_err = Some(err),
+            }
+        }
+
+        Err(ScrapliError {
+            details: last_err.map_or_else(
+                || format!("host '{}' did not resolve to any addresses", self.args.host),
+                |err| {
+                    format!(
+                        "failed connecting to {}:{}, error: {err}",
+                        self.args.host, self.args.port
+                    )
+                },
+            ),
+            kind: ErrorKind::Generic,
+        })
+    }
+
+    fn connection(&mut self) -> Result<&mut TcpConnection, ScrapliError> {
+        self.connection.as_mut().ok_or_else(|| ScrapliError {
+            details: String::from("attempting to use tcp transport with no open connection!"),
+            kind: ErrorKind::Generic,
+        })
+    }
+
+    fn load_ca_file(
+        &self,
+        roots: &mut RootCertStore,
+    ) -> Result<(), ScrapliError> {
+        let ca_file = self.connection_args.tls_ca_file.as_str();
+
+        let certs = match CertificateDer::pem_file_iter(ca_file) {
+            Ok(certs) => certs,
+            Err(err) => {
+                return Err(ScrapliError {
+                    details: format!("failed reading tls ca file '{ca_file}', error: {err}"),
+                    kind: ErrorKind::Generic,
+                })
+            }
+        };
+
+        for cert in certs {
+            let added = cert
+                .map_err(|err| err.to_string())
+                .and_then(|der| roots.add(der).map_err(|err| err.to_string()));
+
+            if let Err(err) = added {
+                return Err(ScrapliError {
+                    details: format!("failed loading tls ca file '{ca_file}', error: {err}"),
+                    kind: ErrorKind::Generic,
+                });
+            }
+        }
+
+        if roots.is_empty() {
+            return Err(ScrapliError {
+                details: format!("tls ca file '{ca_file}' contains no certificates"),
+                kind: ErrorKind::Generic,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Returns a new `Tcp` instance.
+    #[must_use]
+    pub const fn new(
+        args: TransportArgs,
+        connection_args: TcpArgs,
+    ) -> Self {
+        Self {
+            args,
+            connection: None,
+            connection_args,
+        }
+    }
+
+    /// Returns the tls client config built from the tls settings of the tcp args.
+    fn tls_config(&self) -> Result<ClientConfig, ScrapliError> {
+        let provider = Arc::new(ring::default_provider());
+
+        let builder = match ClientConfig::builder_with_provider(Arc::clone(&provider))
+            .with_safe_default_protocol_versions()
+        {
+            Ok(builder) => builder,
+            Err(err) => {
+                return Err(ScrapliError {
+                    details: format!("failed building tls config, error: {err}"),
+                    kind: ErrorKind::Generic,
+                })
+            }
+        };
+
+        if self.connection_args.tls_insecure {
+            return Ok(builder
+                .dangerous()
+                .with_custom_certificate_verifier(Arc::new(InsecureServerCertVerifier { provider }))
+                .with_no_client_auth());
+        }
+
+        let mut roots = RootCertStore::empty();
+
+        if self.connection_args.tls_ca_file.is_empty() {
+            roots.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
+        } else {
+            self.load_ca_file(&mut roots)?;
+        }
+
+        Ok(builder.with_root_certificates(roots).with_no_client_auth())
+    }
+
+    /// Wraps the connected socket in tls, completing the handshake (bounded by `timeout_socket`).
+    fn wrap_tls(
+        &self,
+        mut socket: TcpStream,
+    ) -> Result<TcpConnection, ScrapliError> {
+        let raw_server_name = if self.connection_args.tls_server_name.is_empty() {
+            self.args.host.clone()
+        } else {
+            self.connection_args.tls_server_name.clone()
+        };
+
+        let server_name = match ServerName::try_from(raw_server_name) {
+            Ok(server_name) => server_name,
+            Err(err) => {
+                return Err(ScrapliError {
+                    details: format!("invalid tls server name, error: {err}"),
+                    kind: ErrorKind::Generic,
+                })
+            }
+        };
+
+        let mut client = match ClientConnection::new(Arc::new(self.tls_config()?), server_name) {
+            Ok(client) => client,
+            Err(err) => {
+                return Err(ScrapliError {
+                    details: format!("failed creating tls connection, error: {err}"),
+                    kind: ErrorKind::Generic,
+                })
+            }
+        };
+
+        set_read_timeout(&socket, self.args.timeout_socket)?;
+
+        while client.is_handshaking() {
+            if let Err(err) = client.complete_io(&mut socket) {
+                return Err(ScrapliError {
+                    details: format!("failed completing tls handshake, error: {err}"),
+                    kind: ErrorKind::Generic,
+                });
+            }
+        }
+
+        Ok(TcpConnection {
+            socket,
+            tls: Some(client),
+        })
+    }
+}
+
+impl Transport for Tcp {
+    fn alive(&mut self) -> bool {
+        self.connection.is_some()
+    }
+
+    fn close(&mut self) -> Result<(), ScrapliError> {
+        let Some(mut connection) = self.connection.take() else {
+            return Err(ScrapliError {
+                details: String::from("trying to close tcp transport with no open connection"),
+                kind: ErrorKind::Generic,
+            });
+        };
+
+        if let Some(tls) = connection.tls.as_mut() {
+            tls.send_close_notify();
+
+            if let Err(err) = Stream::new(tls, &mut connection.socket).flush() {
+                debug!("failed sending tls close notify, error: {err}");
+            }
+        }
+
+        if let Err(err) = connection.socket.shutdown(Shutdown::Both) {
+            debug!("failed shutting down tcp connection, error: {err}");
+        }
+
+        Ok(())
+    }
+
+    fn get_host(&self) -> String {
+        self.args.host.clone()
+    }
+
+    fn get_port(&self) -> u16 {
+        self.args.port
+    }
+
+    fn get_transport_args(self) -> TransportArgs {
+        self.args
+    }
+
+    fn in_channel_auth_data(&self) -> InChannelAuthData {
+        InChannelAuthData {
+            auth_type: InChannelAuthType::None,
+            host: self.args.host.clone(),
+            user: self.args.user.clone(),
+            password: self.args.password.clone(),
+            private_key_passphrase: Credential::default(),
+            host_key_policy: DEFAULT_SSH_HOST_KEY_POLICY,
+            jump_hosts: vec![],
+        }
+    }
+
+    fn open(&mut self) -> Result<(), ScrapliError> {
+        debug!(
+            "opening tcp transport to {}:{}, tls: {}",
+            self.args.host, self.args.port, self.connection_args.tls
+        );
+
+        let socket = self.connect()?;
+
+        if let Err(err) = socket.set_nodelay(true) {
+            debug!("failed setting tcp nodelay, error: {err}");
+        }
+
+        let connection = if self.connection_args.tls {
+            self.wrap_tls(socket)?
+        } else {
+            TcpConnection { socket, tls: None }
+        };
+
+        // from here on reads only briefly wait for data so they never block the read loop
+        set_read_timeout(&connection.socket, self.connection_args.read_poll_interval)?;
+
+        self.connection = Some(connection);
+
+        Ok(())
+    }
+
+    fn read(&mut self) -> Result<Vec<u8>, ScrapliError> {
+        self.read_n(self.args.read_size)
+    }
+
+    /// Read up to `n` bytes from the transport.
+    fn read_n(
+        &mut self,
+        n: u16,
+    ) -> Result<Vec<u8>, ScrapliError> {
+        let connection = self.connection()?;
+
+        let mut buf = vec![0_u8; usize::from(n)];
+
+        match connection.read(buf.as_mut_slice()) {
+            Ok(0) => Err(ScrapliError {
+                details: String::from("tcp connection closed by remote"),
+                kind: ErrorKind::Generic,
+            }),
+            Ok(read_n) => {
+                buf.truncate(read_n);
+
+                Ok(buf)
+            }
+            Err(err) if matches!(err.kind(), IoErrorKind::WouldBlock | IoErrorKind::TimedOut) => {
+                Ok(vec![])
+            }
+            Err(err) => Err(ScrapliError {
+                details: format!("error when reading from tcp connection, error: {err}"),
+                kind: ErrorKind::Generic,
+            }),
+        }
+    }
+
+    fn write(
+        &mut self,
+        b: &[u8],
+    ) -> Result<(), ScrapliError> {
+        let connection = self.connection()?;
+
+        if let Err(err) = connection.write_all(b) {
+            return Err(ScrapliError {
+                details: format!("failed writing to tcp connection, error: {err}"),
+                kind: ErrorKind::Generic,
+            });
+        }
+
+        match connection.flush() {
+            Ok(()) => Ok(()),
+            Err(err) => Err(ScrapliError {
+                details: format!("failed flushing tcp connection, error: {err}"),
+                kind: ErrorKind::Generic,
+            }),
+        }
+    }
+}
+
+fn set_read_timeout(
+    socket: &TcpStream,
+    timeout: Duration,
+) -> Result<(), ScrapliError> {
+    socket
+        .set_read_timeout(Some(timeout))
+        .map_err(|err| ScrapliError {
+            details: format!("failed setting tcp read timeout, error: {err}"),
+            kind: ErrorKind::Generic,
+        })
+}
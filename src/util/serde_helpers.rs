@@ -0,0 +1,87 @@
+/// (De)serializes an optional `Duration` as a human readable duration string (ex: "1m 30s").
+pub mod option_duration {
+    use core::time::Duration;
+    use serde::de::Error as DeError;
+    use serde::{
+        Deserialize,
+        Deserializer,
+        Serializer,
+    };
+
+    /// Serializes an optional `Duration` as a human readable duration string.
+    ///
+    /// # Errors
+    ///
+    /// Returns the serializer's error if serializing fails.
+    #[allow(clippy::ref_option)]
+    pub fn serialize<S: Serializer>(
+        d: &Option<Duration>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match d {
+            Some(duration) => {
+                serializer.serialize_some(&humantime::format_duration(*duration).to_string())
+            }
+            None => serializer.serialize_none(),
+        }
+    }
+
+    /// Deserializes an optional `Duration` from a human readable duration string.
+    ///
+    /// # Errors
+    ///
+    /// Returns the deserializer's error if the value is not a valid duration string.
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D
+    ) -> Result<Option<Duration>, D::Error> {
+        let value = Option::<String>::deserialize(deserializer)?;
+
+        value
+            .map(|s| humantime::parse_duration(s.as_str()))
+            .transpose()
+            .map_err(DeError::custom)
+    }
+}
+
+/// (De)serializes an optional (bytes) `Regex` as its pattern string.
+pub mod option_regex {
+    use regex::bytes::Regex;
+    use serde::de::Error as DeError;
+    use serde::{
+        Deserialize,
+        Deserializer,
+        Serializer,
+    };
+
+    /// Serializes an optional `Regex` as its pattern string.
+    ///
+    /// # Errors
+    ///
+    /// Returns the serializer's error if serializing fails.
+    #[allow(clippy::ref_option)]
+    pub fn serialize<S: Serializer>(
+        r: &Option<Regex>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match r {
+            Some(regex) => serializer.serialize_some(regex.as_str()),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    /// Deserializes an optional `Regex` from a pattern string.
+    ///
+    /// # Errors
+    ///
+    /// Returns the deserializer's error if the value is not a valid regex pattern.
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D
+    ) -> Result<Option<Regex>, D::Error> {
+        let value = Option::<String>::deserialize(deserializer)?;
+
+        value
+            .map(|s| Regex::new(s.as_str()))
+            .transpose()
+            .map_err(DeError::custom)
+    }
+}
@@ -12,16 +12,3 @@ pub fn string_contains_any_substring(
 
     false
 }
-
-pub fn string_vec_contains_substring(
-    sv: Vec<String>,
-    s: &str,
-) -> bool {
-    for ss in sv {
-        if ss == s {
-            return true;
-        }
-    }
-
-    false
-}
@@ -0,0 +1,151 @@
+mod common;
+
+use common::MockTransport;
+use regex::bytes::Regex;
+use scraplirs::channel::{
+    AuthResponder,
+    AuthResponseSource,
+};
+use scraplirs::driver::GenericDriver;
+use scraplirs::driver::GenericDriverBuilder;
+use scraplirs::errors::{
+    ErrorKind,
+    ScrapliError,
+};
+use std::sync::{
+    Arc,
+    Mutex,
+};
+use std::time::Duration;
+
+/// Returns a driver for a telnet-ish mock device asking for a username, a password and then a
+/// verification code (which must be "123456"), and the log of every line written to it.
+fn otp_driver(responders: Vec<AuthResponder>) -> (GenericDriver, Arc<Mutex<Vec<String>>>) {
+    let inputs = Arc::new(Mutex::new(vec![]));
+    let transport_inputs = Arc::clone(&inputs);
+
+    let driver = GenericDriverBuilder::new("router")
+        .timeout_ops(Duration::from_secs(5))
+        .read_delay(Duration::from_millis(1))
+        .auth_responders(responders)
+        .transport_factory(move |args| {
+            MockTransport::new(
+                args,
+                "Username: ",
+                Box::new(|line| {
+                    match line {
+                        "admin" => "\r\nPassword: ",
+                        "secret" => "\r\nVerification code: ",
+                        "123456" => "\r\nrouter#",
+                        _ => "\r\nLogin incorrect\r\n",
+                    }
+                    .to_owned()
+                }),
+                transport_inputs,
+            )
+            .telnet_auth("admin", "secret")
+        })
+        .build();
+
+    (driver, inputs)
+}
+
+fn verification_code_responder(source: AuthResponseSource) -> AuthResponder {
+    AuthResponder::new(Regex::new(r"(?i)verification code:\s*$").unwrap(), source)
+}
+
+#[test]
+fn static_response() {
+    let (mut driver, inputs) = otp_driver(vec![verification_code_responder(
+        AuthResponseSource::Static(String::from("123456")),
+    )]);
+
+    driver.open().unwrap();
+
+    assert_eq!(inputs.lock().unwrap()[..3], ["admin", "secret", "123456"]);
+
+    driver.close().unwrap();
+}
+
+#[test]
+fn callback_captures_state() {
+    // the callback hands out codes from its own state and records the prompts it was asked for
+    let codes = Arc::new(Mutex::new(vec![String::from("123456")]));
+    let prompts = Arc::new(Mutex::new(vec![]));
+    let callback_prompts = Arc::clone(&prompts);
+
+    let (mut driver, inputs) = otp_driver(vec![verification_code_responder(
+        AuthResponseSource::Callback(Arc::new(move |prompt: &str| {
+            callback_prompts.lock().unwrap().push(prompt.to_owned());
+
+            codes.lock().unwrap().pop().ok_or_else(|| ScrapliError {
+                details: String::from("out of codes"),
+                kind: ErrorKind::Generic,
+            })
+        })),
+    )]);
+
+    driver.open().unwrap();
+
+    assert_eq!(inputs.lock().unwrap()[2], "123456");
+    assert_eq!(prompts.lock().unwrap().len(), 1);
+    assert!(prompts.lock().unwrap()[0].ends_with("Verification code:"));
+
+    driver.close().unwrap();
+}
+
+#[test]
+fn callback_error_fails_open() {
+    let (mut driver, _) = otp_driver(vec![verification_code_responder(
+        AuthResponseSource::Callback(Arc::new(|_: &str| {
+            Err(ScrapliError {
+                details: String::from("token device unplugged"),
+                kind: ErrorKind::Generic,
+            })
+        })),
+    )]);
+
+    assert_eq!(driver.open().unwrap_err().details, "token device unplugged");
+}
+
+#[test]
+fn env_response() {
+    std::env::set_var("SCRAPLIRS_TEST_VERIFICATION_CODE", "123456");
+
+    let (mut driver, inputs) = otp_driver(vec![verification_code_responder(
+        AuthResponseSource::Env(String::from("SCRAPLIRS_TEST_VERIFICATION_CODE")),
+    )]);
+
+    driver.open().unwrap();
+
+    assert_eq!(inputs.lock().unwrap()[2], "123456");
+
+    driver.close().unwrap();
+}
+
+#[test]
+fn unset_env_response_fails_open() {
+    let (mut driver, _) = otp_driver(vec![verification_code_responder(AuthResponseSource::Env(
+        String::from("SCRAPLIRS_TEST_UNSET_VERIFICATION_CODE"),
+    ))]);
+
+    assert!(driver
+        .open()
+        .unwrap_err()
+        .details
+        .contains("'SCRAPLIRS_TEST_UNSET_VERIFICATION_CODE'"));
+}
+
+#[test]
+fn debug_redacts_static_responses() {
+    let source = AuthResponseSource::Static(String::from("hunter2"));
+
+    assert_eq!(format!("{source:?}"), "Static(<redacted>)");
+    assert_eq!(
+        format!(
+            "{:?}",
+            AuthResponseSource::Callback(Arc::new(|_: &str| Ok(String::new())))
+        ),
+        "Callback"
+    );
+}
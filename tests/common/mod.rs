@@ -0,0 +1,288 @@
+#![allow(dead_code)]
+
+use regex::bytes::Regex;
+use scraplirs::credentials::Credential;
+use scraplirs::driver::network::driver::{
+    PrivilegeLevel,
+    DEFAULT_PRIVILEGE_LEVEL_COST,
+};
+use scraplirs::driver::GenericDriverBuilder;
+use scraplirs::errors::ScrapliError;
+use scraplirs::transport::base::{
+    HostKeyPolicy,
+    InChannelAuthData,
+    InChannelAuthType,
+    Transport,
+    TransportArgs,
+};
+use std::collections::{
+    HashMap,
+    VecDeque,
+};
+use std::sync::atomic::{
+    AtomicBool,
+    AtomicUsize,
+    Ordering,
+};
+use std::sync::{
+    Arc,
+    Mutex,
+};
+use std::time::Duration;
+
+/// The function answering each line written to a `MockTransport` -- it receives the line (without
+/// the return) and returns the output to emit after the echoed line.
+pub type Responder = Box<dyn FnMut(&str) -> String + Send>;
+
+/// `MockTransport` is a scripted, in memory transport -- written input is echoed back, and each
+/// line is answered by the `Responder`.
+pub struct MockTransport {
+    args: TransportArgs,
+    banner: String,
+    responder: Responder,
+    pending: String,
+    output: VecDeque<u8>,
+    inputs: Arc<Mutex<Vec<String>>>,
+    telnet_auth: bool,
+    user: String,
+    password: Credential,
+    closed: Arc<AtomicBool>,
+    reads: Arc<AtomicUsize>,
+}
+
+impl MockTransport {
+    pub fn new(
+        args: TransportArgs,
+        banner: &str,
+        responder: Responder,
+        inputs: Arc<Mutex<Vec<String>>>,
+    ) -> Self {
+        Self {
+            args,
+            banner: banner.to_owned(),
+            responder,
+            pending: String::new(),
+            output: VecDeque::new(),
+            inputs,
+            telnet_auth: false,
+            user: String::new(),
+            password: Credential::default(),
+            closed: Arc::new(AtomicBool::new(false)),
+            reads: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    /// Requests in channel telnet auth with the given user and password.
+    pub fn telnet_auth(
+        mut self,
+        user: &str,
+        password: &str,
+    ) -> Self {
+        self.telnet_auth = true;
+        user.clone_into(&mut self.user);
+        self.password = Credential::from_static(password);
+
+        self
+    }
+
+    /// Shares the flags recording if the transport was closed and how often it was read from.
+    pub fn track(
+        mut self,
+        closed: &Arc<AtomicBool>,
+        reads: &Arc<AtomicUsize>,
+    ) -> Self {
+        self.closed = Arc::clone(closed);
+        self.reads = Arc::clone(reads);
+
+        self
+    }
+}
+
+impl Transport for MockTransport {
+    fn open(&mut self) -> Result<(), ScrapliError> {
+        self.output.extend(self.banner.as_bytes());
+
+        Ok(())
+    }
+
+    fn close(&mut self) -> Result<(), ScrapliError> {
+        self.closed.store(true, Ordering::SeqCst);
+
+        Ok(())
+    }
+
+    fn alive(&mut self) -> bool {
+        !self.closed.load(Ordering::SeqCst)
+    }
+
+    fn read(&mut self) -> Result<Vec<u8>, ScrapliError> {
+        self.reads.fetch_add(1, Ordering::SeqCst);
+
+        Ok(self.output.drain(..).collect())
+    }
+
+    fn read_n(
+        &mut self,
+        _n: u16,
+    ) -> Result<Vec<u8>, ScrapliError> {
+        self.read()
+    }
+
+    fn write(
+        &mut self,
+        b: &[u8],
+    ) -> Result<(), ScrapliError> {
+        for c in String::from_utf8_lossy(b).chars() {
+            if c == '\n' {
+                let line = std::mem::take(&mut self.pending);
+
+                self.inputs.lock().unwrap().push(line.clone());
+
+                let response = (self.responder)(line.as_str());
+
+                self.output.extend(response.as_bytes());
+            } else {
+                self.pending.push(c);
+                self.output.extend(c.to_string().as_bytes());
+            }
+        }
+
+        Ok(())
+    }
+
+    fn get_transport_args(self) -> TransportArgs {
+        self.args
+    }
+
+    fn get_host(&self) -> String {
+        self.args.host.clone()
+    }
+
+    fn get_port(&self) -> u16 {
+        self.args.port
+    }
+
+    fn in_channel_auth_data(&self) -> InChannelAuthData {
+        InChannelAuthData {
+            auth_type: if self.telnet_auth {
+                InChannelAuthType::Telnet
+            } else {
+                InChannelAuthType::None
+            },
+            host: self.args.host.clone(),
+            user: self.user.clone(),
+            password: self.password.clone(),
+            private_key_passphrase: Default::default(),
+            host_key_policy: HostKeyPolicy::Insecure,
+            jump_hosts: vec![],
+        }
+    }
+}
+
+/// Returns a generic driver builder using a `MockTransport` with the given banner and responder,
+/// and the (shared) log of every line written to the transport.
+pub fn mock_builder(
+    banner: &str,
+    responder: Responder,
+) -> (GenericDriverBuilder, Arc<Mutex<Vec<String>>>) {
+    let inputs = Arc::new(Mutex::new(vec![]));
+    let transport_inputs = Arc::clone(&inputs);
+    let banner = banner.to_owned();
+
+    let builder = GenericDriverBuilder::new("mock")
+        .timeout_ops(Duration::from_secs(5))
+        .read_delay(Duration::from_millis(1))
+        .transport_factory(move |args| {
+            MockTransport::new(args, banner.as_str(), responder, transport_inputs)
+        });
+
+    (builder, inputs)
+}
+
+/// Formats device output the way a device would -- a return after the echoed input, then each
+/// output line terminated with "\r\n", then the prompt.
+pub fn device_output(
+    body: &str,
+    prompt: &str,
+) -> String {
+    let mut output = String::from("\r\n");
+
+    for line in body.lines() {
+        output.push_str(line);
+        output.push_str("\r\n");
+    }
+
+    output.push_str(prompt);
+
+    output
+}
+
+/// Returns a responder for a simple IOS-like device named "router" -- it handles "configure
+/// terminal", "end", "exit" and entering "interface" and "router" sub-modes, and answers any other
+/// input with its entry in `outputs` (or no output at all).
+pub fn ios_device(outputs: HashMap<String, String>) -> Responder {
+    let mut context: Option<String> = None;
+
+    Box::new(move |line| {
+        let input = line.trim();
+
+        match (input, context.as_deref()) {
+            ("configure terminal", _) => context = Some(String::from("config")),
+            ("end", _) => context = None,
+            ("exit", Some("config")) => context = None,
+            ("exit", Some(_)) => context = Some(String::from("config")),
+            (_, Some(_)) if input.starts_with("interface ") => {
+                context = Some(String::from("config-if"));
+            }
+            (_, Some(_)) if input.starts_with("router ") => {
+                context = Some(String::from("config-router"));
+            }
+            _ => {}
+        }
+
+        let prompt = context
+            .as_ref()
+            .map_or_else(|| String::from("router#"), |c| format!("router({c})#"));
+
+        device_output(
+            outputs.get(input).map_or("", String::as_str),
+            prompt.as_str(),
+        )
+    })
+}
+
+/// Returns a privilege level with the given name, pattern and previous privilege level.
+pub fn privilege_level(
+    name: &str,
+    pattern: &str,
+    previous_privilege_level: &str,
+    escalate: &str,
+    de_escalate: &str,
+) -> PrivilegeLevel {
+    PrivilegeLevel {
+        name: name.to_owned(),
+        pattern: Regex::new(pattern).unwrap(),
+        not_contains: vec![],
+        previous_privilege_level: previous_privilege_level.to_owned(),
+        de_escalate: de_escalate.to_owned(),
+        escalate: escalate.to_owned(),
+        escalate_auth: false,
+        escalate_prompt: String::new(),
+        cost: DEFAULT_PRIVILEGE_LEVEL_COST,
+        jumps: vec![],
+    }
+}
+
+/// Returns the "privilege-exec" and "configuration" privilege levels of the `ios_device`.
+pub fn ios_privilege_levels() -> Vec<PrivilegeLevel> {
+    vec![
+        privilege_level("privilege-exec", r"(?m)^router#$", "", "", ""),
+        privilege_level(
+            "configuration",
+            r"(?m)^router\([\w\-]+\)#$",
+            "privilege-exec",
+            "configure terminal",
+            "end",
+        ),
+    ]
+}
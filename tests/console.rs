@@ -0,0 +1,136 @@
+mod common;
+
+use common::{
+    MockTransport,
+    Responder,
+};
+use scraplirs::driver::generic::driver::Driver;
+use scraplirs::driver::GenericDriverBuilder;
+use std::sync::{
+    Arc,
+    Mutex,
+};
+use std::time::Duration;
+
+/// Returns a console mode driver (sending a return every `return_interval` the console is quiet)
+/// using a telnet-ish `MockTransport` which logs in with "admin"/"secret", along with the log of
+/// every line written to the transport.
+fn console_driver(
+    banner: &str,
+    responder: Responder,
+    return_interval: Duration,
+    logout_on_close: bool,
+) -> (Driver, Arc<Mutex<Vec<String>>>) {
+    let inputs = Arc::new(Mutex::new(vec![]));
+    let transport_inputs = Arc::clone(&inputs);
+    let banner = banner.to_owned();
+
+    let driver = GenericDriverBuilder::new("console-server")
+        .timeout_ops(Duration::from_secs(5))
+        .read_delay(Duration::from_millis(1))
+        .console_mode(true)
+        .console_return_interval(return_interval)
+        .console_logout_on_close(logout_on_close)
+        .transport_factory(move |args| {
+            MockTransport::new(args, banner.as_str(), responder, transport_inputs)
+                .telnet_auth("admin", "secret")
+        })
+        .build();
+
+    (driver, inputs)
+}
+
+/// A console which shows a login prompt once woken with a return, then logs in "admin"/"secret".
+fn login_console() -> Responder {
+    let mut awake = false;
+
+    Box::new(move |line| match (awake, line) {
+        (false, "") => {
+            awake = true;
+
+            String::from("\r\n\r\nUser Access Verification\r\n\r\nUsername: ")
+        }
+        (false, _) => String::new(),
+        (true, "admin") => String::from("\r\nPassword: "),
+        (true, "secret") => String::from("\r\nrouter>"),
+        (true, _) => String::from("\r\nrouter>"),
+    })
+}
+
+/// A console left logged in (privileged) by a previous session.
+fn logged_in_console() -> Responder {
+    Box::new(|_| String::from("\r\nrouter#"))
+}
+
+#[test]
+fn press_return_banner_is_answered_before_login() {
+    let (mut driver, inputs) = console_driver(
+        "\r\nrouter con0 is now available\r\n\r\nPress RETURN to get started.\r\n",
+        login_console(),
+        // long enough that only the banner can be what the return is sent for
+        Duration::from_secs(30),
+        false,
+    );
+
+    driver.open().unwrap();
+
+    assert_eq!(
+        inputs.lock().unwrap()[..3],
+        [String::new(), String::from("admin"), String::from("secret")]
+    );
+    assert_eq!(driver.get_prompt().unwrap(), "router>");
+}
+
+#[test]
+fn quiet_console_is_woken_with_returns() {
+    // nothing at all is shown until a return is sent
+    let (mut driver, inputs) =
+        console_driver("", login_console(), Duration::from_millis(100), false);
+
+    driver.open().unwrap();
+
+    assert_eq!(
+        inputs.lock().unwrap()[..3],
+        [String::new(), String::from("admin"), String::from("secret")]
+    );
+}
+
+#[test]
+fn logged_in_session_is_reused() {
+    let (mut driver, inputs) =
+        console_driver("", logged_in_console(), Duration::from_millis(100), false);
+
+    driver.open().unwrap();
+
+    let inputs = inputs.lock().unwrap().clone();
+
+    assert!(inputs.iter().all(String::is_empty), "sent {inputs:?}");
+    assert_eq!(driver.get_prompt().unwrap(), "router#");
+}
+
+#[test]
+fn logout_on_close_sends_logout_command() {
+    let (mut driver, inputs) =
+        console_driver("", logged_in_console(), Duration::from_millis(100), true);
+
+    driver.open().unwrap();
+    driver.close().unwrap();
+
+    assert_eq!(
+        inputs.lock().unwrap().last().map(String::as_str),
+        Some("exit")
+    );
+}
+
+#[test]
+fn no_logout_on_close_by_default() {
+    let (mut driver, inputs) =
+        console_driver("", logged_in_console(), Duration::from_millis(100), false);
+
+    driver.open().unwrap();
+    driver.close().unwrap();
+
+    let inputs = inputs.lock().unwrap().clone();
+
+    assert!(inputs.iter().all(String::is_empty), "sent {inputs:?}");
+}
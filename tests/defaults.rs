@@ -0,0 +1,286 @@
+use scraplirs::defaults::{
+    Defaults,
+    OptionSource,
+    DEFAULTS_ENV_PREFIX,
+    DEFAULTS_FILE_ENV_VAR,
+};
+use scraplirs::driver::GenericDriverBuilder;
+use std::path::PathBuf;
+use std::sync::{
+    Mutex,
+    MutexGuard,
+};
+use std::time::Duration;
+
+const TOML_DEFAULTS: &str = r#"
+user = "file-user"
+password = "file-password"
+port = 2022
+ssh-strict-key = false
+ssh-config-file-path = "/dev/null"
+system-extra-args = ["-o", "PubkeyAcceptedKeyTypes=+ssh-rsa"]
+timeout-socket = "15s"
+timeout-ops = "1m 30s"
+"#;
+
+const YAML_DEFAULTS: &str = r#"
+user: file-user
+password: file-password
+port: 2022
+ssh-strict-key: false
+ssh-config-file-path: /dev/null
+system-extra-args: ["-o", "PubkeyAcceptedKeyTypes=+ssh-rsa"]
+timeout-socket: 15s
+timeout-ops: 1m 30s
+"#;
+
+/// The environment is process wide, so every test reading or setting `SCRAPLI_*` variables holds
+/// this lock via `Env`.
+static ENV_LOCK: Mutex<()> = Mutex::new(());
+
+struct Env<'a> {
+    _lock: MutexGuard<'a, ()>,
+}
+
+impl Env<'_> {
+    fn new() -> Self {
+        let lock = ENV_LOCK.lock().unwrap_or_else(|err| err.into_inner());
+
+        clear_scrapli_env();
+
+        Self { _lock: lock }
+    }
+
+    fn set(
+        &self,
+        name: &str,
+        value: &str,
+    ) {
+        std::env::set_var(name, value);
+    }
+}
+
+impl Drop for Env<'_> {
+    fn drop(&mut self) {
+        clear_scrapli_env();
+    }
+}
+
+/// Removes every `SCRAPLI_*` environment variable so no test sees values from the outer shell or
+/// another test.
+fn clear_scrapli_env() {
+    for (name, _) in std::env::vars_os() {
+        if name
+            .to_str()
+            .is_some_and(|name| name.starts_with(DEFAULTS_ENV_PREFIX))
+        {
+            std::env::remove_var(name);
+        }
+    }
+}
+
+struct DefaultsFile(PathBuf);
+
+impl DefaultsFile {
+    fn new(
+        name: &str,
+        content: &str,
+    ) -> Self {
+        let path =
+            std::env::temp_dir().join(format!("scraplirs-defaults-{}-{name}", std::process::id()));
+
+        std::fs::write(&path, content).unwrap();
+
+        Self(path)
+    }
+
+    fn path(&self) -> String {
+        self.0.to_string_lossy().into_owned()
+    }
+}
+
+impl Drop for DefaultsFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
+
+fn assert_file_defaults(defaults: &Defaults) {
+    assert_eq!(defaults.user.as_deref(), Some("file-user"));
+    assert!(defaults.password.as_ref().unwrap().is_set());
+    assert_eq!(defaults.port, Some(2022));
+    assert_eq!(defaults.ssh_strict_key, Some(false));
+    assert_eq!(defaults.ssh_config_file_path.as_deref(), Some("/dev/null"));
+    assert_eq!(
+        defaults.system_extra_args.as_deref(),
+        Some(
+            [
+                String::from("-o"),
+                String::from("PubkeyAcceptedKeyTypes=+ssh-rsa")
+            ]
+            .as_slice()
+        )
+    );
+    assert_eq!(defaults.timeout_socket, Some(Duration::from_secs(15)));
+    assert_eq!(defaults.timeout_ops, Some(Duration::from_secs(90)));
+}
+
+#[test]
+fn toml_file() {
+    let file = DefaultsFile::new("defaults.toml", TOML_DEFAULTS);
+
+    let defaults = Defaults::from_file(file.path().as_str()).unwrap();
+
+    assert_file_defaults(&defaults);
+    assert_eq!(defaults.source("user"), OptionSource::File(file.path()));
+    assert_eq!(
+        defaults.source("timeout-ops"),
+        OptionSource::File(file.path())
+    );
+    assert_eq!(
+        defaults.source("ssh-private-key-path"),
+        OptionSource::Default
+    );
+}
+
+#[test]
+fn toml_extension_is_case_insensitive() {
+    let file = DefaultsFile::new("defaults.TOML", TOML_DEFAULTS);
+
+    assert_file_defaults(&Defaults::from_file(file.path().as_str()).unwrap());
+}
+
+#[test]
+fn yaml_file() {
+    let file = DefaultsFile::new("defaults.yaml", YAML_DEFAULTS);
+
+    assert_file_defaults(&Defaults::from_file(file.path().as_str()).unwrap());
+}
+
+#[test]
+fn toml_content_in_yaml_file_errors() {
+    // the extension decides the format, toml is not valid yaml for these options
+    let file = DefaultsFile::new("toml-content.yaml", TOML_DEFAULTS);
+
+    assert!(Defaults::from_file(file.path().as_str()).is_err());
+}
+
+#[test]
+fn toml_unknown_key_errors() {
+    let file = DefaultsFile::new("unknown.toml", "usr = \"typo\"\n");
+
+    let err = Defaults::from_file(file.path().as_str()).unwrap_err();
+
+    assert!(err.details.contains("failed parsing defaults file"));
+    assert!(err.details.contains(file.path().as_str()));
+}
+
+#[test]
+fn missing_file_errors() {
+    assert!(Defaults::from_file("/nonexistent/scraplirs/defaults.toml")
+        .unwrap_err()
+        .details
+        .contains("failed reading defaults file"));
+}
+
+#[test]
+fn env_overrides_file() {
+    let env = Env::new();
+    let file = DefaultsFile::new("env-overrides.toml", TOML_DEFAULTS);
+
+    env.set(DEFAULTS_FILE_ENV_VAR, file.path().as_str());
+    env.set("SCRAPLI_USER", "env-user");
+    env.set("SCRAPLI_TIMEOUT_OPS", "30s");
+
+    let defaults = Defaults::load().unwrap();
+
+    assert_eq!(defaults.user.as_deref(), Some("env-user"));
+    assert_eq!(
+        defaults.source("user"),
+        OptionSource::Env(String::from("SCRAPLI_USER"))
+    );
+    assert_eq!(defaults.timeout_ops, Some(Duration::from_secs(30)));
+    assert_eq!(
+        defaults.source("timeout-ops"),
+        OptionSource::Env(String::from("SCRAPLI_TIMEOUT_OPS"))
+    );
+    assert_eq!(defaults.port, Some(2022));
+    assert_eq!(defaults.source("port"), OptionSource::File(file.path()));
+}
+
+#[test]
+fn invalid_env_value_errors() {
+    let env = Env::new();
+
+    env.set("SCRAPLI_PORT", "not-a-port");
+
+    assert!(Defaults::load()
+        .unwrap_err()
+        .details
+        .contains("'SCRAPLI_PORT'"));
+}
+
+#[test]
+fn builder_overrides_env_and_file() {
+    let env = Env::new();
+    let file = DefaultsFile::new("builder-overrides.toml", TOML_DEFAULTS);
+
+    env.set(DEFAULTS_FILE_ENV_VAR, file.path().as_str());
+    env.set("SCRAPLI_USER", "env-user");
+    env.set("SCRAPLI_TIMEOUT_OPS", "30s");
+
+    let builder = GenericDriverBuilder::new_with_defaults("router", Defaults::load().unwrap())
+        .user("builder-user")
+        .port(22);
+
+    let resolved = builder.resolved_options();
+
+    let option = |name: &str| {
+        let option = resolved.get(name).unwrap();
+
+        (option.value.clone(), option.source.clone())
+    };
+
+    assert_eq!(
+        option("user"),
+        (String::from("builder-user"), OptionSource::Builder)
+    );
+    assert_eq!(option("port"), (String::from("22"), OptionSource::Builder));
+    assert_eq!(
+        option("timeout-ops"),
+        (
+            String::from("30s"),
+            OptionSource::Env(String::from("SCRAPLI_TIMEOUT_OPS"))
+        )
+    );
+    assert_eq!(
+        option("timeout-socket"),
+        (String::from("15s"), OptionSource::File(file.path()))
+    );
+    assert_eq!(
+        option("ssh-strict-key"),
+        (String::from("no"), OptionSource::File(file.path()))
+    );
+    assert_eq!(option("password").0, "<redacted>");
+    assert_eq!(option("ssh-private-key-path").1, OptionSource::Default);
+
+    let report = resolved.to_string();
+
+    assert!(report.contains("user: builder-user (builder)\n"));
+    assert!(report.contains("timeout-ops: 30s (env SCRAPLI_TIMEOUT_OPS)\n"));
+    assert!(report.contains("port: 22 (builder)\n"));
+    assert!(report.contains(&format!("timeout-socket: 15s (file '{}')\n", file.path())));
+}
+
+#[test]
+fn no_defaults_reports_default_sources() {
+    let _env = Env::new();
+
+    let resolved = GenericDriverBuilder::new_with_defaults("router", Defaults::load().unwrap())
+        .resolved_options();
+
+    assert!(resolved
+        .options
+        .iter()
+        .all(|option| option.source == OptionSource::Default));
+}
@@ -0,0 +1,318 @@
+mod common;
+
+use common::{
+    device_output,
+    ios_device,
+    mock_builder,
+};
+use scraplirs::driver::{
+    GenericDriver,
+    GenericDriverBuilder,
+};
+use std::collections::HashMap;
+use std::sync::{
+    Arc,
+    Mutex,
+};
+use std::thread;
+use std::time::Duration;
+
+fn assert_send<T: Send>(_: &T) {}
+
+fn show_version_device() -> common::Responder {
+    ios_device(HashMap::from([(
+        String::from("show version"),
+        String::from("Cisco IOS XE Software, Version 17.03.01a"),
+    )]))
+}
+
+#[test]
+fn builder_with_transport_factory_is_send() {
+    let (builder, _) = mock_builder("router#", show_version_device());
+
+    assert_send(&builder);
+}
+
+#[test]
+fn open_and_send_command() {
+    let (builder, inputs) = mock_builder("banner\r\nrouter#", show_version_device());
+
+    let mut driver = builder.build();
+
+    driver.open().unwrap();
+
+    let response = driver.send_command("show version").unwrap();
+
+    assert_eq!(
+        response.result.trim(),
+        "Cisco IOS XE Software, Version 17.03.01a"
+    );
+    assert!(!response.failed);
+
+    driver.close().unwrap();
+
+    let sent: Vec<String> = inputs
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|input| !input.is_empty())
+        .cloned()
+        .collect();
+
+    assert_eq!(sent, ["show version"]);
+}
+
+#[test]
+fn builder_moves_to_another_thread() {
+    // the factory (and its mock transport) is built, opened and used on another thread
+    let (builder, inputs) = mock_builder("router#", show_version_device());
+
+    let result = thread::spawn(move || {
+        let mut driver = builder.build();
+
+        driver.open().unwrap();
+
+        let result = driver.send_command("show version").unwrap().result;
+
+        driver.close().unwrap();
+
+        result
+    })
+    .join()
+    .unwrap();
+
+    assert!(result.contains("Version 17.03.01a"));
+    assert!(inputs
+        .lock()
+        .unwrap()
+        .contains(&String::from("show version")));
+}
+
+#[test]
+fn failed_when_contains_marks_response_failed() {
+    let (builder, _) = mock_builder(
+        "router#",
+        Box::new(|line| {
+            device_output(
+                if line == "show bogus" {
+                    "% Invalid input detected at '^' marker."
+                } else {
+                    ""
+                },
+                "router#",
+            )
+        }),
+    );
+
+    let mut driver = builder
+        .failed_when_contains(vec![String::from("% Invalid input detected")])
+        .build();
+
+    driver.open().unwrap();
+
+    assert!(driver.send_command("show bogus").unwrap().failed);
+
+    driver.close().unwrap();
+}
+
+/// Returns a responder answering "show version" with a json payload if (and only if) the command
+/// has a structured output pipe, as a device supporting structured output would.
+fn structured_device() -> common::Responder {
+    Box::new(|line| {
+        device_output(
+            match line.split_once(" | ") {
+                Some(("show version", _)) => r#"{"version": "4.30.1F"}"#,
+                _ => "",
+            },
+            "router#",
+        )
+    })
+}
+
+#[test]
+fn send_command_structured_appends_platform_pipe() {
+    let (builder, inputs) = mock_builder("router#", structured_device());
+
+    let mut driver = builder.platform_type("arista_eos").build();
+
+    driver.open().unwrap();
+
+    let payload = driver.send_command_structured("show version").unwrap();
+
+    driver.close().unwrap();
+
+    assert_eq!(payload["version"], "4.30.1F");
+    assert!(inputs
+        .lock()
+        .unwrap()
+        .contains(&String::from("show version | json")));
+}
+
+#[test]
+fn send_command_structured_prefers_explicit_pipe() {
+    let (builder, inputs) = mock_builder("router#", structured_device());
+
+    let mut driver = builder
+        .platform_type("arista_eos")
+        .structured_output_pipe("| json-ng")
+        .build();
+
+    driver.open().unwrap();
+    driver.send_command_structured("show version").unwrap();
+    driver.close().unwrap();
+
+    assert!(inputs
+        .lock()
+        .unwrap()
+        .contains(&String::from("show version | json-ng")));
+}
+
+#[test]
+fn send_command_structured_errors_without_platform_pipe() {
+    let (builder, inputs) = mock_builder("router#", structured_device());
+
+    // the ios-xe platform definition has no structured output pipe
+    let mut driver = builder.platform_type("cisco_iosxe").build();
+
+    driver.open().unwrap();
+
+    let err = driver
+        .send_command_structured("show version")
+        .err()
+        .expect("structured output should not be supported");
+
+    driver.close().unwrap();
+
+    assert!(err.details.contains("does not support structured output"));
+    assert!(!inputs
+        .lock()
+        .unwrap()
+        .iter()
+        .any(|input| input.starts_with("show version")));
+}
+
+#[test]
+fn send_command_structured_errors_without_platform_type() {
+    let (builder, _) = mock_builder("router#", structured_device());
+
+    let mut driver = builder.build();
+
+    driver.open().unwrap();
+
+    assert!(driver.send_command_structured("show version").is_err());
+
+    driver.close().unwrap();
+}
+
+/// Returns a builder whose lifecycle hooks push their name into the returned labels, in the order
+/// they run.
+fn labelled_hooks_builder() -> (GenericDriverBuilder, Arc<Mutex<Vec<&'static str>>>) {
+    let (builder, _) = mock_builder("router#", show_version_device());
+
+    let labels = Arc::new(Mutex::new(vec![]));
+
+    let push = |label: &'static str| {
+        let labels = Arc::clone(&labels);
+
+        move |_: &mut GenericDriver| {
+            labels.lock().unwrap().push(label);
+
+            Ok(())
+        }
+    };
+
+    let builder = builder
+        .pre_open(push("pre_open"))
+        .post_auth(push("post_auth"))
+        .on_open(push("on_open"))
+        .pre_close(push("pre_close"))
+        .on_close(push("on_close"));
+
+    (builder, labels)
+}
+
+/// Returns a device that never sends a prompt after "hang", so sending it times out.
+fn hanging_device() -> common::Responder {
+    Box::new(|line| {
+        if line == "hang" {
+            String::from("\r\n")
+        } else {
+            device_output("", "router#")
+        }
+    })
+}
+
+#[test]
+fn hooks_run_in_order_on_open() {
+    let (builder, labels) = labelled_hooks_builder();
+
+    let mut driver = builder.build();
+
+    driver.open().unwrap();
+
+    assert_eq!(
+        *labels.lock().unwrap(),
+        ["pre_open", "post_auth", "on_open"]
+    );
+
+    driver.close().unwrap();
+}
+
+#[test]
+fn hooks_run_in_order_on_close() {
+    let (builder, labels) = labelled_hooks_builder();
+
+    let mut driver = builder.build();
+
+    driver.open().unwrap();
+
+    labels.lock().unwrap().clear();
+
+    driver.close().unwrap();
+
+    // close runs the close hooks only, never `on_open`
+    assert_eq!(*labels.lock().unwrap(), ["pre_close", "on_close"]);
+}
+
+#[test]
+fn on_error_runs_on_failed_send() {
+    let (builder, _) = mock_builder("router#", hanging_device());
+
+    let errors = Arc::new(Mutex::new(vec![]));
+    let hook_errors = Arc::clone(&errors);
+
+    let mut driver = builder
+        .timeout_ops(Duration::from_millis(200))
+        .on_error(move |_, err| hook_errors.lock().unwrap().push(err.details.clone()))
+        .build();
+
+    driver.open().unwrap();
+
+    driver.send_command("show version").unwrap();
+    assert!(errors.lock().unwrap().is_empty(), "not run on success");
+
+    let Err(err) = driver.send_command("hang") else {
+        panic!("send without a prompt should time out");
+    };
+    assert_eq!(*errors.lock().unwrap(), [err.details]);
+
+    driver.close().unwrap();
+}
+
+#[test]
+fn on_error_runs_once_for_failed_send_in_hook() {
+    let (builder, _) = mock_builder("router#", hanging_device());
+
+    let errors = Arc::new(Mutex::new(vec![]));
+    let hook_errors = Arc::clone(&errors);
+
+    let mut driver = builder
+        .timeout_ops(Duration::from_millis(200))
+        .on_open(|driver| driver.send_command("hang").map(|_| ()))
+        .on_error(move |_, err| hook_errors.lock().unwrap().push(err.details.clone()))
+        .build();
+
+    let err = driver.open().unwrap_err();
+
+    assert_eq!(*errors.lock().unwrap(), [err.details]);
+}
@@ -0,0 +1,220 @@
+mod common;
+
+use common::{
+    device_output,
+    mock_builder,
+};
+use regex::Regex;
+use scraplirs::channel::{
+    SendInteractiveEvent,
+    SendInteractiveEvents,
+};
+use scraplirs::driver::interceptor::{
+    from_fn,
+    DenyList,
+    Next,
+    Operation,
+};
+use scraplirs::driver::GenericDriverOperationOptions;
+use scraplirs::errors::ErrorKind;
+use std::sync::{
+    Arc,
+    Mutex,
+};
+
+fn echo_device() -> common::Responder {
+    Box::new(|line| {
+        device_output(
+            match line {
+                "enable" => return String::from("\r\nPassword: "),
+                "" => "",
+                _ => line,
+            },
+            "router#",
+        )
+    })
+}
+
+fn sent(inputs: &Arc<Mutex<Vec<String>>>) -> Vec<String> {
+    inputs
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|input| !input.is_empty())
+        .cloned()
+        .collect()
+}
+
+fn enable_events() -> SendInteractiveEvents {
+    SendInteractiveEvents(vec![
+        SendInteractiveEvent::new("enable", "Password:"),
+        SendInteractiveEvent {
+            input: String::from("enable-secret"),
+            response: String::new(),
+            hidden: true,
+        },
+    ])
+}
+
+#[test]
+fn interceptors_run_in_registration_order() {
+    let calls = Arc::new(Mutex::new(vec![]));
+
+    let recorder = |name: &'static str| {
+        let calls = Arc::clone(&calls);
+
+        from_fn(move |op: &mut Operation, next: &mut Next<'_>| {
+            calls.lock().unwrap().push(format!("{name} before"));
+
+            let response = next.run(op);
+
+            calls.lock().unwrap().push(format!("{name} after"));
+
+            response
+        })
+    };
+
+    let (builder, _) = mock_builder("router#", echo_device());
+
+    let mut driver = builder
+        .interceptor(recorder("outer"))
+        .interceptor(recorder("inner"))
+        .build();
+
+    driver.open().unwrap();
+    driver.send_command("show clock").unwrap();
+    driver.close().unwrap();
+
+    assert_eq!(
+        *calls.lock().unwrap(),
+        ["outer before", "inner before", "inner after", "outer after"]
+    );
+}
+
+#[test]
+fn interceptor_rewrites_input() {
+    let (builder, inputs) = mock_builder("router#", echo_device());
+
+    let mut driver = builder
+        .interceptor(from_fn(|op: &mut Operation, next: &mut _| {
+            if let Operation::Command(command) = op {
+                command.push_str(" | no-more");
+            }
+
+            next.run(op)
+        }))
+        .build();
+
+    driver.open().unwrap();
+
+    let response = driver.send_command("show running-config").unwrap();
+
+    driver.close().unwrap();
+
+    assert_eq!(sent(&inputs), ["show running-config | no-more"]);
+    assert_eq!(response.result.trim(), "show running-config | no-more");
+}
+
+#[test]
+fn deny_list_blocks_matching_input() {
+    let (builder, inputs) = mock_builder("router#", echo_device());
+
+    let mut driver = builder
+        .interceptor(DenyList::new(vec![Regex::new(r"^\s*reload").unwrap()]))
+        .build();
+
+    driver.open().unwrap();
+
+    let err = driver
+        .send_command("reload in 5")
+        .err()
+        .expect("reload should be denied");
+
+    assert_eq!(
+        err.kind,
+        ErrorKind::OperationDenied {
+            input: String::from("reload in 5"),
+            pattern: String::from(r"^\s*reload"),
+        }
+    );
+
+    // allowed commands still pass through the deny list
+    driver.send_command("show clock").unwrap();
+    driver.close().unwrap();
+
+    assert_eq!(sent(&inputs), ["show clock"]);
+}
+
+#[test]
+fn deny_list_never_sees_hidden_inputs() {
+    let (builder, inputs) = mock_builder("router#", echo_device());
+
+    let mut driver = builder
+        .interceptor(DenyList::new(vec![Regex::new("secret").unwrap()]))
+        .build();
+
+    driver.open().unwrap();
+    driver
+        .send_interactive(&enable_events(), &GenericDriverOperationOptions::default())
+        .unwrap();
+    driver.close().unwrap();
+
+    assert_eq!(sent(&inputs), ["enable", "enable-secret"]);
+}
+
+#[test]
+fn inputs_skip_hidden_events() {
+    let op = Operation::Interactive(enable_events());
+
+    assert_eq!(op.inputs(), ["enable"]);
+    assert!(!op.to_string().contains("enable-secret"));
+
+    let op = Operation::Config(String::from("interface Gi1\n description uplink"));
+
+    assert_eq!(op.inputs(), ["interface Gi1", " description uplink"]);
+}
+
+#[test]
+fn interceptor_retries_failed_operation() {
+    let mut attempts = 0;
+
+    let (builder, inputs) = mock_builder(
+        "router#",
+        Box::new(move |line| {
+            if line == "show inventory" {
+                attempts += 1;
+
+                if attempts == 1 {
+                    return device_output("% Device busy", "router#");
+                }
+
+                return device_output("NAME: \"Chassis\"", "router#");
+            }
+
+            device_output("", "router#")
+        }),
+    );
+
+    let mut driver = builder
+        .failed_when_contains(vec![String::from("% Device busy")])
+        .interceptor(from_fn(|op: &mut Operation, next: &mut _| {
+            let response = next.run(op)?;
+
+            if response.failed {
+                return next.run(op);
+            }
+
+            Ok(response)
+        }))
+        .build();
+
+    driver.open().unwrap();
+
+    let response = driver.send_command("show inventory").unwrap();
+
+    driver.close().unwrap();
+
+    assert!(!response.failed);
+    assert_eq!(response.result.trim(), "NAME: \"Chassis\"");
+    assert_eq!(sent(&inputs), ["show inventory", "show inventory"]);
+}
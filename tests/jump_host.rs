@@ -0,0 +1,380 @@
+use scraplirs::credentials::Credential;
+use scraplirs::driver::GenericDriverBuilder;
+use scraplirs::errors::ErrorKind;
+use scraplirs::transport::base::{
+    HostKeyPolicy,
+    JumpHost,
+};
+use scraplirs::transport::system::jump_host_open_args;
+use std::os::unix::fs::PermissionsExt;
+use std::path::PathBuf;
+use std::sync::{
+    Arc,
+    Mutex,
+};
+use std::time::Duration;
+
+fn jump_host(
+    host: &str,
+    port: u16,
+    user: &str,
+) -> JumpHost {
+    let mut jump_host = JumpHost::new(host);
+    jump_host.port = port;
+    user.clone_into(&mut jump_host.user);
+
+    jump_host
+}
+
+#[test]
+fn no_jump_hosts_no_args() {
+    assert!(jump_host_open_args("ssh", &[]).is_empty());
+}
+
+#[test]
+fn plain_hops_use_single_jump_arg() {
+    let mut bastion = jump_host("bastion", 22, "jumper");
+    // passwords are answered in channel, so do not need a per hop option
+    bastion.password = Credential::from_static("secret");
+
+    assert_eq!(
+        jump_host_open_args("ssh", &[bastion, jump_host("inner", 2222, "")]),
+        vec!["-J", "jumper@bastion:22,inner:2222"]
+    );
+}
+
+#[test]
+fn single_hop_with_options_uses_proxy_command() {
+    let mut bastion = jump_host("bastion", 2222, "jumper");
+    bastion.private_key_path = String::from("/keys/jumper");
+    bastion.host_key_policy = HostKeyPolicy::Insecure;
+
+    assert_eq!(
+        jump_host_open_args("ssh", &[bastion]),
+        vec![
+            "-o",
+            "ProxyCommand='ssh' -p 2222 -l 'jumper' -i '/keys/jumper' -o \
+             StrictHostKeyChecking=no -o UserKnownHostsFile=/dev/null -W %h:%p 'bastion'"
+        ]
+    );
+}
+
+#[test]
+fn hop_known_hosts_file_is_quoted() {
+    let mut bastion = jump_host("bastion", 22, "");
+    bastion.known_hosts_file_path = String::from("/known hosts");
+
+    assert_eq!(
+        jump_host_open_args("ssh", &[bastion]),
+        vec![
+            "-o",
+            "ProxyCommand='ssh' -p 22 -o StrictHostKeyChecking=yes -o \
+             'UserKnownHostsFile=/known hosts' -W %h:%p 'bastion'"
+        ]
+    );
+}
+
+#[test]
+fn nested_hops_escape_tokens_of_inner_proxy_command() {
+    let outer = jump_host("outer", 22, "u1");
+
+    let mut inner = jump_host("inner", 2022, "u2");
+    inner.host_key_policy = HostKeyPolicy::AcceptNew;
+
+    assert_eq!(
+        jump_host_open_args("/usr/bin/ssh", &[outer, inner]),
+        vec![
+            "-o",
+            "ProxyCommand='/usr/bin/ssh' -p 2022 -l 'u2' -o StrictHostKeyChecking=accept-new -o \
+             'ProxyCommand='\\''/usr/bin/ssh'\\'' -p 22 -l '\\''u1'\\'' -o \
+             StrictHostKeyChecking=yes -W %%h:%%p '\\''outer'\\''' -W %h:%p 'inner'"
+        ]
+    );
+}
+
+/// An ssh "stand-in" -- a script run in place of ssh that records its arguments, then prints each
+/// prompt (password or host key prompts of the jump hosts and target host, like ssh would),
+/// exiting if a wrong answer is sent. Once authenticated it answers "show version" like a device would.
+struct StandIn {
+    dir: PathBuf,
+}
+
+impl StandIn {
+    fn new(
+        name: &str,
+        prompts: &[(&str, &str)],
+    ) -> Self {
+        let dir =
+            std::env::temp_dir().join(format!("scraplirs-jump-host-{}-{name}", std::process::id()));
+
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let mut script = format!(
+            "#!/bin/sh\nprintf '%s\\n' \"$@\" > '{}'\n",
+            dir.join("args").display()
+        );
+
+        for (prompt, answer) in prompts {
+            script.push_str(&format!(
+                "printf \"{prompt}\"\nstty -echo\nIFS= read -r answer\nstty echo\nprintf \
+                 '\\n'\nif [ \"$answer\" != '{answer}' ]; then echo 'Permission denied'; exit \
+                 255; fi\n"
+            ));
+        }
+
+        script.push_str(
+            "printf 'router#'\nwhile IFS= read -r line; do\n  if [ \"$line\" = 'show version' \
+             ]; then printf 'Version 1.2.3\\n'; fi\n  printf 'router#'\ndone\n",
+        );
+
+        let bin = dir.join("ssh");
+
+        std::fs::write(&bin, script).unwrap();
+        std::fs::set_permissions(&bin, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        Self { dir }
+    }
+
+    fn bin(&self) -> String {
+        self.dir.join("ssh").to_string_lossy().into_owned()
+    }
+
+    fn args(&self) -> Vec<String> {
+        std::fs::read_to_string(self.dir.join("args"))
+            .unwrap()
+            .lines()
+            .map(str::to_owned)
+            .collect()
+    }
+}
+
+impl Drop for StandIn {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.dir);
+    }
+}
+
+fn stand_in_builder(
+    stand_in: &StandIn,
+    jump_hosts: Vec<JumpHost>,
+) -> GenericDriverBuilder {
+    GenericDriverBuilder::new("router")
+        .user("admin")
+        .password("router-secret")
+        .system_open_bin(stand_in.bin().as_str())
+        .ssh_jump_hosts(jump_hosts)
+        .timeout_ops(Duration::from_secs(10))
+}
+
+#[test]
+fn stand_in_jump_host_chain_answers_each_password_prompt() {
+    // the prompts deliberately do not come in the order of the hops, passwords are matched to
+    // the host named in the prompt
+    let stand_in = StandIn::new(
+        "chain",
+        &[
+            ("jumper2@bastion2's password: ", "bastion2-secret"),
+            ("jumper1@bastion1's password: ", "bastion1-secret"),
+            ("admin@router's password: ", "router-secret"),
+        ],
+    );
+
+    let mut bastion1 = jump_host("bastion1", 22, "jumper1");
+    bastion1.password = Credential::from_static("bastion1-secret");
+
+    let mut bastion2 = jump_host("bastion2", 2222, "jumper2");
+    bastion2.password = Credential::from_static("bastion2-secret");
+    bastion2.host_key_policy = HostKeyPolicy::Insecure;
+
+    let mut driver = stand_in_builder(&stand_in, vec![bastion1, bastion2]).build();
+
+    driver.open().unwrap();
+
+    let response = driver.send_command("show version").unwrap();
+
+    assert!(response.result.contains("Version 1.2.3"));
+
+    let args = stand_in.args();
+    let proxy_command = args
+        .iter()
+        .find(|arg| arg.starts_with("ProxyCommand="))
+        .unwrap();
+
+    // the second hop has its own host key policy, so the chain is a nested proxy command
+    assert!(proxy_command.contains("-p 2222 -l 'jumper2'"));
+    assert!(proxy_command.contains("StrictHostKeyChecking=no"));
+    assert!(proxy_command.contains("'\\''bastion1'\\''"));
+    assert!(!args.contains(&String::from("-J")));
+
+    driver.close().unwrap();
+}
+
+#[test]
+fn stand_in_plain_jump_host_uses_jump_arg() {
+    let stand_in = StandIn::new(
+        "plain",
+        &[
+            ("jumper@bastion's password: ", "bastion-secret"),
+            ("admin@router's password: ", "router-secret"),
+        ],
+    );
+
+    let mut bastion = jump_host("bastion", 22, "jumper");
+    bastion.password = Credential::from_static("bastion-secret");
+
+    let mut driver = stand_in_builder(&stand_in, vec![bastion]).build();
+
+    driver.open().unwrap();
+
+    assert!(driver
+        .send_command("show version")
+        .unwrap()
+        .result
+        .contains("Version 1.2.3"));
+
+    let args = stand_in.args();
+    let jump_idx = args.iter().position(|arg| arg == "-J").unwrap();
+
+    assert_eq!(args[jump_idx + 1], "jumper@bastion:22");
+
+    driver.close().unwrap();
+}
+
+#[test]
+fn stand_in_jump_host_without_password_gets_host_password() {
+    // a hop without a password of its own is answered with the host password
+    let stand_in = StandIn::new(
+        "shared",
+        &[
+            ("admin@bastion's password: ", "router-secret"),
+            ("admin@router's password: ", "router-secret"),
+        ],
+    );
+
+    let mut driver = stand_in_builder(&stand_in, vec![jump_host("bastion", 22, "admin")]).build();
+
+    driver.open().unwrap();
+
+    driver.close().unwrap();
+}
+
+#[test]
+fn stand_in_wrong_jump_host_password_fails_open() {
+    let stand_in = StandIn::new(
+        "wrong",
+        &[
+            ("jumper@bastion's password: ", "bastion-secret"),
+            ("admin@router's password: ", "router-secret"),
+        ],
+    );
+
+    let mut bastion = jump_host("bastion", 22, "jumper");
+    bastion.password = Credential::from_static("not-the-secret");
+
+    let mut driver = stand_in_builder(&stand_in, vec![bastion])
+        .timeout_ops(Duration::from_secs(2))
+        .build();
+
+    assert!(driver.open().is_err());
+}
+
+const HOST_KEY_PROMPT: &str = "The authenticity of host 'router (10.0.0.1)' can't be \
+                               established.\\nED25519 key fingerprint is \
+                               SHA256:aGVsbG8gd29ybGQ.\\nAre you sure you want to continue \
+                               connecting (yes/no/[fingerprint])? ";
+
+/// Returns a callback policy accepting only `pinned` fingerprints, recording every host and
+/// fingerprint it is asked about in the returned log.
+fn pinned_host_key_policy(
+    pinned: &[(&str, &str)]
+) -> (HostKeyPolicy, Arc<Mutex<Vec<(String, String)>>>) {
+    let pinned: Vec<(String, String)> = pinned
+        .iter()
+        .map(|&(host, fingerprint)| (host.to_owned(), fingerprint.to_owned()))
+        .collect();
+
+    let asked = Arc::new(Mutex::new(vec![]));
+    let callback_asked = Arc::clone(&asked);
+
+    let policy = HostKeyPolicy::Callback(Arc::new(move |host: &str, fingerprint: &str| {
+        callback_asked
+            .lock()
+            .unwrap()
+            .push((host.to_owned(), fingerprint.to_owned()));
+
+        pinned.iter().any(|(pinned_host, pinned_fingerprint)| {
+            pinned_host == host && pinned_fingerprint == fingerprint
+        })
+    }));
+
+    (policy, asked)
+}
+
+#[test]
+fn host_key_callback_is_send_and_sync() {
+    fn assert_send_sync<T: Send + Sync>(_: &T) {}
+
+    let (policy, _) = pinned_host_key_policy(&[]);
+
+    assert_send_sync(&policy);
+    assert_eq!(format!("{policy:?}"), "Callback");
+    assert_eq!(policy.strict_host_key_checking(), "ask");
+}
+
+#[test]
+fn stand_in_host_key_callback_accepts_pinned_key() {
+    let stand_in = StandIn::new(
+        "host-key-pinned",
+        &[
+            (HOST_KEY_PROMPT, "yes"),
+            ("admin@router's password: ", "router-secret"),
+        ],
+    );
+
+    let (policy, asked) = pinned_host_key_policy(&[("router", "SHA256:aGVsbG8gd29ybGQ")]);
+
+    let mut driver = stand_in_builder(&stand_in, vec![])
+        .ssh_host_key_policy(policy)
+        .build();
+
+    driver.open().unwrap();
+
+    assert_eq!(
+        *asked.lock().unwrap(),
+        vec![(
+            String::from("router"),
+            String::from("SHA256:aGVsbG8gd29ybGQ")
+        )]
+    );
+
+    driver.close().unwrap();
+}
+
+#[test]
+fn stand_in_host_key_callback_rejects_unknown_key() {
+    let stand_in = StandIn::new(
+        "host-key-unknown",
+        &[
+            (HOST_KEY_PROMPT, "yes"),
+            ("admin@router's password: ", "router-secret"),
+        ],
+    );
+
+    let (policy, asked) = pinned_host_key_policy(&[("router", "SHA256:c29tZXRoaW5nIGVsc2U")]);
+
+    let mut driver = stand_in_builder(&stand_in, vec![])
+        .ssh_host_key_policy(policy)
+        .timeout_ops(Duration::from_secs(2))
+        .build();
+
+    let err = driver.open().unwrap_err();
+
+    assert_eq!(
+        err.kind,
+        ErrorKind::HostKeyRejected {
+            host: String::from("router"),
+            fingerprint: String::from("SHA256:aGVsbG8gd29ybGQ"),
+        }
+    );
+    assert_eq!(asked.lock().unwrap().len(), 1);
+}
@@ -0,0 +1,203 @@
+use scraplirs::transport::system::known_hosts;
+use std::path::PathBuf;
+
+const ROUTER_KEY: &str = "AAAAC3NzaC1lZDI1NTE5AAAAIBZrFtdhvq3Bdi9pX1TQZ9HamNRWoeyQoEgxuYh2U3rf";
+const ROUTER_FINGERPRINT: &str = "SHA256:1GXydUBXpjt/vbd/yoyjhw2jGZnNc6AUwEHivClTzWY";
+
+const SWITCH_KEY: &str = "AAAAC3NzaC1lZDI1NTE5AAAAIKobkizMG4juy3A1O25YqJwZ6HLOrY+mno+g/yzVnGE0";
+const SWITCH_FINGERPRINT: &str = "SHA256:9zP9K2MX4pPXrpeBp4zr/Xk5GgqMBIQ5Sa9+A5fWKww";
+
+/// A known hosts file removed once the test is done.
+struct KnownHostsFile(PathBuf);
+
+impl KnownHostsFile {
+    fn new(
+        name: &str,
+        content: &str,
+    ) -> Self {
+        let path = std::env::temp_dir().join(format!(
+            "scraplirs-known-hosts-{}-{name}",
+            std::process::id()
+        ));
+
+        std::fs::write(&path, content).unwrap();
+
+        Self(path)
+    }
+
+    fn path(&self) -> String {
+        self.0.to_string_lossy().into_owned()
+    }
+
+    fn content(&self) -> String {
+        std::fs::read_to_string(&self.0).unwrap()
+    }
+}
+
+impl Drop for KnownHostsFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
+
+fn fingerprints(
+    path: &str,
+    host: &str,
+    port: u16,
+) -> Vec<String> {
+    known_hosts::lookup(path, host, port)
+        .unwrap()
+        .iter()
+        .map(|known_host| known_host.fingerprint().unwrap())
+        .collect()
+}
+
+#[test]
+fn lookup_plain_and_wildcard_entries() {
+    let file = KnownHostsFile::new(
+        "lookup",
+        &format!(
+            "# lab devices\n\
+            router,10.0.0.1 ssh-ed25519 {ROUTER_KEY}\n\
+            \n\
+            *.lab,!bad.lab ssh-ed25519 {SWITCH_KEY}\n\
+            [router]:2022 ssh-ed25519 {SWITCH_KEY}\n"
+        ),
+    );
+
+    let entries = known_hosts::lookup(&file.path(), "10.0.0.1", 22).unwrap();
+
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].key_type, "ssh-ed25519");
+    assert_eq!(entries[0].key, ROUTER_KEY);
+    assert_eq!(entries[0].line_number, 2);
+    assert_eq!(entries[0].patterns, ["router", "10.0.0.1"]);
+    assert_eq!(entries[0].fingerprint().unwrap(), ROUTER_FINGERPRINT);
+
+    assert_eq!(
+        fingerprints(&file.path(), "switch1.lab", 22),
+        [SWITCH_FINGERPRINT]
+    );
+    assert!(fingerprints(&file.path(), "bad.lab", 22).is_empty());
+    assert_eq!(
+        fingerprints(&file.path(), "router", 2022),
+        [SWITCH_FINGERPRINT]
+    );
+    assert!(fingerprints(&file.path(), "unknown", 22).is_empty());
+}
+
+#[test]
+fn lookup_hashed_entries() {
+    // as written by "ssh-keygen -H" for "router" and "[router]:2022"
+    let file = KnownHostsFile::new(
+        "hashed",
+        &format!(
+            "|1|LfNzIEPawH9Lx6IJH40WoTbFY68=|pqFrnsvj/rE3W6qfJtvsA2D0Bw0= ssh-ed25519 {ROUTER_KEY}\n\
+            |1|/cRK2ghreZ5vvzawFnkL+7tzbtw=|6FtMBYOkyafiz+sZqlV4C+0Me6o= ssh-ed25519 {SWITCH_KEY}\n"
+        ),
+    );
+
+    assert_eq!(
+        fingerprints(&file.path(), "router", 22),
+        [ROUTER_FINGERPRINT]
+    );
+    assert_eq!(
+        fingerprints(&file.path(), "router", 2022),
+        [SWITCH_FINGERPRINT]
+    );
+    assert!(fingerprints(&file.path(), "switch", 22).is_empty());
+}
+
+#[test]
+fn lookup_missing_file_has_no_entries() {
+    assert!(
+        known_hosts::lookup("/nonexistent/known_hosts", "router", 22)
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[test]
+fn add_appends_entries() {
+    // the existing content has no trailing newline
+    let file = KnownHostsFile::new("add", &format!("switch ssh-ed25519 {SWITCH_KEY}"));
+
+    known_hosts::add(&file.path(), "router", 22, "ssh-ed25519", ROUTER_KEY).unwrap();
+    known_hosts::add(&file.path(), "router", 2022, "ssh-ed25519", ROUTER_KEY).unwrap();
+
+    assert_eq!(
+        file.content(),
+        format!(
+            "switch ssh-ed25519 {SWITCH_KEY}\n\
+            router ssh-ed25519 {ROUTER_KEY}\n\
+            [router]:2022 ssh-ed25519 {ROUTER_KEY}\n"
+        )
+    );
+    assert_eq!(
+        fingerprints(&file.path(), "router", 2022),
+        [ROUTER_FINGERPRINT]
+    );
+}
+
+#[test]
+fn add_creates_missing_file() {
+    let file = KnownHostsFile::new("create", "");
+
+    std::fs::remove_file(&file.0).unwrap();
+
+    known_hosts::add(&file.path(), "router", 22, "ssh-ed25519", ROUTER_KEY).unwrap();
+
+    assert_eq!(
+        fingerprints(&file.path(), "router", 22),
+        [ROUTER_FINGERPRINT]
+    );
+}
+
+#[test]
+fn add_rejects_invalid_key() {
+    let file = KnownHostsFile::new("invalid", "");
+
+    assert!(known_hosts::add(&file.path(), "router", 22, "ssh-ed25519", "not base64!").is_err());
+    assert_eq!(file.content(), "");
+}
+
+#[test]
+fn remove_drops_host_entries_only() {
+    let file = KnownHostsFile::new(
+        "remove",
+        &format!(
+            "# lab devices\n\
+            router,10.0.0.1 ssh-ed25519 {ROUTER_KEY}\n\
+            |1|LfNzIEPawH9Lx6IJH40WoTbFY68=|pqFrnsvj/rE3W6qfJtvsA2D0Bw0= ssh-ed25519 {ROUTER_KEY}\n\
+            @cert-authority router ssh-ed25519 {SWITCH_KEY}\n\
+            [router]:2022 ssh-ed25519 {SWITCH_KEY}\n\
+            switch ssh-ed25519 {SWITCH_KEY}\n"
+        ),
+    );
+
+    assert_eq!(known_hosts::remove(&file.path(), "router", 22).unwrap(), 2);
+    assert_eq!(
+        file.content(),
+        format!(
+            "# lab devices\n\
+            @cert-authority router ssh-ed25519 {SWITCH_KEY}\n\
+            [router]:2022 ssh-ed25519 {SWITCH_KEY}\n\
+            switch ssh-ed25519 {SWITCH_KEY}\n"
+        )
+    );
+
+    assert_eq!(known_hosts::remove(&file.path(), "router", 22).unwrap(), 0);
+}
+
+#[test]
+fn changed_key_can_be_replaced() {
+    let file = KnownHostsFile::new("replace", &format!("router ssh-ed25519 {ROUTER_KEY}\n"));
+
+    known_hosts::remove(&file.path(), "router", 22).unwrap();
+    known_hosts::add(&file.path(), "router", 22, "ssh-ed25519", SWITCH_KEY).unwrap();
+
+    assert_eq!(
+        fingerprints(&file.path(), "router", 22),
+        [SWITCH_FINGERPRINT]
+    );
+}
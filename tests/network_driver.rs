@@ -0,0 +1,678 @@
+mod common;
+
+use common::{
+    device_output,
+    ios_device,
+    ios_privilege_levels,
+    mock_builder,
+    privilege_level,
+    Responder,
+};
+use scraplirs::channel::OperationOptions as ChannelOperationOptions;
+use scraplirs::driver::network::driver::OperationOptions;
+use scraplirs::driver::{
+    NetworkDriver,
+    NetworkDriverBuilder,
+};
+use std::collections::HashMap;
+use std::sync::{
+    Arc,
+    Mutex,
+};
+use std::time::Duration;
+
+fn ios_network_driver(
+    outputs: HashMap<String, String>
+) -> (NetworkDriver, Arc<Mutex<Vec<String>>>) {
+    let (builder, inputs) = mock_builder("router#", ios_device(outputs));
+
+    let mut driver = NetworkDriverBuilder::new(
+        builder.failed_when_contains(vec![String::from("% Invalid input detected")]),
+    )
+    .privilege_levels(ios_privilege_levels())
+    .default_desired_privilege_level("privilege-exec")
+    .build();
+
+    driver.open().unwrap();
+
+    (driver, inputs)
+}
+
+/// Returns a responder for a junos-like device -- the configuration context is printed on the line
+/// above the prompt (ex: `[edit interfaces ge-0/0/0]`).
+fn junos_device() -> Responder {
+    let mut edit: Option<String> = None;
+
+    Box::new(move |line| {
+        let input = line.trim();
+
+        match (input, edit.as_deref()) {
+            ("configure", _) => edit = Some(String::new()),
+            ("exit configuration-mode", _) | ("exit", Some("")) => edit = None,
+            ("exit", Some(_)) => edit = Some(String::new()),
+            (_, Some(current)) if input.starts_with("edit ") => {
+                edit = Some(format!("{current} {}", input.trim_start_matches("edit ")));
+            }
+            _ => {}
+        }
+
+        let prompt = match edit.as_deref() {
+            None => String::from("user@router> "),
+            Some(context) => format!("[edit{context}]\nuser@router# "),
+        };
+
+        device_output("", prompt.as_str())
+    })
+}
+
+fn junos_network_driver() -> (NetworkDriver, Arc<Mutex<Vec<String>>>) {
+    let (builder, inputs) = mock_builder("user@router> ", junos_device());
+
+    let mut driver = NetworkDriverBuilder::new(builder)
+        .privilege_levels(vec![
+            privilege_level("exec", r"(?m)^user@router>\s?$", "", "", ""),
+            privilege_level(
+                "configuration",
+                r"(?m)^user@router#\s?$",
+                "exec",
+                "configure",
+                "exit configuration-mode",
+            ),
+        ])
+        .default_desired_privilege_level("exec")
+        .build();
+
+    driver.open().unwrap();
+
+    (driver, inputs)
+}
+
+/// Returns a responder for a sr os md-cli-like device -- the configuration context is part of the
+/// (two line) prompt (ex: `*(ex)[/configure router "Base"]`).
+fn sros_device() -> Responder {
+    let mut path: Option<String> = None;
+
+    Box::new(move |line| {
+        let input = line.trim();
+
+        match (input, path.as_deref()) {
+            ("edit-config exclusive", _) => path = Some(String::new()),
+            ("quit-config", _) => path = None,
+            ("exit", Some(_)) => path = Some(String::new()),
+            (_, Some(_)) if input.starts_with("/configure ") => {
+                path = Some(input.trim_start_matches('/').to_owned());
+            }
+            _ => {}
+        }
+
+        let prompt = match path.as_deref() {
+            None => String::from("[/]\nA:admin@router# "),
+            Some(context) => format!("*(ex)[/{context}]\nA:admin@router# "),
+        };
+
+        device_output("", prompt.as_str())
+    })
+}
+
+fn sros_network_driver() -> (NetworkDriver, Arc<Mutex<Vec<String>>>) {
+    let (builder, inputs) = mock_builder("[/]\nA:admin@router# ", sros_device());
+
+    let mut driver = NetworkDriverBuilder::new(builder)
+        .privilege_levels(vec![
+            privilege_level("exec", r"(?m)^\[/\]\nA:\S+@\S+#\s?$", "", "", ""),
+            privilege_level(
+                "configuration",
+                r"(?m)^\*?\(ex\)\[.*\]\nA:\S+@\S+#\s?$",
+                "exec",
+                "edit-config exclusive",
+                "quit-config",
+            ),
+        ])
+        .default_desired_privilege_level("exec")
+        .build();
+
+    driver.open().unwrap();
+
+    (driver, inputs)
+}
+
+fn return_to_root_options() -> OperationOptions {
+    OperationOptions {
+        return_to_root_config_context: true,
+        ..OperationOptions::default()
+    }
+}
+
+fn config_contexts(responses: &[scraplirs::response::Response]) -> Vec<&str> {
+    responses
+        .iter()
+        .map(|response| response.config_context.as_str())
+        .collect()
+}
+
+fn non_empty_inputs(inputs: &Arc<Mutex<Vec<String>>>) -> Vec<String> {
+    inputs
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|input| !input.is_empty())
+        .cloned()
+        .collect()
+}
+
+fn eager_options() -> OperationOptions {
+    let mut options = OperationOptions::default();
+    options
+        .generic_driver_operation_options
+        .channel_operation_options
+        .eager = true;
+
+    options
+}
+
+#[test]
+fn send_config_eager_sends_every_line_and_returns_to_prompt() {
+    let (mut driver, inputs) = ios_network_driver(HashMap::new());
+
+    let response = driver
+        .send_config(
+            "hostname router\ninterface e1\n description uplink",
+            &eager_options(),
+        )
+        .unwrap();
+
+    assert!(!response.failed);
+    assert_eq!(
+        response.input,
+        "hostname router\ninterface e1\n description uplink"
+    );
+    assert_eq!(driver.current_privilege_level(), "configuration");
+    // empty inputs are the returns sent to find the prompt
+    assert_eq!(
+        inputs
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|input| !input.is_empty())
+            .collect::<Vec<_>>(),
+        vec![
+            "configure terminal",
+            "hostname router",
+            "interface e1",
+            " description uplink"
+        ]
+    );
+}
+
+#[test]
+fn send_config_eager_enters_sub_modes() {
+    // every line prompts with a config mode prompt, only the prompt after the last line must end
+    // the read
+    let (mut driver, inputs) = ios_network_driver(HashMap::new());
+
+    let response = driver
+        .send_config(
+            "interface e1\n description uplink\nexit\nrouter ospf 1\n network 10.0.0.0 0.255.255.255 area 0",
+            &eager_options(),
+        )
+        .unwrap();
+
+    assert!(!response.failed);
+    assert_eq!(driver.current_privilege_level(), "configuration");
+    assert_eq!(driver.current_config_context(), "config-router");
+    assert!(response.result.contains("network 10.0.0.0"));
+    assert_eq!(
+        non_empty_inputs(&inputs),
+        [
+            "configure terminal",
+            "interface e1",
+            " description uplink",
+            "exit",
+            "router ospf 1",
+            " network 10.0.0.0 0.255.255.255 area 0"
+        ]
+    );
+}
+
+#[test]
+fn send_config_eager_detects_failures_beyond_prompt_search_depth() {
+    // the failure is followed by far more output than the prompt search depth (1024 bytes), it
+    // must still be seen by the aggregated failure check
+    let noisy_output = format!(
+        "% Invalid input detected at '^' marker.\n{}",
+        "some noisy output line\n".repeat(200)
+    );
+
+    let (mut driver, _) = ios_network_driver(HashMap::from([(
+        String::from("bogus command"),
+        noisy_output,
+    )]));
+
+    let response = driver
+        .send_config("hostname router\nbogus command", &eager_options())
+        .unwrap();
+
+    assert!(response.failed);
+    assert!(response.result.contains("% Invalid input detected"));
+}
+
+#[test]
+fn send_config_non_eager_joins_line_outputs() {
+    let (mut driver, _) = ios_network_driver(HashMap::from([(
+        String::from("bogus command"),
+        String::from("% Invalid input detected at '^' marker."),
+    )]));
+
+    let response = driver
+        .send_config(
+            "hostname router\nbogus command",
+            &OperationOptions::default(),
+        )
+        .unwrap();
+
+    assert!(response.failed);
+    assert_eq!(response.input, "hostname router\nbogus command");
+}
+
+#[test]
+fn channel_send_input_eager_returns_input() {
+    let (mut driver, _) = ios_network_driver(HashMap::new());
+
+    let options = ChannelOperationOptions {
+        eager: true,
+        ..ChannelOperationOptions::default()
+    };
+
+    let output = driver
+        .generic_driver
+        .channel
+        .send_input("show clock", &options)
+        .unwrap();
+
+    assert_eq!(output, b"show clock");
+}
+
+#[test]
+fn ios_config_context_is_parsed_from_prompt() {
+    let (mut driver, inputs) = ios_network_driver(HashMap::new());
+
+    let response = driver
+        .send_configs(
+            &[
+                "interface e1",
+                " description uplink",
+                "router ospf 1",
+                " router-id 1.1.1.1",
+            ],
+            &return_to_root_options(),
+        )
+        .unwrap();
+
+    assert_eq!(
+        config_contexts(&response.responses),
+        vec!["config-if", "config-if", "config-router", "config-router"]
+    );
+    assert_eq!(driver.current_config_context(), "config-router");
+    assert_eq!(
+        non_empty_inputs(&inputs),
+        vec![
+            "configure terminal",
+            "interface e1",
+            " description uplink",
+            "exit",
+            "router ospf 1",
+            " router-id 1.1.1.1",
+        ]
+    );
+}
+
+#[test]
+fn ios_config_context_is_kept_without_return_to_root() {
+    let (mut driver, inputs) = ios_network_driver(HashMap::new());
+
+    driver
+        .send_configs(
+            &["interface e1", "router ospf 1"],
+            &OperationOptions::default(),
+        )
+        .unwrap();
+
+    assert!(!non_empty_inputs(&inputs).contains(&String::from("exit")));
+}
+
+#[test]
+fn junos_config_context_is_parsed_from_edit_banner() {
+    let (mut driver, inputs) = junos_network_driver();
+
+    let response = driver
+        .send_configs(
+            &[
+                "edit interfaces ge-0/0/0",
+                " set description uplink",
+                "edit protocols ospf",
+            ],
+            &return_to_root_options(),
+        )
+        .unwrap();
+
+    assert_eq!(
+        config_contexts(&response.responses),
+        vec![
+            "interfaces ge-0/0/0",
+            "interfaces ge-0/0/0",
+            "protocols ospf"
+        ]
+    );
+    assert_eq!(driver.current_config_context(), "protocols ospf");
+    assert_eq!(
+        non_empty_inputs(&inputs),
+        vec![
+            "configure",
+            "edit interfaces ge-0/0/0",
+            " set description uplink",
+            "exit",
+            "edit protocols ospf",
+        ]
+    );
+
+    driver.acquire_privilege_level("exec").unwrap();
+
+    assert_eq!(driver.current_config_context(), "");
+}
+
+#[test]
+fn sros_config_context_is_parsed_from_prompt() {
+    let (mut driver, inputs) = sros_network_driver();
+
+    let response = driver
+        .send_configs(
+            &["/configure router \"Base\"", "/configure system"],
+            &return_to_root_options(),
+        )
+        .unwrap();
+
+    assert_eq!(
+        config_contexts(&response.responses),
+        vec!["configure router \"Base\"", "configure system"]
+    );
+    assert_eq!(
+        non_empty_inputs(&inputs),
+        vec![
+            "edit-config exclusive",
+            "/configure router \"Base\"",
+            "exit",
+            "/configure system",
+        ]
+    );
+}
+
+/// Returns a responder for an IOS-like device that also has an unprivileged "exec" mode ("enable"
+/// and "disable" move between it and privilege exec) -- starting in privilege exec.
+fn ios_exec_device() -> Responder {
+    let mut enabled = true;
+    let mut privileged = ios_device(HashMap::new());
+
+    Box::new(move |line| match line.trim() {
+        "enable" => {
+            enabled = true;
+
+            device_output("", "router#")
+        }
+        "disable" => {
+            enabled = false;
+
+            device_output("", "router>")
+        }
+        _ if !enabled => device_output("", "router>"),
+        _ => privileged(line),
+    })
+}
+
+fn ios_exec_network_driver(responder: Responder) -> (NetworkDriver, Arc<Mutex<Vec<String>>>) {
+    let (builder, inputs) = mock_builder("router#", responder);
+
+    let mut driver = NetworkDriverBuilder::new(builder)
+        .privilege_levels(vec![
+            privilege_level("exec", r"(?m)^router>$", "", "", ""),
+            privilege_level(
+                "privilege-exec",
+                r"(?m)^router#$",
+                "exec",
+                "enable",
+                "disable",
+            ),
+            privilege_level(
+                "configuration",
+                r"(?m)^router\([\w\-]+\)#$",
+                "privilege-exec",
+                "configure terminal",
+                "end",
+            ),
+        ])
+        .default_desired_privilege_level("privilege-exec")
+        .build();
+
+    driver.open().unwrap();
+
+    inputs.lock().unwrap().clear();
+
+    (driver, inputs)
+}
+
+fn privilege_level_options(privilege_level: &str) -> OperationOptions {
+    OperationOptions {
+        privilege_level: privilege_level.to_owned(),
+        ..OperationOptions::default()
+    }
+}
+
+#[test]
+fn send_command_with_options_runs_in_the_given_privilege_level() {
+    let (mut driver, inputs) = ios_exec_network_driver(ios_exec_device());
+
+    driver
+        .send_command_with_options(
+            "do show ip interface brief",
+            &privilege_level_options("configuration"),
+        )
+        .unwrap();
+
+    assert_eq!(driver.current_privilege_level(), "configuration");
+    assert_eq!(
+        non_empty_inputs(&inputs),
+        ["configure terminal", "do show ip interface brief"]
+    );
+}
+
+#[test]
+fn send_command_returns_to_the_default_privilege_level_after_a_per_call_level() {
+    let (mut driver, inputs) = ios_exec_network_driver(ios_exec_device());
+
+    driver
+        .send_command_with_options("show version", &privilege_level_options("exec"))
+        .unwrap();
+
+    assert_eq!(driver.current_privilege_level(), "exec");
+
+    driver.send_command("show running-config").unwrap();
+
+    assert_eq!(driver.current_privilege_level(), "privilege-exec");
+    assert_eq!(
+        non_empty_inputs(&inputs),
+        ["disable", "show version", "enable", "show running-config"]
+    );
+}
+
+#[test]
+fn privilege_level_guard_restores_on_drop() {
+    let (mut driver, inputs) = ios_exec_network_driver(ios_exec_device());
+
+    {
+        let mut guard = driver.privilege_level_guard("configuration").unwrap();
+
+        guard.send_command("do show clock").unwrap();
+
+        assert_eq!(guard.current_privilege_level(), "configuration");
+    }
+
+    assert_eq!(driver.current_privilege_level(), "privilege-exec");
+
+    driver.send_command("show clock").unwrap();
+
+    assert_eq!(
+        non_empty_inputs(&inputs),
+        ["configure terminal", "do show clock", "end", "show clock"]
+    );
+}
+
+#[test]
+fn nested_privilege_level_guards_restore_their_own_levels() {
+    let (mut driver, inputs) = ios_exec_network_driver(ios_exec_device());
+
+    {
+        let mut outer = driver.privilege_level_guard("configuration").unwrap();
+
+        {
+            let mut inner = outer.privilege_level_guard("exec").unwrap();
+
+            inner.send_command("show version").unwrap();
+
+            assert_eq!(inner.current_privilege_level(), "exec");
+        }
+
+        assert_eq!(outer.current_privilege_level(), "configuration");
+
+        // the outer guard's level is the target again once the inner guard is gone
+        outer.send_command("do show clock").unwrap();
+
+        assert_eq!(outer.current_privilege_level(), "configuration");
+    }
+
+    assert_eq!(driver.current_privilege_level(), "privilege-exec");
+    assert_eq!(
+        non_empty_inputs(&inputs),
+        [
+            "configure terminal",
+            "end",
+            "disable",
+            "show version",
+            "enable",
+            "configure terminal",
+            "do show clock",
+            "end",
+        ]
+    );
+}
+
+#[test]
+fn privilege_level_guard_failed_restore_does_not_panic() {
+    // the device refuses to leave configuration mode, so the guard cannot restore privilege exec
+    let mut device = ios_exec_device();
+    let mut stuck = false;
+
+    let (mut driver, _) = ios_exec_network_driver(Box::new(move |line| {
+        if stuck && line.trim() == "end" {
+            return device_output("% Configuration locked", "router(config)#");
+        }
+
+        if line.trim() == "configure terminal" {
+            stuck = true;
+        }
+
+        device(line)
+    }));
+
+    {
+        let guard = driver.privilege_level_guard("configuration").unwrap();
+
+        assert_eq!(guard.current_privilege_level(), "configuration");
+    }
+
+    assert_eq!(driver.current_privilege_level(), "configuration");
+    assert!(driver.acquire_privilege_level("privilege-exec").is_err());
+}
+
+/// Returns a responder answering every line with the (shared, changeable) prompt.
+fn prompt_device(prompt: &Arc<Mutex<String>>) -> Responder {
+    let prompt = Arc::clone(prompt);
+
+    Box::new(move |_| device_output("", prompt.lock().unwrap().as_str()))
+}
+
+#[test]
+fn refresh_privilege_level_follows_the_device() {
+    let prompt = Arc::new(Mutex::new(String::from("router#")));
+
+    let (mut driver, _) = ios_exec_network_driver(prompt_device(&prompt));
+
+    driver.send_command("show clock").unwrap();
+
+    assert_eq!(driver.current_privilege_level(), "privilege-exec");
+
+    // the device changes mode behind the driver's back
+    "router(config-if)#".clone_into(&mut prompt.lock().unwrap());
+
+    assert_eq!(driver.current_privilege_level(), "privilege-exec");
+    assert_eq!(driver.refresh_privilege_level().unwrap(), "configuration");
+    assert_eq!(driver.current_privilege_level(), "configuration");
+    assert_eq!(driver.current_config_context(), "config-if");
+
+    "router>".clone_into(&mut prompt.lock().unwrap());
+
+    driver.refresh_privilege_level().unwrap();
+
+    assert_eq!(driver.current_privilege_level(), "exec");
+    assert_eq!(driver.current_config_context(), "");
+}
+
+#[test]
+fn refresh_privilege_level_rejects_unknown_prompt() {
+    let prompt = Arc::new(Mutex::new(String::from("router#")));
+
+    let (mut driver, _) = ios_exec_network_driver(prompt_device(&prompt));
+
+    driver.send_command("show clock").unwrap();
+
+    driver.generic_driver.channel.args.timeout_ops = Duration::from_millis(250);
+
+    "rommon 1 >".clone_into(&mut prompt.lock().unwrap());
+
+    assert!(driver.refresh_privilege_level().is_err());
+    assert_eq!(driver.current_privilege_level(), "privilege-exec");
+}
+
+#[test]
+fn on_error_runs_on_failed_send() {
+    let mut ios = ios_device(HashMap::new());
+
+    // the device never sends a prompt after "hang", so sending it times out
+    let (builder, _) = mock_builder(
+        "router#",
+        Box::new(move |line| {
+            if line == "hang" {
+                String::from("\r\n")
+            } else {
+                ios(line)
+            }
+        }),
+    );
+
+    let errors = Arc::new(Mutex::new(vec![]));
+    let hook_errors = Arc::clone(&errors);
+
+    let mut driver = NetworkDriverBuilder::new(builder.timeout_ops(Duration::from_millis(200)))
+        .privilege_levels(ios_privilege_levels())
+        .default_desired_privilege_level("privilege-exec")
+        .on_error(move |_, err| hook_errors.lock().unwrap().push(err.details.clone()))
+        .build();
+
+    driver.open().unwrap();
+    assert!(errors.lock().unwrap().is_empty(), "not run on success");
+
+    let Err(err) = driver.send_command("hang") else {
+        panic!("send without a prompt should time out");
+    };
+    assert_eq!(*errors.lock().unwrap(), [err.details]);
+
+    driver.close().unwrap();
+}
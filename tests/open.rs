@@ -0,0 +1,237 @@
+mod common;
+
+use common::MockTransport;
+use scraplirs::driver::GenericDriverBuilder;
+use scraplirs::errors::{
+    ErrorKind,
+    OpenPhase,
+};
+use std::sync::atomic::{
+    AtomicBool,
+    AtomicUsize,
+    Ordering,
+};
+use std::sync::{
+    Arc,
+    Mutex,
+};
+use std::thread;
+use std::time::{
+    Duration,
+    Instant,
+};
+
+/// Opens a driver against a telnet-ish mock device which answers the login with `login_output`,
+/// returning the open result, whether the transport was closed and whether the read loop kept
+/// reading from the transport after `open` returned.
+fn open_telnet(login_output: &'static str) -> (Result<(), ErrorKind>, bool, bool) {
+    open_telnet_platform("", login_output)
+}
+
+/// Like `open_telnet`, with the driver's `platform_type` set to `platform_type`.
+fn open_telnet_platform(
+    platform_type: &str,
+    login_output: &'static str,
+) -> (Result<(), ErrorKind>, bool, bool) {
+    let closed = Arc::new(AtomicBool::new(false));
+    let reads = Arc::new(AtomicUsize::new(0));
+
+    let factory_closed = Arc::clone(&closed);
+    let factory_reads = Arc::clone(&reads);
+
+    let mut driver = GenericDriverBuilder::new("router")
+        .timeout_ops(Duration::from_secs(5))
+        .read_delay(Duration::from_millis(1))
+        .platform_type(platform_type)
+        .transport_factory(move |args| {
+            MockTransport::new(
+                args,
+                "Username: ",
+                Box::new(move |line| match line {
+                    "admin" => String::from("\r\nPassword: "),
+                    _ => String::from(login_output),
+                }),
+                Arc::new(Mutex::new(vec![])),
+            )
+            .telnet_auth("admin", "secret")
+            .track(&factory_closed, &factory_reads)
+        })
+        .build();
+
+    let result = driver.open().map_err(|err| err.kind);
+
+    // give a still running read loop a chance to show itself
+    let reads_after_open = reads.load(Ordering::SeqCst);
+    thread::sleep(Duration::from_millis(50));
+    let read_loop_running = reads.load(Ordering::SeqCst) > reads_after_open;
+
+    (result, closed.load(Ordering::SeqCst), read_loop_running)
+}
+
+#[test]
+fn successful_open_leaves_transport_running() {
+    let (result, closed, read_loop_running) = open_telnet("\r\nrouter#");
+
+    assert_eq!(result, Ok(()));
+    assert!(!closed);
+    assert!(read_loop_running);
+}
+
+#[test]
+fn failed_authentication_closes_transport() {
+    let (result, closed, read_loop_running) = open_telnet("\r\nLogin incorrect\r\n");
+
+    assert_eq!(
+        result,
+        Err(ErrorKind::AuthenticationFailed {
+            output: String::from("Login incorrect")
+        })
+    );
+    assert!(closed);
+    assert!(!read_loop_running);
+}
+
+#[test]
+fn generic_authentication_error_closes_transport() {
+    // the user prompt coming back over and over is a (non fatal output) authentication failure
+    let (result, closed, read_loop_running) = open_telnet("\r\nUsername: ");
+
+    assert!(result.is_err());
+    assert!(closed);
+    assert!(!read_loop_running);
+}
+
+#[test]
+fn platform_auth_fatal_output_fails_authentication() {
+    let (result, closed, read_loop_running) = open_telnet_platform(
+        "cisco_iosxe",
+        "\r\n% Authentication failed\r\n\r\nUsername: ",
+    );
+
+    assert_eq!(
+        result,
+        Err(ErrorKind::AuthenticationFailed {
+            output: String::from("% Authentication failed")
+        })
+    );
+    assert!(closed);
+    assert!(!read_loop_running);
+}
+
+#[test]
+fn platform_auth_fatal_output_is_not_fatal_for_other_platforms() {
+    let (result, _, _) = open_telnet_platform(
+        "juniper_junos",
+        "\r\n% Authentication failed\r\n\r\nUsername: ",
+    );
+
+    assert!(result.is_err());
+    assert!(!matches!(
+        result,
+        Err(ErrorKind::AuthenticationFailed { .. })
+    ));
+}
+
+/// Returns a generic driver builder (open timeout of `timeout_open`) using a telnet-ish mock device
+/// which never answers the login, along with the flag recording if the transport was closed.
+fn silent_telnet_builder(timeout_open: Duration) -> (GenericDriverBuilder, Arc<AtomicBool>) {
+    let closed = Arc::new(AtomicBool::new(false));
+    let factory_closed = Arc::clone(&closed);
+
+    let builder = GenericDriverBuilder::new("router")
+        .timeout_ops(Duration::from_secs(5))
+        .timeout_open(timeout_open)
+        .read_delay(Duration::from_millis(1))
+        .transport_factory(move |args| {
+            MockTransport::new(
+                args,
+                "Username: ",
+                Box::new(|_| String::new()),
+                Arc::new(Mutex::new(vec![])),
+            )
+            .telnet_auth("admin", "secret")
+            .track(&factory_closed, &Arc::new(AtomicUsize::new(0)))
+        });
+
+    (builder, closed)
+}
+
+#[test]
+fn open_times_out_when_device_never_sends_prompt() {
+    let closed = Arc::new(AtomicBool::new(false));
+    let factory_closed = Arc::clone(&closed);
+
+    let mut driver = GenericDriverBuilder::new("router")
+        .auth_bypass(true)
+        .timeout_ops(Duration::from_secs(5))
+        .timeout_open(Duration::from_millis(250))
+        .read_delay(Duration::from_millis(1))
+        .post_auth(|driver| driver.get_prompt().map(|_| ()))
+        .transport_factory(move |args| {
+            MockTransport::new(
+                args,
+                "Welcome to the lab\r\n",
+                Box::new(|_| String::new()),
+                Arc::new(Mutex::new(vec![])),
+            )
+            .track(&factory_closed, &Arc::new(AtomicUsize::new(0)))
+        })
+        .build();
+
+    let started = Instant::now();
+    let result = driver.open().map_err(|err| err.kind);
+
+    assert_eq!(
+        result,
+        Err(ErrorKind::OpenTimedOut {
+            phase: OpenPhase::PromptDiscovery
+        })
+    );
+    assert!(started.elapsed() < Duration::from_secs(5));
+    assert!(closed.load(Ordering::SeqCst));
+}
+
+#[test]
+fn open_times_out_during_authentication() {
+    let (builder, closed) = silent_telnet_builder(Duration::from_millis(250));
+
+    let result = builder.build().open().map_err(|err| err.kind);
+
+    assert_eq!(
+        result,
+        Err(ErrorKind::OpenTimedOut {
+            phase: OpenPhase::Authentication
+        })
+    );
+    assert!(closed.load(Ordering::SeqCst));
+}
+
+#[test]
+fn open_cancelled_from_another_thread() {
+    let (builder, closed) = silent_telnet_builder(Duration::from_secs(30));
+
+    let mut driver = builder.build();
+    let cancel_handle = driver.cancel_handle();
+
+    let canceller = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(100));
+        cancel_handle.cancel();
+    });
+
+    let started = Instant::now();
+    let result = driver.open().map_err(|err| err.kind);
+
+    canceller.join().unwrap();
+
+    assert_eq!(
+        result,
+        Err(ErrorKind::OpenCancelled {
+            phase: OpenPhase::Authentication
+        })
+    );
+    assert!(started.elapsed() < Duration::from_secs(5));
+    assert!(closed.load(Ordering::SeqCst));
+
+    // the cancellation is consumed by the failed open
+    assert!(!driver.cancel_handle().is_cancelled());
+}
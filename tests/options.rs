@@ -0,0 +1,135 @@
+use scraplirs::options::DriverOptions;
+use scraplirs::transport::base::TransportType;
+use scraplirs::transport::exec::ExecPreset;
+use scraplirs::transport::serial::{
+    FlowControl,
+    Parity,
+};
+use std::time::Duration;
+
+const PROFILE: &str = r"
+host: router
+port: 2022
+user: admin
+password:
+  env: DEVICE_PASSWORD
+secondary-password:
+  value: enable-secret
+ssh-private-key-passphrase:
+  command:
+  - pass
+  - show
+  - router/key
+console-password:
+  file: /secrets/console
+platform-type: cisco_iosxe
+transport-type: serial
+prompt-pattern: ^\S+[>#]$
+timeout-ops: 1m 30s
+read-delay: 5ms
+failed-when-contains:
+- '% Invalid input'
+serial:
+  device: /dev/ttyUSB0
+  baud-rate: 115200
+  parity: even
+  flow-control: hardware
+exec:
+  preset: kubectl
+  target: router-0
+  command:
+  - /bin/bash
+tcp:
+  read-poll-interval: 20ms
+  tls: true
+  tls-server-name: router.example.com
+";
+
+#[test]
+fn yaml_round_trip() {
+    let options = DriverOptions::from_yaml(PROFILE).expect("profile should parse");
+    let yaml = options.to_yaml().expect("options should serialize");
+    let round_tripped = DriverOptions::from_yaml(&yaml).expect("serialized options should parse");
+
+    assert_eq!(
+        round_tripped.to_yaml().expect("options should serialize"),
+        yaml
+    );
+
+    assert_eq!(round_tripped.host, "router");
+    assert_eq!(round_tripped.port, Some(2022));
+    assert_eq!(round_tripped.transport_type, Some(TransportType::Serial));
+    assert_eq!(round_tripped.timeout_ops, Some(Duration::from_secs(90)));
+    assert_eq!(round_tripped.read_delay, Some(Duration::from_millis(5)));
+    assert_eq!(
+        round_tripped
+            .prompt_pattern
+            .as_ref()
+            .map(regex::bytes::Regex::as_str),
+        Some(r"^\S+[>#]$")
+    );
+
+    let serial = round_tripped.serial.expect("serial options should be set");
+    assert_eq!(serial.device.as_deref(), Some("/dev/ttyUSB0"));
+    assert_eq!(serial.baud_rate, Some(115_200));
+    assert_eq!(serial.parity, Some(Parity::Even));
+    assert_eq!(serial.flow_control, Some(FlowControl::Hardware));
+
+    let exec = round_tripped.exec.expect("exec options should be set");
+    assert_eq!(exec.preset, Some(ExecPreset::Kubectl));
+    assert_eq!(exec.command, Some(vec![String::from("/bin/bash")]));
+
+    let tcp = round_tripped.tcp.expect("tcp options should be set");
+    assert_eq!(tcp.read_poll_interval, Some(Duration::from_millis(20)));
+    assert_eq!(tcp.tls, Some(true));
+    assert_eq!(tcp.tls_server_name.as_deref(), Some("router.example.com"));
+}
+
+#[test]
+fn yaml_round_trip_keeps_credential_sources() {
+    let yaml = DriverOptions::from_yaml(PROFILE)
+        .expect("profile should parse")
+        .to_yaml()
+        .expect("options should serialize");
+
+    assert!(
+        yaml.contains("password:\n  env: DEVICE_PASSWORD\n"),
+        "{yaml}"
+    );
+    assert!(
+        yaml.contains("secondary-password:\n  value: enable-secret\n"),
+        "{yaml}"
+    );
+    assert!(
+        yaml.contains(
+            "ssh-private-key-passphrase:\n  command:\n  - pass\n  - show\n  - router/key\n"
+        ),
+        "{yaml}"
+    );
+    assert!(
+        yaml.contains("console-password:\n  file: /secrets/console\n"),
+        "{yaml}"
+    );
+}
+
+#[test]
+fn empty_options_serialize_only_the_host() {
+    let options = DriverOptions {
+        host: String::from("router"),
+        ..DriverOptions::default()
+    };
+
+    assert_eq!(
+        options.to_yaml().expect("options should serialize"),
+        "host: router\n"
+    );
+}
+
+#[test]
+fn unknown_options_are_rejected() {
+    let err = DriverOptions::from_yaml("host: router\nusername: admin\n")
+        .err()
+        .expect("unknown option should be rejected");
+
+    assert!(err.details.contains("username"), "{}", err.details);
+}
@@ -0,0 +1,270 @@
+use scraplirs::parsers::{
+    parse,
+    InterfaceStatus,
+    ShowVersion,
+};
+
+fn fixture(
+    platform_type: &str,
+    command: &str,
+) -> String {
+    let path = format!(
+        "{}/tests/fixtures/parsers/{platform_type}/{}.txt",
+        env!("CARGO_MANIFEST_DIR"),
+        command.replace(' ', "_")
+    );
+
+    std::fs::read_to_string(path).unwrap()
+}
+
+fn show_version(
+    platform_type: &str,
+    command: &str,
+) -> ShowVersion {
+    parse(
+        platform_type,
+        command,
+        fixture(platform_type, command).as_str(),
+    )
+    .unwrap()
+}
+
+fn interface_status(
+    platform_type: &str,
+    command: &str,
+) -> Vec<(String, String, String)> {
+    parse::<Vec<InterfaceStatus>>(
+        platform_type,
+        command,
+        fixture(platform_type, command).as_str(),
+    )
+    .unwrap()
+    .into_iter()
+    .map(|interface| {
+        (
+            interface.name,
+            interface.admin_status,
+            interface.oper_status,
+        )
+    })
+    .collect()
+}
+
+fn expected_show_version(
+    os_version: &str,
+    uptime: &str,
+    serial: &str,
+) -> ShowVersion {
+    ShowVersion {
+        os_version: os_version.to_owned(),
+        uptime: uptime.to_owned(),
+        serial: serial.to_owned(),
+    }
+}
+
+fn expected_interfaces(interfaces: &[(&str, &str, &str)]) -> Vec<(String, String, String)> {
+    interfaces
+        .iter()
+        .map(|&(name, admin_status, oper_status)| {
+            (
+                name.to_owned(),
+                admin_status.to_owned(),
+                oper_status.to_owned(),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn cisco_iosxe_show_version() {
+    assert_eq!(
+        show_version("cisco_iosxe", "show version"),
+        expected_show_version("17.03.01a", "2 hours, 13 minutes", "9FKLJWM5EB0")
+    );
+}
+
+#[test]
+fn cisco_iosxe_show_ip_interface_brief() {
+    assert_eq!(
+        interface_status("cisco_iosxe", "show ip interface brief"),
+        expected_interfaces(&[
+            ("GigabitEthernet1", "up", "up"),
+            ("GigabitEthernet2", "down", "down"),
+            ("GigabitEthernet3", "up", "down"),
+            ("Loopback0", "up", "up"),
+        ])
+    );
+}
+
+#[test]
+fn cisco_nxos_show_version() {
+    assert_eq!(
+        show_version("cisco_nxos", "show version"),
+        expected_show_version(
+            "9.3(3)",
+            "0 day(s), 3 hour(s), 49 minute(s), 16 second(s)",
+            "9N3KD63KWT0"
+        )
+    );
+}
+
+#[test]
+fn cisco_nxos_show_ip_interface_brief() {
+    // the vrf preamble line must not be mistaken for the column header
+    assert_eq!(
+        interface_status("cisco_nxos", "show ip interface brief"),
+        expected_interfaces(&[
+            ("Lo0", "up", "up"),
+            ("Eth1/1", "up", "down"),
+            ("Eth1/2", "down", "down"),
+        ])
+    );
+}
+
+#[test]
+fn cisco_iosxr_show_version() {
+    assert_eq!(
+        show_version("cisco_iosxr", "show version"),
+        expected_show_version("6.5.3", "1 day 2 hours 3 minutes", "")
+    );
+}
+
+#[test]
+fn cisco_iosxr_show_ip_interface_brief() {
+    assert_eq!(
+        interface_status("cisco_iosxr", "show ip interface brief"),
+        expected_interfaces(&[
+            ("Loopback0", "up", "up"),
+            ("MgmtEth0/RP0/CPU0/0", "up", "up"),
+            ("GigabitEthernet0/0/0/0", "down", "down"),
+            ("GigabitEthernet0/0/0/1", "up", "down"),
+        ])
+    );
+}
+
+#[test]
+fn arista_eos_show_version() {
+    assert_eq!(
+        show_version("arista_eos", "show version"),
+        expected_show_version(
+            "4.22.1F",
+            "0 weeks, 0 days, 3 hours and 21 minutes",
+            "5F3C3C9D1BCD92B5D6D1FD1E3FC1A2C3"
+        )
+    );
+}
+
+#[test]
+fn arista_eos_show_ip_interface_brief() {
+    assert_eq!(
+        interface_status("arista_eos", "show ip interface brief"),
+        expected_interfaces(&[
+            ("Ethernet1", "up", "up"),
+            ("Ethernet2", "down", "down"),
+            ("Loopback0", "up", "up"),
+            ("Management1", "up", "up"),
+        ])
+    );
+}
+
+#[test]
+fn juniper_junos_show_version() {
+    assert_eq!(
+        show_version("juniper_junos", "show version"),
+        expected_show_version("17.3R2.10", "", "")
+    );
+}
+
+#[test]
+fn juniper_junos_show_interfaces_terse() {
+    assert_eq!(
+        interface_status("juniper_junos", "show interfaces terse"),
+        expected_interfaces(&[
+            ("ge-0/0/0", "up", "up"),
+            ("ge-0/0/0.0", "up", "up"),
+            ("gr-0/0/0", "up", "up"),
+            ("ge-0/0/1", "down", "down"),
+            ("lo0.16384", "up", "up"),
+        ])
+    );
+}
+
+#[test]
+fn nokia_srl_show_version() {
+    assert_eq!(
+        show_version("nokia_srl", "show version"),
+        expected_show_version("v21.11.2", "2022-03-07T16:07:52.512Z", "Sim Serial No.")
+    );
+}
+
+#[test]
+fn nokia_srl_show_interface_brief() {
+    let interfaces = parse::<Vec<InterfaceStatus>>(
+        "nokia_srl",
+        "show interface brief",
+        fixture("nokia_srl", "show interface brief").as_str(),
+    )
+    .unwrap();
+
+    assert_eq!(
+        interfaces
+            .iter()
+            .map(|interface| {
+                (
+                    interface.name.as_str(),
+                    interface.admin_status.as_str(),
+                    interface.oper_status.as_str(),
+                    interface.description.as_str(),
+                )
+            })
+            .collect::<Vec<_>>(),
+        vec![
+            ("ethernet-1/1", "up", "up", "uplink"),
+            ("ethernet-1/2", "down", "down", ""),
+            ("mgmt0", "up", "up", ""),
+        ]
+    );
+}
+
+#[test]
+fn nokia_sros_show_version() {
+    assert_eq!(
+        show_version("nokia_sros", "show version"),
+        expected_show_version("B-22.10.R1", "", "")
+    );
+}
+
+#[test]
+fn nokia_sros_show_system_information() {
+    assert_eq!(
+        show_version("nokia_sros", "show system information"),
+        expected_show_version("B-22.10.R1", "0 days, 01:02:03.04", "")
+    );
+}
+
+#[test]
+fn nokia_sros_show_router_interface() {
+    assert_eq!(
+        interface_status("nokia_sros", "show router interface"),
+        expected_interfaces(&[
+            ("system", "up", "up"),
+            ("to-r2", "up", "down"),
+            ("unused", "down", "down"),
+        ])
+    );
+}
+
+#[test]
+fn show_version_without_os_version_errors() {
+    assert!(parse::<ShowVersion>("cisco_iosxe", "show version", "nothing useful\n").is_err());
+}
+
+#[test]
+fn interface_status_without_header_errors() {
+    assert!(parse::<Vec<InterfaceStatus>>(
+        "cisco_nxos",
+        "show ip interface brief",
+        "IP Interface Status for VRF \"default\"(1)\n"
+    )
+    .is_err());
+}
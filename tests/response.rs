@@ -0,0 +1,269 @@
+use regex::Regex;
+use scraplirs::parsers::ShowVersion;
+use scraplirs::response::diff::{
+    LineKind,
+    Options,
+};
+use scraplirs::response::{
+    FailurePattern,
+    FailureSeverity,
+    MultiResponse,
+    Response,
+};
+
+fn response(
+    input: &str,
+    result: &str,
+) -> Response {
+    let mut response = Response::new(
+        input,
+        "router",
+        22,
+        vec![String::from("% Invalid input detected")],
+    );
+
+    response.record(result.as_bytes().to_vec());
+
+    response
+}
+
+fn no_context_options() -> Options {
+    Options {
+        context_lines: 0,
+        ..Options::default()
+    }
+}
+
+#[test]
+fn diff_renders_unified_diff() {
+    let old = response("show run", "hostname r1\ninterface e1\n shutdown\n!\n");
+    let new = response("show run", "hostname r1\ninterface e1\n no shutdown\n!\n");
+
+    let diff = old.diff(&new, &Options::default());
+
+    assert!(diff.has_changes());
+    assert_eq!(
+        diff.to_string(),
+        "--- router: show run\n\
+         +++ router: show run\n\
+         @@ -1,4 +1,4 @@\n \
+         hostname r1\n \
+         interface e1\n\
+         - shutdown\n\
+         + no shutdown\n \
+         !\n"
+    );
+}
+
+#[test]
+fn diff_pure_insertion_starts_at_preceding_line() {
+    let old = response("show run", "a\nb\nc\n");
+    let new = response("show run", "a\nb\nc\nd\ne\n");
+
+    let diff = old.diff(&new, &no_context_options());
+
+    assert_eq!(diff.hunks.len(), 1);
+    assert_eq!(
+        (
+            diff.hunks[0].old_start,
+            diff.hunks[0].old_len,
+            diff.hunks[0].new_start,
+            diff.hunks[0].new_len
+        ),
+        (3, 0, 4, 2)
+    );
+    assert!(diff.to_string().contains("@@ -3,0 +4,2 @@\n+d\n+e\n"));
+}
+
+#[test]
+fn diff_pure_deletion_starts_at_preceding_line() {
+    let old = response("show run", "a\nb\nc\n");
+    let new = response("show run", "a\nc\n");
+
+    let diff = old.diff(&new, &no_context_options());
+
+    assert!(diff.to_string().contains("@@ -2,1 +1,0 @@\n-b\n"));
+}
+
+#[test]
+fn diff_insertion_into_empty_output() {
+    let old = response("show run", "");
+    let new = response("show run", "a\n");
+
+    let diff = old.diff(&new, &Options::default());
+
+    assert!(diff.to_string().contains("@@ -0,0 +1,1 @@\n+a\n"));
+}
+
+#[test]
+fn diff_masks_are_ignored() {
+    let old = response("show clock", "uptime 1 day\ncounter 10\n");
+    let new = response("show clock", "uptime 2 days\ncounter 42\n");
+
+    let options = Options {
+        masks: vec![
+            Regex::new(r"uptime .+").unwrap(),
+            Regex::new(r"\d+").unwrap(),
+        ],
+        ..Options::default()
+    };
+
+    assert!(!old.diff(&new, &options).has_changes());
+    assert!(old.diff(&new, &Options::default()).has_changes());
+}
+
+#[test]
+fn diff_serializes_for_reports() {
+    let old = response("show run", "a\n");
+    let new = response("show run", "b\n");
+
+    let value = serde_json::to_value(old.diff(&new, &Options::default())).unwrap();
+
+    assert_eq!(value["old-label"], "router: show run");
+    assert_eq!(value["hunks"][0]["old-start"], 1);
+    assert_eq!(value["hunks"][0]["lines"][0]["kind"], "delete");
+    assert_eq!(value["hunks"][0]["lines"][1]["kind"], "insert");
+    assert_eq!(value["hunks"][0]["lines"][1]["new-line-number"], 1);
+}
+
+#[test]
+fn multi_response_diff_pairs_responses() {
+    let mut old = MultiResponse::new("router");
+    old.record_response(response("show version", "v1\n"));
+
+    let mut new = MultiResponse::new("router");
+    new.record_response(response("show version", "v1\n"));
+    new.record_response(response("show clock", "12:00\n"));
+
+    let diff = old.diff(&new, &Options::default());
+
+    assert_eq!(diff.diffs.len(), 2);
+    assert!(!diff.diffs[0].has_changes());
+    assert_eq!(diff.diffs[1].old_label, "<no response>");
+    assert_eq!(diff.diffs[1].hunks[0].lines[0].kind, LineKind::Insert);
+    assert!(diff.has_changes());
+}
+
+#[test]
+fn raise_for_status_ok_when_not_failed() {
+    assert!(response("show version", "v1\n").raise_for_status().is_ok());
+}
+
+#[test]
+fn raise_for_status_reports_failing_line() {
+    let failed = response(
+        "show bogus",
+        "show bogus\n% Invalid input detected at '^' marker.\nrouter#",
+    );
+
+    let err = failed.raise_for_status().unwrap_err();
+
+    assert!(err.details.contains("input 'show bogus' on host 'router'"));
+    assert!(err
+        .details
+        .contains("in line '% Invalid input detected at '^' marker.'"));
+}
+
+#[test]
+fn raise_for_status_ignores_warnings() {
+    let mut warned = Response::new("show version", "router", 22, vec![]);
+    warned.failure_patterns = vec![FailurePattern::new(
+        Regex::new("deprecated").unwrap(),
+        FailureSeverity::Warning,
+    )];
+    warned.record(b"this command is deprecated\n".to_vec());
+
+    assert!(warned.warned());
+    assert!(!warned.failed);
+    assert!(warned.raise_for_status().is_ok());
+}
+
+#[test]
+fn multi_response_raise_for_status_returns_first_failure() {
+    let mut multi_response = MultiResponse::new("router");
+    multi_response.record_response(response("show version", "v1\n"));
+    multi_response.record_response(response("show bogus", "% Invalid input detected\n"));
+
+    assert!(multi_response.failed);
+    assert!(multi_response
+        .raise_for_status()
+        .unwrap_err()
+        .details
+        .contains("input 'show bogus'"));
+}
+
+#[test]
+fn json_skips_banner_and_prompt() {
+    let structured = response(
+        "show version | json",
+        "show version | json\n[some {banner}]\n{\"version\": \"4.30\", \"list\": [1, 2]}\nrouter#",
+    );
+
+    let value = structured.json().unwrap();
+
+    assert_eq!(value["version"], "4.30");
+    assert_eq!(value["list"][1], 2);
+}
+
+#[test]
+fn json_parses_top_level_array() {
+    let structured = response("show interfaces | json", "[{\"name\": \"e1\"}]\nrouter#");
+
+    assert_eq!(structured.json().unwrap()[0]["name"], "e1");
+}
+
+#[test]
+fn json_errors_without_payload() {
+    let err = response("show version | json", "no structured output here\n")
+        .json()
+        .unwrap_err();
+
+    assert!(err.details.contains("no structured output found"));
+}
+
+#[test]
+fn json_errors_on_invalid_payload() {
+    let err = response("show version | json", "{\"version\": \n")
+        .json()
+        .unwrap_err();
+
+    assert!(err.details.contains("failed parsing structured output"));
+}
+
+#[test]
+fn json_errors_on_failed_response() {
+    let failed = response("show version | json", "% Invalid input detected\n");
+
+    assert!(failed.json().is_err());
+}
+
+#[test]
+fn parse_uses_platform_parser() {
+    let mut show_version = response(
+        "  SHOW   version ",
+        "Arista vEOS\nSoftware image version: 4.30.1F\nSerial number: ABC123\nUptime: 1 \
+         hour\n",
+    );
+    show_version.platform_type = String::from("arista_eos");
+
+    assert_eq!(
+        show_version.parse::<ShowVersion>().unwrap(),
+        ShowVersion {
+            os_version: String::from("4.30.1F"),
+            uptime: String::from("1 hour"),
+            serial: String::from("ABC123"),
+        }
+    );
+}
+
+#[test]
+fn parse_errors_without_registered_parser() {
+    let mut show_version = response("show version", "whatever\n");
+    show_version.platform_type = String::from("not_a_platform");
+
+    assert!(show_version
+        .parse::<ShowVersion>()
+        .unwrap_err()
+        .details
+        .contains("no parser registered for platform type 'not_a_platform'"));
+}
@@ -0,0 +1,357 @@
+use nix::poll::{
+    poll,
+    PollFd,
+    PollFlags,
+};
+use nix::pty::openpty;
+use nix::sys::termios::{
+    tcgetattr,
+    ControlFlags,
+    InputFlags,
+};
+use nix::unistd::{
+    close,
+    ttyname,
+};
+use scraplirs::driver::GenericDriverBuilder;
+use scraplirs::transport::base::{
+    Transport,
+    TransportArgs,
+    TransportType,
+};
+use scraplirs::transport::serial::{
+    FlowControl,
+    Parity,
+    Serial,
+    SerialArgs,
+    StopBits,
+};
+use std::fs::File;
+use std::io::{
+    Read,
+    Write,
+};
+use std::os::unix::io::{
+    AsRawFd,
+    FromRawFd,
+    RawFd,
+};
+use std::sync::mpsc::{
+    channel,
+    Sender,
+};
+use std::thread;
+use std::time::{
+    Duration,
+    Instant,
+};
+
+/// A pty pair -- the transport opens the slave device by its path, the test plays the device on
+/// the master side.
+struct Pty {
+    master: RawFd,
+    slave: RawFd,
+    device: String,
+}
+
+impl Pty {
+    fn new() -> Self {
+        let pty = openpty(None, None).unwrap();
+        let device = ttyname(pty.slave).unwrap().to_string_lossy().into_owned();
+
+        Self {
+            master: pty.master,
+            slave: pty.slave,
+            device,
+        }
+    }
+
+    /// Returns the master side of the pair as a file, the pair then no longer owns the master.
+    fn take_master(&mut self) -> File {
+        let master = std::mem::replace(&mut self.master, -1);
+
+        unsafe { File::from_raw_fd(master) }
+    }
+
+    fn read_master(
+        &self,
+        want: usize,
+    ) -> Vec<u8> {
+        let mut master = unsafe { File::from_raw_fd(self.master) };
+        let output = read_at_least(&mut master, want, Duration::from_millis(500));
+
+        // the pair still owns the fd
+        std::mem::forget(master);
+
+        output
+    }
+}
+
+impl Drop for Pty {
+    fn drop(&mut self) {
+        if self.master >= 0 {
+            let _ = close(self.master);
+        }
+
+        let _ = close(self.slave);
+    }
+}
+
+/// Reads from `file` (a pty master, made non blocking by the poll) until `want` bytes were read
+/// or `timeout` passes.
+fn read_at_least(
+    file: &mut File,
+    want: usize,
+    timeout: Duration,
+) -> Vec<u8> {
+    let deadline = Instant::now() + timeout;
+    let mut output = vec![];
+    let mut buf = [0_u8; 256];
+
+    while output.len() < want && Instant::now() < deadline {
+        if poll(&mut [PollFd::new(file.as_raw_fd(), PollFlags::POLLIN)], 10).unwrap() == 1 {
+            let read_n = file.read(&mut buf).unwrap();
+
+            output.extend_from_slice(&buf[..read_n]);
+        }
+    }
+
+    output
+}
+
+fn open_serial(
+    pty: &Pty,
+    serial_args: SerialArgs,
+) -> Serial {
+    let mut serial = Serial::new(TransportArgs::new(pty.device.as_str()), serial_args);
+
+    serial.open().unwrap();
+
+    serial
+}
+
+#[test]
+fn line_settings_odd_parity_two_stop_bits_hardware_flow_control() {
+    let pty = Pty::new();
+
+    let mut serial = open_serial(
+        &pty,
+        SerialArgs {
+            parity: Parity::Odd,
+            stop_bits: StopBits::Two,
+            flow_control: FlowControl::Hardware,
+            ..SerialArgs::default()
+        },
+    );
+
+    let termios = tcgetattr(pty.slave).unwrap();
+
+    // the pty driver always clears PARENB (but keeps PARODD), parity enable is covered by the
+    // serial unit tests
+    assert!(termios.control_flags.contains(ControlFlags::PARODD));
+    assert!(termios.control_flags.contains(ControlFlags::CSTOPB));
+    assert!(termios.control_flags.contains(ControlFlags::CRTSCTS));
+    assert!(!termios
+        .input_flags
+        .intersects(InputFlags::IXON | InputFlags::IXOFF));
+
+    serial.close().unwrap();
+}
+
+#[test]
+fn line_settings_even_parity_software_flow_control() {
+    let pty = Pty::new();
+
+    let mut serial = open_serial(
+        &pty,
+        SerialArgs {
+            parity: Parity::Even,
+            flow_control: FlowControl::Software,
+            ..SerialArgs::default()
+        },
+    );
+
+    let termios = tcgetattr(pty.slave).unwrap();
+
+    assert!(!termios.control_flags.contains(ControlFlags::PARODD));
+    assert!(!termios.control_flags.contains(ControlFlags::CSTOPB));
+    assert!(!termios.control_flags.contains(ControlFlags::CRTSCTS));
+    assert!(termios
+        .input_flags
+        .contains(InputFlags::IXON | InputFlags::IXOFF));
+
+    serial.close().unwrap();
+}
+
+#[test]
+fn line_settings_default_and_restored_on_close() {
+    let pty = Pty::new();
+    let original = tcgetattr(pty.slave).unwrap();
+
+    let mut serial = open_serial(&pty, SerialArgs::default());
+
+    let termios = tcgetattr(pty.slave).unwrap();
+
+    assert!(!termios
+        .control_flags
+        .intersects(ControlFlags::PARODD | ControlFlags::CSTOPB | ControlFlags::CRTSCTS));
+    assert!(!termios
+        .input_flags
+        .intersects(InputFlags::IXON | InputFlags::IXOFF));
+
+    serial.close().unwrap();
+
+    let restored = tcgetattr(pty.slave).unwrap();
+
+    assert_eq!(restored.control_flags, original.control_flags);
+    assert_eq!(restored.input_flags, original.input_flags);
+    assert_eq!(restored.local_flags, original.local_flags);
+}
+
+#[test]
+fn wake_returns_are_written_on_open() {
+    let pty = Pty::new();
+
+    let mut serial = open_serial(
+        &pty,
+        SerialArgs {
+            wake_returns: 3,
+            ..SerialArgs::default()
+        },
+    );
+
+    assert_eq!(pty.read_master(4), b"\r\r\r");
+
+    serial.close().unwrap();
+}
+
+#[test]
+fn no_wake_returns() {
+    let pty = Pty::new();
+
+    let mut serial = open_serial(
+        &pty,
+        SerialArgs {
+            wake_returns: 0,
+            ..SerialArgs::default()
+        },
+    );
+
+    assert!(pty.read_master(1).is_empty());
+
+    serial.close().unwrap();
+}
+
+/// Plays a console on `master` -- the wake return gets a username prompt, then a password prompt,
+/// then the prompt if the credentials are right (and the login prompt again if not). Input is
+/// echoed like a terminal would. Returns every line the console received once stopped -- the
+/// master must stay open until the transport is done reading, closing it hangs up the tty.
+fn console(mut master: File) -> (Sender<()>, thread::JoinHandle<Vec<String>>) {
+    let (stop_sender, stop_receiver) = channel();
+
+    let console = thread::spawn(move || {
+        let mut lines = vec![];
+        let mut line = String::new();
+        // the user sent at the username prompt, none while waiting for the wake return
+        let mut user: Option<String> = None;
+        let mut logged_in = false;
+
+        while stop_receiver.try_recv().is_err() {
+            for b in read_at_least(&mut master, 1, Duration::from_millis(50)) {
+                let c = char::from(b);
+
+                if c != '\r' && c != '\n' {
+                    line.push(c);
+                    master.write_all(&[b]).unwrap();
+
+                    continue;
+                }
+
+                let input = std::mem::take(&mut line);
+                lines.push(input.clone());
+
+                let output = match (logged_in, user.as_deref()) {
+                    (true, _) if input == "show version" => "\r\nVersion 1.2.3\r\nrouter#",
+                    (true, _) => "\r\nrouter#",
+                    (false, None) if c == '\r' => {
+                        user = Some(String::new());
+
+                        "\r\nconsole ready\r\nUsername: "
+                    }
+                    (false, None) => "",
+                    (false, Some("")) => {
+                        user = Some(input);
+
+                        "\r\nPassword: "
+                    }
+                    (false, Some(user_sent)) => {
+                        if user_sent == "admin" && input == "secret" {
+                            logged_in = true;
+
+                            "\r\nrouter#"
+                        } else {
+                            user = Some(String::new());
+
+                            "\r\nLogin incorrect\r\nUsername: "
+                        }
+                    }
+                };
+
+                master.write_all(output.as_bytes()).unwrap();
+            }
+        }
+
+        lines
+    });
+
+    (stop_sender, console)
+}
+
+#[test]
+fn telnet_style_login() {
+    let mut pty = Pty::new();
+    let (stop_console, console) = console(pty.take_master());
+
+    let mut driver = GenericDriverBuilder::new(pty.device.as_str())
+        .transport_type(TransportType::Serial)
+        .user("admin")
+        .password("secret")
+        .timeout_ops(Duration::from_secs(5))
+        .build();
+
+    driver.open().unwrap();
+
+    let response = driver.send_command("show version").unwrap();
+
+    assert!(response.result.contains("Version 1.2.3"));
+
+    driver.close().unwrap();
+    stop_console.send(()).unwrap();
+
+    assert_eq!(
+        console.join().unwrap(),
+        ["", "admin", "secret", "show version"]
+    );
+}
+
+#[test]
+fn telnet_style_login_wrong_password() {
+    let mut pty = Pty::new();
+    let (stop_console, console) = console(pty.take_master());
+
+    let mut driver = GenericDriverBuilder::new(pty.device.as_str())
+        .transport_type(TransportType::Serial)
+        .user("admin")
+        .password("not-the-secret")
+        .timeout_ops(Duration::from_secs(5))
+        .build();
+
+    assert!(driver.open().is_err());
+
+    stop_console.send(()).unwrap();
+
+    assert_eq!(
+        console.join().unwrap()[..3],
+        ["", "admin", "not-the-secret"]
+    );
+}
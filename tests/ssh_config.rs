@@ -0,0 +1,308 @@
+mod common;
+
+use common::MockTransport;
+use scraplirs::driver::GenericDriverBuilder;
+use scraplirs::transport::base::{
+    HostKeyPolicy,
+    JumpHost,
+    TransportArgs,
+    TransportSSHArgs,
+};
+use scraplirs::transport::system::ssh_config::Config;
+use scraplirs::transport::system::{
+    SettingSource,
+    System,
+    SystemArgs,
+};
+use std::path::PathBuf;
+use std::sync::{
+    Arc,
+    Mutex,
+};
+use std::time::Duration;
+
+const SSH_CONFIG: &str = r#"
+# options before any block apply to every host
+ServerAliveInterval 15
+
+Host router1
+    HostName 10.0.0.1
+    User admin
+    Port 2222
+
+Host *.lab !bastion.lab
+    User labuser
+    IdentityFile ~/.ssh/lab
+    KexAlgorithms diffie-hellman-group14-sha1
+
+Host sw?
+    HostName %h.example.com
+    ConnectTimeout 5
+
+Match host "*.example.com" user netops
+    IdentityFile ~/.ssh/netops
+
+Match originalhost router1 !user admin
+    ProxyJump jumphost
+
+Host *
+    User fallback
+    IdentityFile ~/.ssh/id_rsa
+    StrictHostKeyChecking accept-new
+"#;
+
+fn config() -> Config {
+    Config::parse(SSH_CONFIG).unwrap()
+}
+
+#[test]
+fn host_block_options_apply_first_value_wins() {
+    let options = config().resolve("router1", "");
+
+    assert_eq!(options.get("hostname"), Some("10.0.0.1"));
+    // "User admin" is obtained first, the "Host *" user does not override it
+    assert_eq!(options.get("user"), Some("admin"));
+    assert_eq!(options.get("Port"), Some("2222"));
+    assert_eq!(options.get("serveraliveinterval"), Some("15"));
+    assert_eq!(options.get("proxyjump"), None);
+}
+
+#[test]
+fn glob_and_negated_host_patterns() {
+    let config = config();
+
+    let lab = config.resolve("leaf1.lab", "");
+    assert_eq!(lab.get("user"), Some("labuser"));
+    assert_eq!(
+        lab.get("kexalgorithms"),
+        Some("diffie-hellman-group14-sha1")
+    );
+
+    // negated pattern excludes the host even though "*.lab" matches
+    let bastion = config.resolve("bastion.lab", "");
+    assert_eq!(bastion.get("user"), Some("fallback"));
+    assert_eq!(bastion.get("kexalgorithms"), None);
+
+    // "?" matches exactly one character
+    assert_eq!(
+        config.resolve("sw1", "").get("hostname"),
+        Some("sw1.example.com")
+    );
+    assert_eq!(config.resolve("sw10", "").get("hostname"), None);
+}
+
+#[test]
+fn identity_files_accumulate() {
+    assert_eq!(
+        config().resolve("leaf1.lab", "").get_all("identityfile"),
+        ["~/.ssh/lab", "~/.ssh/id_rsa"]
+    );
+}
+
+#[test]
+fn match_host_uses_resolved_host_name() {
+    let options = config().resolve("sw1", "netops");
+
+    assert_eq!(
+        options.get_all("identityfile"),
+        ["~/.ssh/netops", "~/.ssh/id_rsa"]
+    );
+    assert!(!config()
+        .resolve("sw1", "someone")
+        .get_all("identityfile")
+        .contains(&String::from("~/.ssh/netops")));
+}
+
+#[test]
+fn match_negated_user_criterion() {
+    // the user from the config (admin) is used when no user is given, so the block does not apply
+    assert_eq!(config().resolve("router1", "").get("proxyjump"), None);
+    assert_eq!(
+        config().resolve("router1", "operator").get("proxyjump"),
+        Some("jumphost")
+    );
+}
+
+#[test]
+fn invalid_config_errors() {
+    assert!(Config::parse("Host\n").is_err());
+    assert!(Config::parse("Match user\n").is_err());
+}
+
+struct ConfigFile(PathBuf);
+
+impl ConfigFile {
+    fn new(name: &str) -> Self {
+        let path = std::env::temp_dir().join(format!(
+            "scraplirs-ssh-config-{}-{name}",
+            std::process::id()
+        ));
+
+        std::fs::write(&path, SSH_CONFIG).unwrap();
+
+        Self(path)
+    }
+}
+
+impl Drop for ConfigFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
+
+fn system(
+    args: TransportArgs,
+    mut ssh_args: TransportSSHArgs,
+    config_file: &ConfigFile,
+) -> System {
+    ssh_args.config_file_path = config_file.0.to_string_lossy().into_owned();
+
+    System::new(args, ssh_args, SystemArgs::default())
+}
+
+#[test]
+fn effective_settings_from_config_file() {
+    let config_file = ConfigFile::new("config-file");
+
+    let settings = system(
+        TransportArgs::new("router1"),
+        TransportSSHArgs::default(),
+        &config_file,
+    )
+    .effective_settings()
+    .unwrap();
+
+    assert_eq!(settings.host_name.value, "10.0.0.1");
+    assert_eq!(settings.host_name.source, SettingSource::ConfigFile);
+    assert_eq!(settings.user.value, "admin");
+    assert_eq!(settings.user.source, SettingSource::ConfigFile);
+    assert_eq!(settings.port.value, 2222);
+    assert_eq!(settings.port.source, SettingSource::ConfigFile);
+    assert_eq!(settings.server_alive_interval.value, 15);
+    assert_eq!(
+        settings.server_alive_interval.source,
+        SettingSource::ConfigFile
+    );
+    assert_eq!(settings.connect_timeout.source, SettingSource::Default);
+    assert_eq!(settings.identity_files.value, ["~/.ssh/id_rsa"]);
+    assert_eq!(settings.identity_files.source, SettingSource::ConfigFile);
+    assert_eq!(settings.proxy_jump.source, SettingSource::Default);
+    assert_eq!(settings.kex_algorithms.source, SettingSource::Default);
+}
+
+#[test]
+fn effective_settings_builder_values_win() {
+    let config_file = ConfigFile::new("builder");
+
+    let mut args = TransportArgs::new("leaf1.lab");
+    args.user = String::from("operator");
+    args.timeout_socket = Duration::from_secs(7);
+    args.timeout_socket_set = true;
+
+    let mut ssh_args = TransportSSHArgs::default();
+    ssh_args.private_key_path = String::from("/keys/operator");
+    ssh_args.host_key_policy = HostKeyPolicy::Insecure;
+    ssh_args.jump_hosts = vec![JumpHost::new("bastion.lab")];
+
+    let settings = system(args, ssh_args, &config_file)
+        .effective_settings()
+        .unwrap();
+
+    assert_eq!(settings.host_name.source, SettingSource::Builder);
+    assert_eq!(settings.user.value, "operator");
+    assert_eq!(settings.user.source, SettingSource::Builder);
+    assert_eq!(settings.port.value, 22);
+    assert_eq!(settings.port.source, SettingSource::Default);
+    assert_eq!(settings.server_alive_interval.value, 7);
+    assert_eq!(
+        settings.server_alive_interval.source,
+        SettingSource::Builder
+    );
+    assert_eq!(settings.strict_host_key_checking.value, "no");
+    assert_eq!(
+        settings.strict_host_key_checking.source,
+        SettingSource::Builder
+    );
+    assert_eq!(settings.user_known_hosts_file.value, "/dev/null");
+    // "-i" identity files are tried before the config file ones
+    assert_eq!(
+        settings.identity_files.value,
+        ["/keys/operator", "~/.ssh/lab", "~/.ssh/id_rsa"]
+    );
+    assert_eq!(settings.identity_files.source, SettingSource::Builder);
+    assert_eq!(settings.proxy_jump.value, "bastion.lab:22");
+    assert_eq!(settings.proxy_jump.source, SettingSource::Builder);
+    assert_eq!(settings.kex_algorithms.value, "diffie-hellman-group14-sha1");
+}
+
+#[test]
+fn effective_settings_explicit_default_port_wins() {
+    let config_file = ConfigFile::new("explicit-port");
+
+    let mut args = TransportArgs::new("router1");
+    args.port = 22;
+    args.port_set = true;
+
+    let settings = system(args, TransportSSHArgs::default(), &config_file)
+        .effective_settings()
+        .unwrap();
+
+    assert_eq!(settings.port.value, 22);
+    assert_eq!(settings.port.source, SettingSource::Builder);
+}
+
+#[test]
+fn effective_settings_without_config_file() {
+    let settings = System::new(
+        TransportArgs::new("router1"),
+        TransportSSHArgs::default(),
+        SystemArgs::default(),
+    )
+    .effective_settings()
+    .unwrap();
+
+    assert_eq!(settings.host_name.value, "router1");
+    assert_eq!(settings.port.source, SettingSource::Default);
+    assert_eq!(settings.user.source, SettingSource::Default);
+    assert!(settings.identity_files.value.is_empty());
+}
+
+#[test]
+fn builder_marks_explicit_transport_args() {
+    let explicit_flags = |builder: GenericDriverBuilder| {
+        let flags = Arc::new(Mutex::new(None));
+        let factory_flags = Arc::clone(&flags);
+
+        let driver = builder
+            .transport_factory(move |args: TransportArgs| {
+                *factory_flags.lock().unwrap() = Some((args.port_set, args.timeout_socket_set));
+
+                MockTransport::new(
+                    args,
+                    "",
+                    Box::new(|_| String::new()),
+                    Arc::new(Mutex::new(vec![])),
+                )
+            })
+            .build();
+
+        assert_eq!(driver.args.host, "router1");
+
+        let recorded_flags = *flags.lock().unwrap();
+
+        recorded_flags
+    };
+
+    assert_eq!(
+        explicit_flags(GenericDriverBuilder::new("router1")),
+        Some((false, false))
+    );
+    assert_eq!(
+        explicit_flags(
+            GenericDriverBuilder::new("router1")
+                .port(22)
+                .timeout_socket(Duration::from_secs(30))
+        ),
+        Some((true, true))
+    );
+}
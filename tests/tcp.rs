@@ -0,0 +1,269 @@
+use rustls::crypto::ring;
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::{
+    CertificateDer,
+    PrivateKeyDer,
+};
+use rustls::{
+    ServerConfig,
+    ServerConnection,
+    StreamOwned,
+};
+use scraplirs::driver::GenericDriverBuilder;
+use scraplirs::transport::base::{
+    Transport,
+    TransportArgs,
+    TransportType,
+};
+use scraplirs::transport::tcp::{
+    Tcp,
+    TcpArgs,
+};
+use std::io::{
+    Read,
+    Write,
+};
+use std::net::TcpListener;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+fn fixture(name: &str) -> String {
+    format!("{}/tests/fixtures/tls/{name}", env!("CARGO_MANIFEST_DIR"))
+}
+
+/// Returns a tls server config presenting the self-signed "localhost" certificate.
+fn server_config() -> Arc<ServerConfig> {
+    let certs = CertificateDer::pem_file_iter(fixture("cert.pem"))
+        .unwrap()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    let key = PrivateKeyDer::from_pem_file(fixture("key.pem")).unwrap();
+
+    Arc::new(
+        ServerConfig::builder_with_provider(Arc::new(ring::default_provider()))
+            .with_safe_default_protocol_versions()
+            .unwrap()
+            .with_no_client_auth()
+            .with_single_cert(certs, key)
+            .unwrap(),
+    )
+}
+
+/// Plays a device on `stream` -- the prompt is sent right away, input is echoed and each line is
+/// answered ("show version" with a version). Returns every line received once the peer is gone.
+fn device(mut stream: impl Read + Write) -> Vec<String> {
+    let mut lines = vec![];
+    let mut line = String::new();
+    let mut buf = [0_u8; 256];
+
+    if stream.write_all(b"router#").and(stream.flush()).is_err() {
+        return lines;
+    }
+
+    loop {
+        let read_n = match stream.read(&mut buf) {
+            Ok(0) | Err(_) => return lines,
+            Ok(read_n) => read_n,
+        };
+
+        for &b in &buf[..read_n] {
+            let output = if b == b'\n' {
+                let input = std::mem::take(&mut line);
+                let output = if input == "show version" {
+                    "\r\nVersion 1.2.3\r\nrouter#"
+                } else {
+                    "\r\nrouter#"
+                };
+
+                lines.push(input);
+
+                output.as_bytes().to_vec()
+            } else {
+                line.push(char::from(b));
+
+                vec![b]
+            };
+
+            if stream.write_all(&output).and(stream.flush()).is_err() {
+                return lines;
+            }
+        }
+    }
+}
+
+/// Listens on an ephemeral local port, serving a single connection (wrapped in tls if a server
+/// config is given). Returns the port and the handle returning the lines the device received.
+fn serve(tls: Option<Arc<ServerConfig>>) -> (u16, thread::JoinHandle<Vec<String>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+
+    let server = thread::spawn(move || {
+        let (socket, _) = listener.accept().unwrap();
+
+        match tls {
+            None => device(socket),
+            Some(config) => device(StreamOwned::new(
+                ServerConnection::new(config).unwrap(),
+                socket,
+            )),
+        }
+    });
+
+    (port, server)
+}
+
+fn tcp_builder(
+    port: u16,
+    tcp_args: TcpArgs,
+) -> GenericDriverBuilder {
+    GenericDriverBuilder::new("127.0.0.1")
+        .transport_type(TransportType::Tcp)
+        .port(port)
+        .tcp_read_poll_interval(tcp_args.read_poll_interval)
+        .tcp_tls(tcp_args.tls)
+        .tcp_tls_server_name(tcp_args.tls_server_name.as_str())
+        .tcp_tls_ca_file(tcp_args.tls_ca_file.as_str())
+        .tcp_tls_insecure(tcp_args.tls_insecure)
+        .timeout_socket(Duration::from_secs(2))
+        .timeout_ops(Duration::from_secs(5))
+}
+
+/// Opens a driver with the given tcp args against a local device, sends "show version" and
+/// returns the lines the device received.
+fn send_show_version(
+    tls: Option<Arc<ServerConfig>>,
+    tcp_args: TcpArgs,
+) -> Vec<String> {
+    let (port, server) = serve(tls);
+
+    let mut driver = tcp_builder(port, tcp_args).build();
+
+    driver.open().unwrap();
+
+    let response = driver.send_command("show version").unwrap();
+
+    assert!(response.result.contains("Version 1.2.3"));
+
+    driver.close().unwrap();
+
+    server.join().unwrap()
+}
+
+#[test]
+fn plain_tcp() {
+    let lines = send_show_version(None, TcpArgs::default());
+
+    assert!(lines.contains(&String::from("show version")));
+}
+
+#[test]
+fn plain_tcp_with_longer_read_poll_interval() {
+    let lines = send_show_version(
+        None,
+        TcpArgs {
+            read_poll_interval: Duration::from_millis(100),
+            ..TcpArgs::default()
+        },
+    );
+
+    assert!(lines.contains(&String::from("show version")));
+}
+
+#[test]
+fn tls_self_signed_accepted_via_ca_file() {
+    let lines = send_show_version(
+        Some(server_config()),
+        TcpArgs {
+            tls: true,
+            tls_server_name: String::from("localhost"),
+            tls_ca_file: fixture("cert.pem"),
+            ..TcpArgs::default()
+        },
+    );
+
+    assert!(lines.contains(&String::from("show version")));
+}
+
+#[test]
+fn tls_ca_file_checks_server_name() {
+    // the certificate is for "localhost" (and 127.0.0.1) only
+    let (port, _server) = serve(Some(server_config()));
+
+    let mut tcp = Tcp::new(
+        TransportArgs {
+            port,
+            ..TransportArgs::new("127.0.0.1")
+        },
+        TcpArgs {
+            tls: true,
+            tls_server_name: String::from("router.example.com"),
+            tls_ca_file: fixture("cert.pem"),
+            ..TcpArgs::default()
+        },
+    );
+
+    let err = tcp.open().unwrap_err();
+
+    assert!(err.details.contains("failed completing tls handshake"));
+}
+
+#[test]
+fn tls_self_signed_rejected_by_default_roots() {
+    let (port, _server) = serve(Some(server_config()));
+
+    let mut tcp = Tcp::new(
+        TransportArgs {
+            port,
+            ..TransportArgs::new("127.0.0.1")
+        },
+        TcpArgs {
+            tls: true,
+            tls_server_name: String::from("localhost"),
+            ..TcpArgs::default()
+        },
+    );
+
+    let err = tcp.open().unwrap_err();
+
+    assert!(err.details.contains("failed completing tls handshake"));
+    assert!(err.details.to_lowercase().contains("certificate"));
+}
+
+#[test]
+fn tls_self_signed_accepted_when_insecure() {
+    // no server name either, the ip is used and not verified
+    let lines = send_show_version(
+        Some(server_config()),
+        TcpArgs {
+            tls: true,
+            tls_insecure: true,
+            ..TcpArgs::default()
+        },
+    );
+
+    assert!(lines.contains(&String::from("show version")));
+}
+
+#[test]
+fn tls_ca_file_without_certificates_errors() {
+    let (port, _server) = serve(Some(server_config()));
+
+    let mut tcp = Tcp::new(
+        TransportArgs {
+            port,
+            ..TransportArgs::new("127.0.0.1")
+        },
+        TcpArgs {
+            tls: true,
+            tls_ca_file: fixture("key.pem"),
+            ..TcpArgs::default()
+        },
+    );
+
+    assert!(tcp
+        .open()
+        .unwrap_err()
+        .details
+        .contains("contains no certificates"));
+}